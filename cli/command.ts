@@ -2,28 +2,43 @@ import { program } from 'commander';
 import { Cluster, PublicKey } from '@solana/web3.js';
 import {
   changeRole,
+  closeCollection,
+  closeUser,
   deposit,
+  depositFor,
   finalizeDeposit,
+  getAllCollections,
   getAllDeposits,
   getAllRoles,
   getCollectionInfo,
   getDepositInfo,
   getGlobalInfo,
   getUserRole,
+  getWeb2UserDeposits,
   initProject,
   initUser,
   loadWalletFromKeypair,
+  migrate,
   registerCollection,
+  registerDepositDelegate,
+  registerMint,
   revokeCollection,
+  revokeDepositDelegate,
+  revokeMint,
+  setCollectionMetadata,
   setConnection,
+  setDepositorMerkleRoot,
+  setDepositQuota,
+  setMintMerkleRoot,
   setTreasury,
   transferAuthority,
+  transferDeposit,
   updateDeposit,
   withdrawOwner,
   withdrawTreasury,
 } from './scripts';
 import { IFindDepositFilter } from '../lib/scripts';
-import { DepositStatus } from '../lib/types';
+import { DepositStatus, LockReason } from '../lib/types';
 
 program.version('0.0.1');
 
@@ -74,6 +89,21 @@ programCommand('transfer_authority')
     await transferAuthority(new PublicKey(address));
   });
 
+programCommand('migrate')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await migrate();
+  });
+
 programCommand('init_user')
   .requiredOption('-a --address <string>', 'Address of user')
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
@@ -90,6 +120,62 @@ programCommand('init_user')
     await initUser(new PublicKey(address));
   });
 
+programCommand('close_user')
+  .option('-a --address <string>', 'Address of user, signer by default')
+  .option('-t --recipient <string>', 'Receiver of the rent, signer by default')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, recipient, env, keypair, rpc, fm } = cmd.opts();
+
+    const wallet = loadWalletFromKeypair(keypair);
+    await setConnection({
+      cluster: env as Cluster,
+      wallet,
+      rpc,
+      fm,
+    });
+
+    await closeUser(
+      address ? new PublicKey(address) : wallet.publicKey,
+      recipient ? new PublicKey(recipient) : wallet.publicKey
+    );
+  });
+
+programCommand('register_delegate')
+  .requiredOption(
+    '-a --address <string>',
+    'Address allowed to deposit for signer'
+  )
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await registerDepositDelegate(new PublicKey(address));
+  });
+
+programCommand('revoke_delegate')
+  .requiredOption('-a --address <string>', 'Address of the revoked delegate')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await revokeDepositDelegate(new PublicKey(address));
+  });
+
 programCommand('set_role')
   .requiredOption('-a, --address <string>', 'Address of user')
   .option('-m, --admin <number>', 'Admin authority (1 - Assign / 0 - Revoke)')
@@ -180,6 +266,122 @@ programCommand('revoke_collection')
     await revokeCollection(new PublicKey(address));
   });
 
+programCommand('close_collection')
+  .requiredOption('-a --address <string>', 'Address of collection')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await closeCollection(new PublicKey(address));
+  });
+
+programCommand('register_mint')
+  .requiredOption('-m --mint <string>', 'Address of Nft')
+  .requiredOption('-a --address <string>', 'Address of collection')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { mint, address, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await registerMint(new PublicKey(mint), new PublicKey(address));
+  });
+
+programCommand('revoke_mint')
+  .requiredOption('-m --mint <string>', 'Address of Nft')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { mint, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await revokeMint(new PublicKey(mint));
+  });
+
+programCommand('set_mint_merkle_root')
+  .requiredOption('-a --address <string>', 'Address of collection')
+  .option('-o --root <string>', 'Merkle root of allowed mints in hex (32 bytes)')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, root, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    const mintMerkleRoot = root
+      ? Array.from(Buffer.from(root, 'hex'))
+      : new Array(32).fill(0);
+
+    await setMintMerkleRoot(new PublicKey(address), mintMerkleRoot);
+  });
+
+programCommand('set_depositor_merkle_root')
+  .option('-a --address <string>', 'Address of collection, global root if omitted')
+  .option('-o --root <string>', 'Merkle root of approved depositors in hex (32 bytes)')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, root, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    const depositorMerkleRoot = root
+      ? Array.from(Buffer.from(root, 'hex'))
+      : new Array(32).fill(0);
+
+    await setDepositorMerkleRoot(
+      address ? new PublicKey(address) : null,
+      depositorMerkleRoot
+    );
+  });
+
+programCommand('set_deposit_quota')
+  .option('-a --address <string>', 'Address of collection, global quota if omitted')
+  .option('-c --concurrent <number>', 'Max active deposits per user, 0 unlimited', '0')
+  .option('-d --daily <number>', 'Max deposits per user per day, 0 unlimited', '0')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, concurrent, daily, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await setDepositQuota(
+      address ? new PublicKey(address) : null,
+      parseInt(concurrent),
+      parseInt(daily)
+    );
+  });
+
 programCommand('collection_status')
   .requiredOption('-a --address <string>', 'Address of collection')
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
@@ -196,12 +398,63 @@ programCommand('collection_status')
     await getCollectionInfo(new PublicKey(address));
   });
 
+programCommand('get_collections')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await getAllCollections();
+  });
+
+programCommand('set_collection_metadata')
+  .requiredOption('-a --address <string>', 'Address of collection')
+  .requiredOption('-n --name <string>', 'Display name of collection')
+  .requiredOption('-u --uri <string>', 'Display metadata uri of collection')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { address, name, uri, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await setCollectionMetadata(new PublicKey(address), name, uri);
+  });
+
 programCommand('create_deposit')
   .requiredOption('-m --mint <string>', 'Address of Nft')
   .requiredOption('-u --userid <string>', 'uuid of User')
+  .option('-t --receipt', 'Mint custody receipt token to the depositor')
+  .option('-c --collection <string>', 'Collection the mint proof is for')
+  .option('-p --proof <string>', 'Comma separated mint merkle proof in hex')
+  .option(
+    '-d --depositor-proof <string>',
+    'Comma separated depositor merkle proof in hex'
+  )
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { mint, userid, env, keypair, rpc, fm } = cmd.opts();
+    const {
+      mint,
+      userid,
+      receipt,
+      collection,
+      proof,
+      depositorProof,
+      env,
+      keypair,
+      rpc,
+      fm,
+    } = cmd.opts();
 
     await setConnection({
       cluster: env as Cluster,
@@ -210,7 +463,54 @@ programCommand('create_deposit')
       fm,
     });
 
-    await deposit(new PublicKey(mint), userid);
+    const mintProof =
+      collection && proof
+        ? {
+            collection: new PublicKey(collection),
+            proof: proof
+              .split(',')
+              .map((node: string) => Array.from(Buffer.from(node, 'hex'))),
+          }
+        : null;
+
+    const depositorMerkleProof = depositorProof
+      ? depositorProof
+          .split(',')
+          .map((node: string) => Array.from(Buffer.from(node, 'hex')))
+      : null;
+
+    await deposit(
+      new PublicKey(mint),
+      userid,
+      !!receipt,
+      mintProof,
+      depositorMerkleProof
+    );
+  });
+
+programCommand('create_deposit_for')
+  .requiredOption('-b --beneficiary <string>', 'Address owning the deposit')
+  .requiredOption('-m --mint <string>', 'Address of Nft')
+  .requiredOption('-u --userid <string>', 'uuid of User')
+  .option('-t --receipt', 'Mint custody receipt token to the beneficiary')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { beneficiary, mint, userid, receipt, env, keypair, rpc, fm } =
+      cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await depositFor(
+      new PublicKey(beneficiary),
+      new PublicKey(mint),
+      userid,
+      !!receipt
+    );
   });
 
 programCommand('get_deposits')
@@ -221,9 +521,11 @@ programCommand('get_deposits')
   .option('-l --locked <number>', 'Filter 1/0 Locked/unlocked PDA')
   .option('-s --status <number>', 'Filter by status of PDA')
   .option('-m --mint <string>', 'Filter by deposited nft mint')
+  .option('-u --userid <string>', 'Active deposits of web2 user uuid')
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { mint, address, locked, status, env, keypair, rpc, fm } = cmd.opts();
+    const { mint, userid, address, locked, status, env, keypair, rpc, fm } =
+      cmd.opts();
 
     await setConnection({
       cluster: env as Cluster,
@@ -233,6 +535,7 @@ programCommand('get_deposits')
     });
 
     if (mint) await getDepositInfo(new PublicKey(mint));
+    else if (userid) await getWeb2UserDeposits(userid);
     else {
       let filters: IFindDepositFilter = {};
       if (address) filters.owner = new PublicKey(address);
@@ -246,9 +549,12 @@ programCommand('update_deposit')
   .requiredOption('-a --pda <string>', 'Address of deposit PDA')
   .option('-l --locked <number>', '1/0 Locked/unlocked')
   .option('-s --status <number>', 'deposit status')
+  .option('-o --reason <number>', 'lock reason code')
+  .option('-x --until <number>', 'lock expiry unix timestamp (0 - no expiry)')
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { pda, locked, status, env, keypair, rpc, fm } = cmd.opts();
+    const { pda, locked, status, reason, until, env, keypair, rpc, fm } =
+      cmd.opts();
 
     await setConnection({
       cluster: env as Cluster,
@@ -260,7 +566,9 @@ programCommand('update_deposit')
     await updateDeposit(
       new PublicKey(pda),
       locked === undefined ? null : Number(locked) === 1,
-      status === undefined ? null : (Number(status) as DepositStatus)
+      status === undefined ? null : (Number(status) as DepositStatus),
+      reason === undefined ? null : (Number(reason) as LockReason),
+      until === undefined ? null : Number(until)
     );
   });
 
@@ -296,11 +604,30 @@ programCommand('withdraw_to_owner')
     await withdrawOwner(new PublicKey(pda));
   });
 
+programCommand('transfer_deposit')
+  .requiredOption('-a --pda <string>', 'Address of deposit PDA')
+  .requiredOption('-n --newowner <string>', 'Wallet of new owner')
+  .requiredOption('-u --userid <string>', 'uuid of new User')
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { pda, newowner, userid, env, keypair, rpc, fm } = cmd.opts();
+
+    await setConnection({
+      cluster: env as Cluster,
+      wallet: loadWalletFromKeypair(keypair),
+      rpc,
+      fm,
+    });
+
+    await transferDeposit(new PublicKey(pda), new PublicKey(newowner), userid);
+  });
+
 programCommand('finalize_deposit')
   .requiredOption('-a --pda <string>', 'Address of deposit PDA')
+  .option('-p --proof <string>', 'Delivery proof hash in hex (32 bytes)')
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { pda, env, keypair, rpc, fm } = cmd.opts();
+    const { pda, proof, env, keypair, rpc, fm } = cmd.opts();
 
     await setConnection({
       cluster: env as Cluster,
@@ -309,7 +636,11 @@ programCommand('finalize_deposit')
       fm,
     });
 
-    await finalizeDeposit(new PublicKey(pda));
+    const proofHash = proof
+      ? Array.from(Buffer.from(proof, 'hex'))
+      : new Array(32).fill(0);
+
+    await finalizeDeposit(new PublicKey(pda), proofHash);
   });
 
 function programCommand(name: string) {
@@ -13,19 +13,42 @@ import {
 
 import { IDL } from '../target/types/shipment_sc';
 import {
+  changeCollectionMetadataIx,
+  changeMintMerkleRootIx,
+  changeDepositorMerkleRootIx,
+  changeCollectionDepositorMerkleRootIx,
+  changeDepositQuotaIx,
+  changeCollectionDepositQuotaIx,
   changeTreasuryIx,
   createChangeRoleIx,
+  createCloseUserIx,
+  createCloseCollectionIx,
+  createDepositNftForTx,
   createDepositNftTx,
   createFinalizeDepositIx,
   createInitializeIx,
+  createCloseLegacyDepositIx,
   createInitUserIx,
+  createMigrateCollectionPoolIx,
+  createMigrateGlobalPoolIx,
+  createMigrateNftDepositIx,
+  createMigrateUserPoolIx,
   createRegisterCollectionIx,
+  createRegisterDepositDelegateIx,
+  createRegisterMintIx,
   createRevokeCollectionIx,
+  createRevokeDepositDelegateIx,
+  createRevokeMintIx,
+  createTransferDepositOwnershipIx,
   createUpdateDepositIx,
   createWithdrawOwnerIx,
   createWithdrawTreasuryIx,
+  findAllCollections,
   findAllDeposits,
   findAllUserPools,
+  findDepositCollection,
+  findLegacyAccounts,
+  findWeb2UserDeposits,
   getCollectionPoolState,
   getGlobalState,
   getNftDepositState,
@@ -33,7 +56,7 @@ import {
   IFindDepositFilter,
   transferSuperAdminIx,
 } from '../lib/scripts';
-import { DepositStatus } from '../lib/types';
+import { DepositStatus, LockReason, MintProof } from '../lib/types';
 
 interface ISetConnectionParams {
   cluster: web3.Cluster; // env from CLI global params
@@ -175,6 +198,72 @@ export const initUser = async (user: PublicKey) => {
   }
 };
 
+export const closeUser = async (user: PublicKey, recipient: PublicKey) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createCloseUserIx(payer.publicKey, user, recipient, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const registerDepositDelegate = async (delegate: PublicKey) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createRegisterDepositDelegateIx(payer.publicKey, delegate, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const revokeDepositDelegate = async (delegate: PublicKey) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createRevokeDepositDelegateIx(payer.publicKey, delegate, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
 export const changeRole = async (
   user: PublicKey,
   admin: boolean | null,
@@ -267,14 +356,50 @@ export const revokeCollection = async (collection: PublicKey) => {
   }
 };
 
-export const deposit = async (mint: PublicKey, userId: string) => {
+export const deposit = async (
+  mint: PublicKey,
+  userId: string,
+  withReceipt = false,
+  mintProof: MintProof | null = null,
+  depositorProof: number[][] | null = null
+) => {
   try {
     const tx = await createDepositNftTx(
       payer as Wallet,
       userId,
       mint,
       program,
-      solConnection
+      solConnection,
+      withReceipt,
+      mintProof,
+      depositorProof
+    );
+
+    await addAdminSignAndConfirm(tx);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const depositFor = async (
+  beneficiary: PublicKey,
+  mint: PublicKey,
+  userId: string,
+  withReceipt = false,
+  mintProof: MintProof | null = null,
+  depositorProof: number[][] | null = null
+) => {
+  try {
+    const tx = await createDepositNftForTx(
+      payer as Wallet,
+      beneficiary,
+      userId,
+      mint,
+      program,
+      solConnection,
+      withReceipt,
+      mintProof,
+      depositorProof
     );
 
     await addAdminSignAndConfirm(tx);
@@ -286,12 +411,22 @@ export const deposit = async (mint: PublicKey, userId: string) => {
 export const updateDeposit = async (
   pda: PublicKey,
   locked: boolean | null,
-  status: DepositStatus | null
+  status: DepositStatus | null,
+  lockReason: LockReason | null,
+  lockedUntil: number | null
 ) => {
   try {
     const tx = new Transaction().add(
       ...getGasIxs(),
-      await createUpdateDepositIx(payer.publicKey, pda, status, locked, program)
+      await createUpdateDepositIx(
+        payer.publicKey,
+        pda,
+        status,
+        locked,
+        lockReason,
+        lockedUntil,
+        program
+      )
     );
     const { blockhash } = await solConnection.getLatestBlockhash();
     tx.recentBlockhash = blockhash;
@@ -331,6 +466,39 @@ export const withdrawOwner = async (pda: PublicKey) => {
   }
 };
 
+export const transferDeposit = async (
+  pda: PublicKey,
+  newOwner: PublicKey,
+  newUserId: string
+) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createTransferDepositOwnershipIx(
+        payer.publicKey,
+        pda,
+        newOwner,
+        newUserId,
+        null,
+        program
+      )
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
 export const withdrawTreasury = async (pda: PublicKey) => {
   try {
     const tx = new Transaction().add(
@@ -353,11 +521,214 @@ export const withdrawTreasury = async (pda: PublicKey) => {
   }
 };
 
-export const finalizeDeposit = async (pda: PublicKey) => {
+export const finalizeDeposit = async (pda: PublicKey, proofHash: number[]) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createFinalizeDepositIx(payer.publicKey, pda, proofHash, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const closeCollection = async (collection: PublicKey) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createCloseCollectionIx(payer.publicKey, collection, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const registerMint = async (mint: PublicKey, collection: PublicKey) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createRegisterMintIx(payer.publicKey, mint, collection, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const revokeMint = async (mint: PublicKey) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await createRevokeMintIx(payer.publicKey, mint, program)
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const setMintMerkleRoot = async (
+  collection: PublicKey,
+  mintMerkleRoot: number[]
+) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      await changeMintMerkleRootIx(
+        payer.publicKey,
+        collection,
+        mintMerkleRoot,
+        program
+      )
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const setDepositorMerkleRoot = async (
+  collection: PublicKey | null,
+  depositorMerkleRoot: number[]
+) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      collection
+        ? await changeCollectionDepositorMerkleRootIx(
+            payer.publicKey,
+            collection,
+            depositorMerkleRoot,
+            program
+          )
+        : await changeDepositorMerkleRootIx(
+            payer.publicKey,
+            depositorMerkleRoot,
+            program
+          )
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const setDepositQuota = async (
+  collection: PublicKey | null,
+  maxConcurrentDeposits: number,
+  maxDailyDeposits: number
+) => {
+  try {
+    const tx = new Transaction().add(
+      ...getGasIxs(),
+      collection
+        ? await changeCollectionDepositQuotaIx(
+            payer.publicKey,
+            collection,
+            maxConcurrentDeposits,
+            maxDailyDeposits,
+            program
+          )
+        : await changeDepositQuotaIx(
+            payer.publicKey,
+            maxConcurrentDeposits,
+            maxDailyDeposits,
+            program
+          )
+    );
+    const { blockhash } = await solConnection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = payer.publicKey;
+
+    payer.signTransaction(tx);
+
+    const txId = await provider.sendAndConfirm(tx, [], {
+      commitment: 'confirmed',
+    });
+
+    console.log('txHash: ', txId);
+  } catch (e) {
+    console.log(e);
+  }
+};
+
+export const setCollectionMetadata = async (
+  collection: PublicKey,
+  name: string,
+  uri: string
+) => {
   try {
     const tx = new Transaction().add(
       ...getGasIxs(),
-      await createFinalizeDepositIx(payer.publicKey, pda, program)
+      await changeCollectionMetadataIx(
+        payer.publicKey,
+        collection,
+        name,
+        uri,
+        program
+      )
     );
     const { blockhash } = await solConnection.getLatestBlockhash();
     tx.recentBlockhash = blockhash;
@@ -375,6 +746,72 @@ export const finalizeDeposit = async (pda: PublicKey) => {
   }
 };
 
+/**
+ * Move every account left in the legacy layout by a program upgrade
+ * the global pool goes first as the other migrations read it, collections before their deposits
+ */
+export const migrate = async () => {
+  const send = async (ix: web3.TransactionInstruction) => {
+    try {
+      const tx = new Transaction().add(...getGasIxs(), ix);
+      const { blockhash } = await solConnection.getLatestBlockhash();
+      tx.recentBlockhash = blockhash;
+      tx.feePayer = payer.publicKey;
+
+      payer.signTransaction(tx);
+
+      const txId = await provider.sendAndConfirm(tx, [], {
+        commitment: 'confirmed',
+      });
+
+      console.log('txHash: ', txId);
+    } catch (e) {
+      console.log(e);
+    }
+  };
+
+  const legacy = await findLegacyAccounts(program);
+  console.log(
+    `legacy accounts: global ${legacy.globalPool}, ${legacy.users.length} users, ${legacy.collections.length} collections, ${legacy.deposits.length} deposits`
+  );
+
+  if (legacy.globalPool) {
+    await send(await createMigrateGlobalPoolIx(payer.publicKey, program));
+  }
+  for (const user of legacy.users) {
+    await send(await createMigrateUserPoolIx(payer.publicKey, user, program));
+  }
+  // One at a time, each one reads the collection count the previous one bumped
+  for (const collection of legacy.collections) {
+    await send(
+      await createMigrateCollectionPoolIx(payer.publicKey, collection, program)
+    );
+  }
+  // Live deposits stay in custody, withdrawn ones give their rent back
+  for (const { owner, mint, status, user } of legacy.deposits) {
+    if (status === 0) {
+      await send(
+        await createCloseLegacyDepositIx(payer.publicKey, owner, mint, program)
+      );
+      continue;
+    }
+    const { collection } = await findDepositCollection(
+      mint,
+      program,
+      solConnection
+    );
+    await send(
+      await createMigrateNftDepositIx(
+        payer.publicKey,
+        mint,
+        collection,
+        user,
+        program
+      )
+    );
+  }
+};
+
 export const getGlobalInfo = async () => {
   const globalState = await getGlobalState(program);
   console.log('global state: ', globalState);
@@ -395,11 +832,24 @@ export const getCollectionInfo = async (collection: PublicKey) => {
   console.log('collection pool state: ', collectionState);
 };
 
+export const getAllCollections = async () => {
+  const res = await findAllCollections(program);
+  console.dir(res, { depth: null });
+};
+
 export const getDepositInfo = async (mint: PublicKey) => {
   const depositState = await getNftDepositState(mint, program);
   console.log('nft deposit state: ', depositState);
 };
 
+export const getWeb2UserDeposits = async (userId: string) => {
+  const mints = await findWeb2UserDeposits(userId, program);
+  console.dir(
+    mints.map((mint) => mint.toBase58()),
+    { depth: null }
+  );
+};
+
 export const getAllDeposits = async ({
   owner,
   locked,
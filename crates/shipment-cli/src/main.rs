@@ -0,0 +1,606 @@
+//! Admin and user operations of the shipment-sc program, the Rust
+//! counterpart of `yarn script` in `cli/command.ts`.
+
+mod output;
+
+use std::process::ExitCode;
+
+use anchor_spl::token::spl_token::{self, solana_program::program_pack::Pack};
+use anyhow::{anyhow, bail, Context as _, Result};
+use clap::{Args, Parser, Subcommand};
+use mpl_token_metadata::accounts::Metadata;
+use serde_json::{json, Value};
+use shipment_sc::state::RentDestination;
+use shipment_sc_client::{
+    accounts,
+    error::ClientError,
+    instructions::{self, DepositOptions, DepositUpdate, FinalizedDeposit, Redemption},
+    pda,
+    rpc::RpcClient,
+    state::{NftDeposit, UserPool},
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "shipment-cli",
+    version,
+    about = "Manage the shipment-sc program"
+)]
+struct Cli {
+    #[command(flatten)]
+    config: Config,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Config {
+    /// Cluster: devnet, testnet, mainnet-beta or localnet
+    #[arg(short = 'e', long, global = true, default_value = "devnet")]
+    cluster: String,
+
+    /// RPC url, overrides the cluster url
+    #[arg(short, long, global = true)]
+    rpc: Option<String>,
+
+    /// Keypair signing and paying for transactions
+    #[arg(short, long, global = true, default_value = "./keys/user.json")]
+    keypair: String,
+
+    /// Fee multiplier, raises compute unit price and limit when not 1
+    #[arg(short = 'f', long = "fm", global = true)]
+    fee_multiplier: Option<f64>,
+
+    /// Simulate transactions instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initialize the global pool, the signer becomes super admin
+    Init {
+        #[arg(short, long)]
+        treasury: Pubkey,
+    },
+    /// Show the global pool, or a deposit
+    Status {
+        #[arg(short, long)]
+        mint: Option<Pubkey>,
+    },
+    /// Create the user pool of a wallet, the signer by default
+    InitUser {
+        #[arg(short, long)]
+        user: Option<Pubkey>,
+    },
+    /// Close the user pool of a wallet without deposits or roles, the signer by default
+    CloseUser {
+        #[arg(short, long)]
+        user: Option<Pubkey>,
+        /// Receives the user pool rent, the signer by default
+        #[arg(short = 't', long)]
+        recipient: Option<Pubkey>,
+    },
+    /// Assign or revoke roles of a user
+    SetRole {
+        #[arg(short = 'a', long)]
+        user: Pubkey,
+        #[arg(short = 'm', long)]
+        admin: Option<bool>,
+        #[arg(short, long)]
+        updater: Option<bool>,
+    },
+    /// Show roles of a user, or of every user
+    GetRoles {
+        #[arg(short = 'a', long)]
+        user: Option<Pubkey>,
+    },
+    /// Allow deposits of a collection
+    RegisterCollection {
+        #[arg(short = 'a', long)]
+        collection: Pubkey,
+    },
+    /// Stop new deposits of a collection
+    RevokeCollection {
+        #[arg(short = 'a', long)]
+        collection: Pubkey,
+    },
+    /// Deposit an nft held by the signer
+    Deposit {
+        #[arg(short, long)]
+        mint: Pubkey,
+        /// uuid of the web2 user
+        #[arg(short, long)]
+        uuid: String,
+        /// Collection to deposit under, read from allowlist or metadata by default
+        #[arg(short, long)]
+        collection: Option<Pubkey>,
+        /// Mint a custody receipt to the signer
+        #[arg(short = 't', long)]
+        receipt: bool,
+        /// Comma separated mint merkle proof in hex
+        #[arg(short, long, value_delimiter = ',', value_parser = parse_hash)]
+        proof: Option<Vec<[u8; 32]>>,
+        /// Comma separated depositor merkle proof in hex
+        #[arg(short, long, value_delimiter = ',', value_parser = parse_hash)]
+        depositor_proof: Option<Vec<[u8; 32]>>,
+    },
+    /// Deposit an nft held by the signer on behalf of a beneficiary that registered the signer as its delegate
+    DepositFor {
+        /// Wallet owning the deposit
+        #[arg(short, long)]
+        beneficiary: Pubkey,
+        #[arg(short, long)]
+        mint: Pubkey,
+        /// uuid of the web2 user
+        #[arg(short, long)]
+        uuid: String,
+        /// Collection to deposit under, read from allowlist or metadata by default
+        #[arg(short, long)]
+        collection: Option<Pubkey>,
+        /// Mint a custody receipt to the beneficiary
+        #[arg(short = 't', long)]
+        receipt: bool,
+        /// Comma separated mint merkle proof in hex
+        #[arg(short, long, value_delimiter = ',', value_parser = parse_hash)]
+        proof: Option<Vec<[u8; 32]>>,
+        /// Comma separated depositor merkle proof in hex
+        #[arg(short, long, value_delimiter = ',', value_parser = parse_hash)]
+        depositor_proof: Option<Vec<[u8; 32]>>,
+    },
+    /// Allow a wallet to deposit on behalf of the signer
+    RegisterDelegate {
+        #[arg(short = 'a', long)]
+        delegate: Pubkey,
+    },
+    /// Revoke a deposit delegate of the signer
+    RevokeDelegate {
+        #[arg(short = 'a', long)]
+        delegate: Pubkey,
+    },
+    /// Update status or lock of a deposit as updater
+    Update {
+        #[arg(short, long)]
+        mint: Pubkey,
+        #[arg(short, long)]
+        status: Option<u8>,
+        #[arg(short, long)]
+        locked: Option<bool>,
+        /// Lock reason code
+        #[arg(short = 'o', long)]
+        reason: Option<u8>,
+        /// Lock expiry unix timestamp, 0 keeps the lock until released
+        #[arg(short = 'x', long)]
+        until: Option<i64>,
+    },
+    /// Return a deposit to its owner
+    WithdrawOwner {
+        #[arg(short, long)]
+        mint: Pubkey,
+    },
+    /// Send a deposit to the treasury as admin
+    WithdrawTreasury {
+        #[arg(short, long)]
+        mint: Pubkey,
+    },
+    /// Finalize a delivered deposit as updater
+    Finalize {
+        #[arg(short, long)]
+        mint: Pubkey,
+        /// Delivery proof hash in hex (32 bytes)
+        #[arg(short, long, value_parser = parse_hash)]
+        proof: Option<[u8; 32]>,
+    },
+}
+
+fn parse_hash(value: &str) -> Result<[u8; 32], String> {
+    if value.len() != 64 {
+        return Err(format!("expected 32 bytes of hex, got {value:?}"));
+    }
+    let mut hash = [0u8; 32];
+    for (index, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[index * 2..index * 2 + 2], 16)
+            .map_err(|err| format!("invalid hex {value:?}: {err}"))?;
+    }
+    Ok(hash)
+}
+
+fn cluster_url(cluster: &str) -> Result<String> {
+    Ok(match cluster {
+        "devnet" => "https://api.devnet.solana.com",
+        "testnet" => "https://api.testnet.solana.com",
+        "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        "localnet" => "http://127.0.0.1:8899",
+        cluster => bail!("unknown cluster {cluster}"),
+    }
+    .to_string())
+}
+
+struct Context {
+    rpc: RpcClient,
+    payer: Keypair,
+    fee_multiplier: Option<f64>,
+    dry_run: bool,
+    json: bool,
+}
+
+impl Context {
+    fn new(config: &Config) -> Result<Self> {
+        let url = match &config.rpc {
+            Some(rpc) => rpc.clone(),
+            None => cluster_url(&config.cluster)?,
+        };
+        let payer = read_keypair_file(&config.keypair)
+            .map_err(|err| anyhow!("failed to read keypair {}: {err}", config.keypair))?;
+
+        Ok(Context {
+            rpc: RpcClient::new(url),
+            payer,
+            fee_multiplier: config.fee_multiplier,
+            dry_run: config.dry_run,
+            json: config.json,
+        })
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    fn gas_ixs(&self) -> Vec<Instruction> {
+        match self.fee_multiplier {
+            Some(multiplier) if multiplier != 1.0 => vec![
+                ComputeBudgetInstruction::set_compute_unit_price((5_000_000.0 * multiplier) as u64),
+                ComputeBudgetInstruction::set_compute_unit_limit((200_000.0 * multiplier) as u32),
+            ],
+            _ => vec![],
+        }
+    }
+
+    /// Signs and sends `instructions`, or simulates them on dry run.
+    fn send(&self, instructions: Vec<Instruction>) -> Result<()> {
+        let mut ixs = self.gas_ixs();
+        ixs.extend(instructions);
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&self.signer()),
+            &[&self.payer],
+            blockhash,
+        );
+
+        if self.dry_run {
+            let simulation = self.rpc.simulate_transaction(&transaction)?;
+            if let Some(err) = simulation.err {
+                return Err(ClientError::Transaction {
+                    err: err.to_string(),
+                    logs: simulation.logs,
+                }
+                .into());
+            }
+            output::print(
+                &json!({
+                    "dryRun": true,
+                    "unitsConsumed": simulation.units_consumed,
+                    "logs": simulation.logs,
+                }),
+                self.json,
+            );
+            return Ok(());
+        }
+
+        let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+        output::print(&json!({ "signature": signature.to_string() }), self.json);
+        Ok(())
+    }
+
+    fn deposit(&self, mint: &Pubkey) -> Result<NftDeposit> {
+        accounts::fetch_nft_deposit(&self.rpc, mint)?
+            .ok_or_else(|| anyhow!("no deposit for mint {mint}"))
+    }
+}
+
+/// Collection a mint is deposited under: its allowlist entry, or the
+/// verified collection or first creator of its metadata.
+fn deposit_collection(ctx: &Context, mint: &Pubkey) -> Result<(Pubkey, bool)> {
+    if let Some(allowlist) = accounts::fetch_mint_allowlist(&ctx.rpc, mint)? {
+        return Ok((allowlist.collection, true));
+    }
+    let metadata_key = pda::metadata(mint);
+    let account = ctx
+        .rpc
+        .get_account(&metadata_key)?
+        .ok_or_else(|| anyhow!("mint {mint} has no metadata"))?;
+    let metadata = Metadata::safe_deserialize(&account.data)
+        .map_err(|err| anyhow!("invalid metadata {metadata_key}: {err}"))?;
+    let collection = metadata
+        .collection
+        .map(|collection| collection.key)
+        .or_else(|| metadata.creators?.first().map(|creator| creator.address))
+        .ok_or_else(|| anyhow!("mint {mint} has no collection or creators"))?;
+    Ok((collection, false))
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let ctx = Context::new(&cli.config)?;
+    let signer = ctx.signer();
+
+    match cli.command {
+        Command::Init { treasury } => ctx.send(vec![instructions::initialize(&signer, &treasury)]),
+        Command::Status { mint: None } => {
+            let global_pool = accounts::fetch_global_pool(&ctx.rpc)?;
+            output::print(
+                &output::global_pool(&pda::global_pool().0, &global_pool),
+                ctx.json,
+            );
+            Ok(())
+        }
+        Command::Status { mint: Some(mint) } => {
+            let deposit = ctx.deposit(&mint)?;
+            output::print(
+                &output::nft_deposit(&pda::nft_deposit(&mint).0, &deposit),
+                ctx.json,
+            );
+            Ok(())
+        }
+        Command::InitUser { user } => {
+            let user = user.unwrap_or(signer);
+            ctx.send(vec![instructions::init_user(&signer, &user)])
+        }
+        Command::CloseUser { user, recipient } => {
+            let user = user.unwrap_or(signer);
+            let recipient = recipient.unwrap_or(signer);
+            ctx.send(vec![instructions::close_user(&signer, &user, &recipient)])
+        }
+        Command::SetRole {
+            user,
+            admin,
+            updater,
+        } => ctx.send(vec![instructions::change_role(
+            &signer, &user, admin, updater,
+        )]),
+        Command::GetRoles { user: Some(user) } => {
+            let pool = accounts::fetch_user_pool(&ctx.rpc, &user)?
+                .ok_or_else(|| anyhow!("user {user} has no user pool"))?;
+            output::print(
+                &output::user_pool(&pda::user_pool(&user).0, &pool),
+                ctx.json,
+            );
+            Ok(())
+        }
+        Command::GetRoles { user: None } => {
+            let pools: Vec<Value> = accounts::fetch_all::<UserPool>(&ctx.rpc, &[])?
+                .iter()
+                .filter(|(_, pool)| pool.admin || pool.updater)
+                .map(|(key, pool)| output::user_pool(key, pool))
+                .collect();
+            output::print(&Value::Array(pools), ctx.json);
+            Ok(())
+        }
+        Command::RegisterCollection { collection } => {
+            let global_pool = accounts::fetch_global_pool(&ctx.rpc)?;
+            ctx.send(vec![instructions::register_collection(
+                &signer,
+                &collection,
+                global_pool.collection_count,
+            )])
+        }
+        Command::RevokeCollection { collection } => {
+            ctx.send(vec![instructions::revoke_collection(&signer, &collection)])
+        }
+        Command::Deposit {
+            mint,
+            uuid,
+            collection,
+            receipt,
+            proof,
+            depositor_proof,
+        } => {
+            let (collection, mint_allowlist) = match collection {
+                Some(collection) => (
+                    collection,
+                    accounts::fetch_mint_allowlist(&ctx.rpc, &mint)?.is_some(),
+                ),
+                None => deposit_collection(&ctx, &mint)?,
+            };
+
+            // The user pool is created by the first deposit
+            ctx.send(vec![instructions::deposit_nft(
+                &signer,
+                &mint,
+                &collection,
+                &uuid,
+                DepositOptions {
+                    mint_allowlist,
+                    mint_proof: if mint_allowlist { None } else { proof },
+                    depositor_proof,
+                    receipt,
+                    index_page: accounts::free_web2_user_index_page(&ctx.rpc, &uuid)?,
+                },
+            )])
+        }
+        Command::DepositFor {
+            beneficiary,
+            mint,
+            uuid,
+            collection,
+            receipt,
+            proof,
+            depositor_proof,
+        } => {
+            let (collection, mint_allowlist) = match collection {
+                Some(collection) => (
+                    collection,
+                    accounts::fetch_mint_allowlist(&ctx.rpc, &mint)?.is_some(),
+                ),
+                None => deposit_collection(&ctx, &mint)?,
+            };
+
+            ctx.send(vec![instructions::deposit_nft_for(
+                &signer,
+                &beneficiary,
+                &mint,
+                &collection,
+                &uuid,
+                true,
+                DepositOptions {
+                    mint_allowlist,
+                    mint_proof: if mint_allowlist { None } else { proof },
+                    depositor_proof,
+                    receipt,
+                    index_page: accounts::free_web2_user_index_page(&ctx.rpc, &uuid)?,
+                },
+            )])
+        }
+        Command::RegisterDelegate { delegate } => {
+            ctx.send(vec![instructions::register_deposit_delegate(
+                &signer, &delegate,
+            )])
+        }
+        Command::RevokeDelegate { delegate } => {
+            ctx.send(vec![instructions::revoke_deposit_delegate(
+                &signer, &delegate,
+            )])
+        }
+        Command::Update {
+            mint,
+            status,
+            locked,
+            reason,
+            until,
+        } => {
+            let deposit = ctx.deposit(&mint)?;
+            ctx.send(vec![instructions::update_deposit(
+                &signer,
+                &deposit.owner,
+                &mint,
+                DepositUpdate {
+                    status,
+                    locked,
+                    lock_reason: reason,
+                    locked_until: until,
+                },
+            )])
+        }
+        Command::WithdrawOwner { mint } => {
+            let deposit = ctx.deposit(&mint)?;
+            ctx.send(vec![instructions::withdraw_owner(
+                &signer,
+                &deposit.owner,
+                &mint,
+                &pda::deposit_web2_user_index(&deposit),
+                &deposit.collection,
+                deposit.receipt,
+            )])
+        }
+        Command::WithdrawTreasury { mint } => {
+            let deposit = ctx.deposit(&mint)?;
+            let global_pool = accounts::fetch_global_pool(&ctx.rpc)?;
+            ctx.send(vec![instructions::withdraw_treasury(
+                &signer,
+                &deposit.owner,
+                &mint,
+                &pda::deposit_web2_user_index(&deposit),
+                &deposit.collection,
+                &global_pool.treasury,
+                deposit.receipt,
+            )])
+        }
+        Command::Finalize { mint, proof } => {
+            let deposit = ctx.deposit(&mint)?;
+            let global_pool = accounts::fetch_global_pool(&ctx.rpc)?;
+            let collection_pool = accounts::fetch_collection_pool(&ctx.rpc, &deposit.collection)?
+                .ok_or_else(|| {
+                anyhow!("collection {} is not registered", deposit.collection)
+            })?;
+
+            let rent_recipient = match collection_pool.rent_destination {
+                RentDestination::Treasury => global_pool.treasury,
+                RentDestination::Depositor => deposit.payer,
+                RentDestination::Updater => signer,
+            };
+            let redemption = if collection_pool.redemption_mint == Pubkey::default() {
+                None
+            } else {
+                // Redemption tokens are minted when the program holds the mint authority
+                let mint_key = collection_pool.redemption_mint;
+                let account = ctx
+                    .rpc
+                    .get_account(&mint_key)?
+                    .ok_or_else(|| anyhow!("redemption mint {mint_key} not found"))?;
+                let redemption_mint = spl_token::state::Mint::unpack(&account.data)
+                    .with_context(|| format!("invalid redemption mint {mint_key}"))?;
+                let authority = pda::redemption_authority(&deposit.collection).0;
+                Some(Redemption {
+                    mint: mint_key,
+                    source: (redemption_mint.mint_authority != Some(authority).into()).then(|| {
+                        anchor_spl::associated_token::get_associated_token_address(
+                            &authority, &mint_key,
+                        )
+                    }),
+                })
+            };
+
+            ctx.send(vec![instructions::finalize_deposit(
+                &signer,
+                &FinalizedDeposit {
+                    owner: deposit.owner,
+                    mint,
+                    web2_user_index: pda::deposit_web2_user_index(&deposit),
+                    collection: deposit.collection,
+                    treasury: global_pool.treasury,
+                    rent_recipient,
+                    redemption,
+                    receipt: deposit.receipt,
+                },
+                proof.unwrap_or_default(),
+            )])
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let json = cli.config.json;
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let client_error = err.downcast_ref::<ClientError>();
+            let program_error = client_error.and_then(ClientError::program_error);
+            let logs = client_error.map(ClientError::logs).unwrap_or_default();
+            if json {
+                let error = json!({
+                    "error": err.to_string(),
+                    "programError": program_error.as_ref().map(|error| json!({
+                        "code": error.code,
+                        "name": error.name,
+                        "message": error.message,
+                    })),
+                    "logs": logs,
+                });
+                println!("{}", serde_json::to_string_pretty(&error).unwrap());
+            } else {
+                eprintln!("Error: {err:#}");
+                if let Some(program_error) = program_error {
+                    eprintln!("Program error: {program_error}");
+                }
+                for log in logs {
+                    eprintln!("  {log}");
+                }
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! JSON views of program accounts and printing of command results.
+
+use serde_json::{json, Value};
+use shipment_sc::state::*;
+use solana_sdk::pubkey::Pubkey;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn global_pool(key: &Pubkey, pool: &GlobalPool) -> Value {
+    json!({
+        "key": key.to_string(),
+        "superAdmin": pool.super_admin.to_string(),
+        "treasury": pool.treasury.to_string(),
+        "totalDepositCount": pool.total_deposit_count,
+        "withdrawalGracePeriod": pool.withdrawal_grace_period,
+        "deliveryProofRequired": pool.delivery_proof_required,
+        "courierOracle": pool.courier_oracle.to_string(),
+        "transferCosignRequired": pool.transfer_cosign_required,
+        "collectionCount": pool.collection_count,
+        "depositorMerkleRoot": hex(&pool.depositor_merkle_root),
+        "depositQuota": deposit_quota(&pool.deposit_quota),
+        "identityAuthority": pool.identity_authority.to_string(),
+    })
+}
+
+fn deposit_quota(quota: &DepositQuota) -> Value {
+    json!({
+        "maxConcurrentDeposits": quota.max_concurrent_deposits,
+        "maxDailyDeposits": quota.max_daily_deposits,
+    })
+}
+
+pub fn user_pool(key: &Pubkey, pool: &UserPool) -> Value {
+    json!({
+        "key": key.to_string(),
+        "address": pool.address.to_string(),
+        "depositCount": pool.deposit_count,
+        "admin": pool.admin,
+        "updater": pool.updater,
+        "recentDeposits": pool
+            .recent_deposits
+            .iter()
+            .filter(|timestamp| **timestamp != 0)
+            .collect::<Vec<_>>(),
+        "web2User": hex(&pool.web2_user),
+    })
+}
+
+pub fn nft_deposit(key: &Pubkey, deposit: &NftDeposit) -> Value {
+    json!({
+        "key": key.to_string(),
+        "owner": deposit.owner.to_string(),
+        "mint": deposit.mint.to_string(),
+        "collection": deposit.collection.to_string(),
+        "user": deposit.user,
+        "indexPage": deposit.index_page,
+        "status": deposit.status,
+        "created": deposit.created,
+        "locked": deposit.locked,
+        "lockReason": deposit.lock_reason,
+        "lockedUntil": deposit.locked_until,
+        "withdrawalRequestedAt": deposit.withdrawal_requested_at,
+        "disputed": deposit.disputed,
+        "receipt": deposit.receipt,
+        "payer": deposit.payer.to_string(),
+    })
+}
+
+/// Prints `value` as JSON, or as `field: value` lines.
+pub fn print(value: &Value, json: bool) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value).unwrap());
+        return;
+    }
+    match value {
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                print(item, false);
+            }
+        }
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                match value {
+                    Value::String(value) => println!("{field}: {value}"),
+                    Value::Object(_) | Value::Array(_) => {
+                        println!("{field}: {}", serde_json::to_string(value).unwrap())
+                    }
+                    value => println!("{field}: {value}"),
+                }
+            }
+        }
+        Value::String(value) => println!("{value}"),
+        value => println!("{value}"),
+    }
+}
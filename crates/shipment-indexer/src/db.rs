@@ -0,0 +1,510 @@
+//! SQLite schema, writes of indexed program state and deposit queries.
+
+use std::path::Path;
+
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension};
+use serde_json::{json, Map, Value as Json};
+use shipment_sc::instruction::{DepositNft, DepositNftFor, UpdateDeposit};
+use shipment_sc_client::{
+    events::{events_from_logs, DepositFinalized, DisputeResolved},
+    instructions::decode,
+    rpc::{ConfirmedTransaction, SignatureInfo},
+    state::{DisputeOutcome, UserPool},
+    ID,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::sync::Snapshot;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS deposits (
+    mint TEXT PRIMARY KEY,
+    key TEXT NOT NULL,
+    owner TEXT NOT NULL,
+    collection TEXT NOT NULL,
+    user TEXT NOT NULL,
+    status INTEGER NOT NULL,
+    created INTEGER NOT NULL,
+    locked INTEGER NOT NULL,
+    lock_reason INTEGER NOT NULL,
+    locked_until INTEGER NOT NULL,
+    withdrawal_requested_at INTEGER NOT NULL,
+    disputed INTEGER NOT NULL,
+    receipt INTEGER NOT NULL,
+    payer TEXT NOT NULL,
+    delivery_proof TEXT NOT NULL,
+    closed INTEGER NOT NULL DEFAULT 0,
+    slot INTEGER NOT NULL,
+    locked_since INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS deposits_user ON deposits (user);
+CREATE INDEX IF NOT EXISTS deposits_owner ON deposits (owner);
+CREATE INDEX IF NOT EXISTS deposits_collection ON deposits (collection);
+
+CREATE TABLE IF NOT EXISTS user_pools (
+    address TEXT PRIMARY KEY,
+    key TEXT NOT NULL,
+    deposit_count INTEGER NOT NULL,
+    admin INTEGER NOT NULL,
+    updater INTEGER NOT NULL,
+    recent_deposits TEXT NOT NULL,
+    web2_user TEXT NOT NULL,
+    closed INTEGER NOT NULL DEFAULT 0,
+    slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS collection_pools (
+    address TEXT PRIMARY KEY,
+    key TEXT NOT NULL,
+    allowed INTEGER NOT NULL,
+    deposit_count INTEGER NOT NULL,
+    registry_index INTEGER NOT NULL,
+    rent_destination TEXT NOT NULL,
+    redemption_mint TEXT NOT NULL,
+    name TEXT NOT NULL,
+    uri TEXT NOT NULL,
+    closed INTEGER NOT NULL DEFAULT 0,
+    slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    err TEXT,
+    logs TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transactions_slot ON transactions (slot);
+";
+
+/// Deposits matching every field that is set.
+#[derive(Debug, Clone, Default)]
+pub struct DepositFilter {
+    /// uuid of the web2 user
+    pub user: Option<String>,
+    pub owner: Option<String>,
+    pub collection: Option<String>,
+    pub status: Option<u8>,
+    /// Only deposits whose lock is in force at this timestamp.
+    pub locked_at: Option<i64>,
+    /// Only deposits created before this timestamp.
+    pub created_before: Option<i64>,
+    /// Only deposits locked before this timestamp.
+    pub locked_before: Option<i64>,
+    /// Include withdrawn and finalized deposits.
+    pub include_closed: bool,
+    pub limit: Option<u32>,
+}
+
+pub struct Db {
+    conn: Connection,
+}
+
+impl Db {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        // Lock time was added after the first indexes were written
+        let columns: Vec<String> = conn
+            .prepare("SELECT name FROM pragma_table_info('deposits')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        if !columns.iter().any(|column| column == "locked_since") {
+            conn.execute_batch(
+                "ALTER TABLE deposits ADD COLUMN locked_since INTEGER NOT NULL DEFAULT 0",
+            )?;
+        }
+        Ok(Db { conn })
+    }
+
+    /// Replaces indexed accounts with `snapshot`. Accounts missing from it
+    /// were closed on chain and are kept, flagged `closed`. Delivery proofs
+    /// and lock times only found in transactions are kept, deposits first
+    /// seen locked are taken as locked at the snapshot time.
+    pub fn write_snapshot(&mut self, snapshot: &Snapshot) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        let slot = snapshot.slot as i64;
+        for table in ["deposits", "user_pools", "collection_pools"] {
+            tx.execute(
+                &format!("UPDATE {table} SET closed = 1, slot = ?1 WHERE closed = 0"),
+                [slot],
+            )?;
+        }
+
+        for (key, deposit) in &snapshot.deposits {
+            tx.execute(
+                "INSERT INTO deposits VALUES
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, '', 0, ?15,
+                  CASE WHEN ?8 THEN ?16 ELSE 0 END)
+                 ON CONFLICT (mint) DO UPDATE SET
+                    key = excluded.key,
+                    owner = excluded.owner,
+                    collection = excluded.collection,
+                    user = excluded.user,
+                    status = excluded.status,
+                    created = excluded.created,
+                    locked = excluded.locked,
+                    lock_reason = excluded.lock_reason,
+                    locked_until = excluded.locked_until,
+                    withdrawal_requested_at = excluded.withdrawal_requested_at,
+                    disputed = excluded.disputed,
+                    receipt = excluded.receipt,
+                    payer = excluded.payer,
+                    closed = 0,
+                    slot = excluded.slot,
+                    locked_since = CASE
+                        WHEN NOT excluded.locked THEN 0
+                        WHEN locked_since != 0 THEN locked_since
+                        ELSE excluded.locked_since
+                    END",
+                params![
+                    deposit.mint.to_string(),
+                    key.to_string(),
+                    deposit.owner.to_string(),
+                    deposit.collection.to_string(),
+                    deposit.user,
+                    deposit.status,
+                    deposit.created,
+                    deposit.locked,
+                    deposit.lock_reason,
+                    deposit.locked_until,
+                    deposit.withdrawal_requested_at,
+                    deposit.disputed,
+                    deposit.receipt,
+                    deposit.payer.to_string(),
+                    slot,
+                    snapshot.time,
+                ],
+            )?;
+        }
+        for (key, pool) in &snapshot.user_pools {
+            tx.execute(
+                "INSERT OR REPLACE INTO user_pools VALUES
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8)",
+                params![
+                    pool.address.to_string(),
+                    key.to_string(),
+                    pool.deposit_count as i64,
+                    pool.admin,
+                    pool.updater,
+                    recent_deposits(pool),
+                    hex(&pool.web2_user),
+                    slot,
+                ],
+            )?;
+        }
+        for (key, pool) in &snapshot.collection_pools {
+            tx.execute(
+                "INSERT OR REPLACE INTO collection_pools VALUES
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, ?10)",
+                params![
+                    pool.address.to_string(),
+                    key.to_string(),
+                    pool.allowed,
+                    pool.deposit_count as i64,
+                    pool.registry_index as i64,
+                    format!("{:?}", pool.rent_destination),
+                    pool.redemption_mint.to_string(),
+                    pool.name,
+                    pool.uri,
+                    slot,
+                ],
+            )?;
+        }
+        tx.commit()
+    }
+
+    /// Newest indexed program transaction, transactions are inserted oldest first.
+    pub fn last_signature(&self) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT signature FROM transactions ORDER BY rowid DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// Stores a program transaction and applies the deposit changes of its
+    /// instructions and events. Deposits created and closed between two
+    /// snapshots, delivery proofs and lock times are only found here.
+    pub fn insert_transaction(
+        &self,
+        signature: &SignatureInfo,
+        transaction: &ConfirmedTransaction,
+    ) -> rusqlite::Result<()> {
+        if signature.err.is_none() {
+            let slot = signature.slot as i64;
+            let time = signature.block_time.unwrap_or_default();
+            for ix in &transaction.instructions {
+                self.apply_instruction(ix, slot, time)?;
+            }
+            for event in events_from_logs::<DepositFinalized, _>(&transaction.logs) {
+                self.conn.execute(
+                    "UPDATE deposits SET delivery_proof = ?1, closed = 1, slot = ?3 WHERE mint = ?2",
+                    params![hex(&event.delivery_proof), event.mint.to_string(), slot],
+                )?;
+            }
+            for event in events_from_logs::<DisputeResolved, _>(&transaction.logs) {
+                if event.outcome == DisputeOutcome::Finalize {
+                    self.conn.execute(
+                        "UPDATE deposits SET closed = 1, slot = ?2 WHERE mint = ?1",
+                        params![event.mint.to_string(), slot],
+                    )?;
+                }
+            }
+        }
+        self.conn.execute(
+            "INSERT OR IGNORE INTO transactions VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                signature.signature,
+                signature.slot as i64,
+                signature.block_time,
+                signature.err.as_ref().map(|err| err.to_string()),
+                serde_json::to_string(&transaction.logs).unwrap(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    // Deposit rows written by a successful program instruction at `time`
+    fn apply_instruction(&self, ix: &Instruction, slot: i64, time: i64) -> rusqlite::Result<()> {
+        if let Some(deposit) = NewDeposit::decode(ix) {
+            // A deposit account outliving a withdrawal keeps its first payer
+            self.conn.execute(
+                "INSERT INTO deposits VALUES
+                 (?1, ?2, ?3, ?4, ?5, 1, ?6, 0, 0, 0, 0, 0, ?7, ?8, '', 0, ?9, 0)
+                 ON CONFLICT (mint) DO UPDATE SET
+                    key = excluded.key,
+                    owner = excluded.owner,
+                    collection = excluded.collection,
+                    user = excluded.user,
+                    status = 1,
+                    created = excluded.created,
+                    locked = 0,
+                    lock_reason = 0,
+                    locked_until = 0,
+                    withdrawal_requested_at = 0,
+                    disputed = 0,
+                    receipt = excluded.receipt,
+                    payer = CASE WHEN closed THEN excluded.payer ELSE payer END,
+                    delivery_proof = '',
+                    closed = 0,
+                    slot = excluded.slot,
+                    locked_since = 0",
+                params![
+                    deposit.mint.to_string(),
+                    deposit.key.to_string(),
+                    deposit.owner.to_string(),
+                    deposit.collection.to_string(),
+                    deposit.user,
+                    time,
+                    deposit.receipt,
+                    deposit.payer.to_string(),
+                    slot,
+                ],
+            )?;
+        } else if let (Some(args), Some(mint)) = (decode::<UpdateDeposit>(ix), ix.accounts.get(4)) {
+            // Lock context is cleared on release, as the program does
+            self.conn.execute(
+                "UPDATE deposits SET
+                    status = COALESCE(?2, status),
+                    locked = COALESCE(?3, locked),
+                    lock_reason = CASE
+                        WHEN COALESCE(?3, locked) THEN COALESCE(?4, lock_reason) ELSE 0
+                    END,
+                    locked_until = CASE
+                        WHEN COALESCE(?3, locked) THEN COALESCE(?5, locked_until) ELSE 0
+                    END,
+                    locked_since = CASE
+                        WHEN NOT COALESCE(?3, locked) THEN 0
+                        WHEN locked AND locked_since != 0 THEN locked_since
+                        ELSE ?6
+                    END,
+                    slot = ?7
+                 WHERE mint = ?1",
+                params![
+                    mint.pubkey.to_string(),
+                    args.status,
+                    args.locked,
+                    args.lock_reason,
+                    args.locked_until,
+                    time,
+                    slot,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn deposits(&self, filter: &DepositFilter) -> rusqlite::Result<Vec<Json>> {
+        let mut conditions = vec![];
+        let mut values: Vec<Value> = vec![];
+        let mut condition = |sql: &str, value: Option<Value>| {
+            values.extend(value);
+            conditions.push(sql.replace('?', &format!("?{}", values.len())));
+        };
+
+        if !filter.include_closed {
+            condition("closed = 0 AND status != 0", None);
+        }
+        if let Some(user) = &filter.user {
+            condition("user = ?", Some(user.clone().into()));
+        }
+        if let Some(owner) = &filter.owner {
+            condition("owner = ?", Some(owner.clone().into()));
+        }
+        if let Some(collection) = &filter.collection {
+            condition("collection = ?", Some(collection.clone().into()));
+        }
+        if let Some(status) = filter.status {
+            condition("status = ?", Some(i64::from(status).into()));
+        }
+        if let Some(now) = filter.locked_at {
+            condition(
+                "locked = 1 AND (locked_until = 0 OR locked_until > ?)",
+                Some(now.into()),
+            );
+        }
+        if let Some(timestamp) = filter.created_before {
+            condition("created < ?", Some(timestamp.into()));
+        }
+        if let Some(timestamp) = filter.locked_before {
+            condition("locked = 1 AND locked_since < ?", Some(timestamp.into()));
+        }
+
+        let mut sql = "SELECT * FROM deposits".to_string();
+        if !conditions.is_empty() {
+            sql += &format!(" WHERE {}", conditions.join(" AND "));
+        }
+        sql += " ORDER BY created";
+        if let Some(limit) = filter.limit {
+            sql += &format!(" LIMIT {limit}");
+        }
+        self.rows(&sql, values)
+    }
+
+    /// User pools, only those holding a role if `roles` is set.
+    pub fn user_pools(&self, roles: bool) -> rusqlite::Result<Vec<Json>> {
+        let filter = if roles {
+            " AND (admin = 1 OR updater = 1)"
+        } else {
+            ""
+        };
+        self.rows(
+            &format!("SELECT * FROM user_pools WHERE closed = 0{filter} ORDER BY address"),
+            vec![],
+        )
+    }
+
+    pub fn collection_pools(&self) -> rusqlite::Result<Vec<Json>> {
+        self.rows(
+            "SELECT * FROM collection_pools WHERE closed = 0 ORDER BY registry_index",
+            vec![],
+        )
+    }
+
+    pub fn transactions(&self, limit: u32) -> rusqlite::Result<Vec<Json>> {
+        self.rows(
+            &format!("SELECT * FROM transactions ORDER BY slot DESC LIMIT {limit}"),
+            vec![],
+        )
+    }
+
+    /// Rows of `sql` as JSON objects keyed by camelCase column names.
+    fn rows(&self, sql: &str, values: Vec<Value>) -> rusqlite::Result<Vec<Json>> {
+        let mut statement = self.conn.prepare(sql)?;
+        let columns: Vec<String> = statement
+            .column_names()
+            .into_iter()
+            .map(camel_case)
+            .collect();
+        let rows = statement.query_map(params_from_iter(values), |row| {
+            let mut object = Map::new();
+            for (index, column) in columns.iter().enumerate() {
+                let value = match row.get::<_, Value>(index)? {
+                    Value::Null => Json::Null,
+                    Value::Integer(value) => json!(value),
+                    Value::Real(value) => json!(value),
+                    Value::Text(value) if column == "logs" || column == "recentDeposits" => {
+                        serde_json::from_str(&value).unwrap_or(Json::String(value))
+                    }
+                    Value::Text(value) => json!(value),
+                    Value::Blob(value) => json!(hex(&value)),
+                };
+                object.insert(column.clone(), value);
+            }
+            Ok(Json::Object(object))
+        })?;
+        rows.collect()
+    }
+}
+
+/// Deposit account written by `deposit_nft` or `deposit_nft_for`.
+struct NewDeposit {
+    key: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    collection: Pubkey,
+    user: String,
+    receipt: bool,
+}
+
+impl NewDeposit {
+    fn decode(ix: &Instruction) -> Option<Self> {
+        // Account positions follow the `Accounts` structs of the instructions
+        let (owner, payer, mint, key, receipt_mint, collection, user) =
+            if let Some(args) = decode::<DepositNft>(ix) {
+                (0, 0, 4, 7, 12, args.collection, args.user)
+            } else if let Some(args) = decode::<DepositNftFor>(ix) {
+                (1, 0, 6, 9, 14, args.collection, args.user)
+            } else {
+                return None;
+            };
+        let account = |index: usize| ix.accounts.get(index).map(|meta| meta.pubkey);
+        Some(NewDeposit {
+            key: account(key)?,
+            mint: account(mint)?,
+            owner: account(owner)?,
+            payer: account(payer)?,
+            collection,
+            user,
+            // Absent optional accounts are replaced by the program id
+            receipt: account(receipt_mint)? != ID,
+        })
+    }
+}
+
+fn camel_case(column: &str) -> String {
+    let mut words = column.split('_');
+    let mut name = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let mut chars = word.chars();
+        name.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+        name.push_str(chars.as_str());
+    }
+    name
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// Timestamps of the deposits a user pool still tracks, as a JSON array
+fn recent_deposits(pool: &UserPool) -> String {
+    let timestamps: Vec<i64> = pool
+        .recent_deposits
+        .iter()
+        .copied()
+        .filter(|timestamp| *timestamp != 0)
+        .collect();
+    serde_json::to_string(&timestamps).unwrap()
+}
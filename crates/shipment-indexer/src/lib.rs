@@ -0,0 +1,7 @@
+//! Off-chain index of shipment-sc deposits. [`sync`] snapshots program
+//! accounts and transactions from JSON-RPC, [`db`] stores them in SQLite
+//! and answers queries the program cannot, like every deposit of a web2
+//! user or locks older than a week.
+
+pub mod db;
+pub mod sync;
@@ -0,0 +1,218 @@
+//! Indexes shipment-sc accounts and transactions into SQLite and queries
+//! the index.
+
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Result};
+use clap::{Args, Parser, Subcommand};
+use serde_json::Value;
+use shipment_indexer::{
+    db::{Db, DepositFilter},
+    sync,
+};
+use shipment_sc_client::rpc::RpcClient;
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[derive(Parser)]
+#[command(
+    name = "shipment-indexer",
+    version,
+    about = "Index shipment-sc deposits into SQLite"
+)]
+struct Cli {
+    #[command(flatten)]
+    config: Config,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Config {
+    /// SQLite database file
+    #[arg(short, long, global = true, default_value = "./shipment.db")]
+    db: String,
+
+    /// Cluster: devnet, testnet, mainnet-beta or localnet
+    #[arg(short = 'e', long, global = true, default_value = "devnet")]
+    cluster: String,
+
+    /// RPC url, overrides the cluster url
+    #[arg(short, long, global = true)]
+    rpc: Option<String>,
+
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index program accounts and new transactions
+    Sync {
+        /// Keep indexing, waiting this many seconds between rounds
+        #[arg(short, long)]
+        interval: Option<u64>,
+        /// Skip transaction history, only snapshot accounts
+        #[arg(long)]
+        accounts_only: bool,
+    },
+    /// Query indexed deposits, active ones by default
+    Deposits {
+        /// uuid of the web2 user
+        #[arg(short, long)]
+        user: Option<String>,
+        #[arg(short, long)]
+        owner: Option<String>,
+        #[arg(short, long)]
+        collection: Option<String>,
+        #[arg(short, long)]
+        status: Option<u8>,
+        /// Only deposits whose lock is in force
+        #[arg(short, long)]
+        locked: bool,
+        /// Only deposits created more than this many days ago
+        #[arg(short = 'a', long)]
+        older_than_days: Option<i64>,
+        /// Only deposits locked more than this many days ago, lock in force
+        #[arg(long)]
+        locked_for_days: Option<i64>,
+        /// Include withdrawn and finalized deposits
+        #[arg(long)]
+        all: bool,
+        #[arg(short = 'n', long)]
+        limit: Option<u32>,
+    },
+    /// Query indexed user pools
+    Users {
+        /// Only users holding the admin or updater role
+        #[arg(long)]
+        roles: bool,
+    },
+    /// Query indexed collections
+    Collections,
+    /// Query indexed program transactions, newest first
+    Transactions {
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: u32,
+    },
+}
+
+fn cluster_url(cluster: &str) -> Result<String> {
+    Ok(match cluster {
+        "devnet" => "https://api.devnet.solana.com",
+        "testnet" => "https://api.testnet.solana.com",
+        "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        "localnet" => "http://127.0.0.1:8899",
+        cluster => bail!("unknown cluster {cluster}"),
+    }
+    .to_string())
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+fn sync_once(rpc: &RpcClient, db: &mut Db, accounts_only: bool) -> Result<()> {
+    // Transactions first, the snapshot taken after them is the newer state
+    if !accounts_only {
+        let count = sync::sync_transactions(rpc, db)?;
+        eprintln!("{count} new transactions");
+    }
+    let snapshot = sync::fetch_snapshot(rpc)?;
+    db.write_snapshot(&snapshot)?;
+    eprintln!(
+        "slot {}: {} deposits, {} users, {} collections",
+        snapshot.slot,
+        snapshot.deposits.len(),
+        snapshot.user_pools.len(),
+        snapshot.collection_pools.len()
+    );
+    Ok(())
+}
+
+/// Prints rows as JSON, or as `field: value` blocks.
+fn print(rows: Vec<Value>, json: bool) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows).unwrap());
+        return;
+    }
+    for (index, row) in rows.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        for (field, value) in row.as_object().into_iter().flatten() {
+            match value {
+                Value::String(value) => println!("{field}: {value}"),
+                value => println!("{field}: {value}"),
+            }
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let mut db = Db::open(&cli.config.db)?;
+    let json = cli.config.json;
+
+    match cli.command {
+        Command::Sync {
+            interval,
+            accounts_only,
+        } => {
+            let url = match cli.config.rpc {
+                Some(rpc) => rpc,
+                None => cluster_url(&cli.config.cluster)?,
+            };
+            let rpc = RpcClient::new(url);
+            let Some(interval) = interval else {
+                return sync_once(&rpc, &mut db, accounts_only);
+            };
+            loop {
+                // A failed round is retried on the next one
+                if let Err(err) = sync_once(&rpc, &mut db, accounts_only) {
+                    eprintln!("sync failed: {err:#}");
+                }
+                thread::sleep(Duration::from_secs(interval));
+            }
+        }
+        Command::Deposits {
+            user,
+            owner,
+            collection,
+            status,
+            locked,
+            older_than_days,
+            locked_for_days,
+            all,
+            limit,
+        } => {
+            let now = now();
+            let filter = DepositFilter {
+                user,
+                owner,
+                collection,
+                status,
+                locked_at: (locked || locked_for_days.is_some()).then_some(now),
+                created_before: older_than_days.map(|days| now - days * DAY),
+                locked_before: locked_for_days.map(|days| now - days * DAY),
+                include_closed: all,
+                limit,
+            };
+            print(db.deposits(&filter)?, json);
+        }
+        Command::Users { roles } => print(db.user_pools(roles)?, json),
+        Command::Collections => print(db.collection_pools()?, json),
+        Command::Transactions { limit } => print(db.transactions(limit)?, json),
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    run(Cli::parse())
+}
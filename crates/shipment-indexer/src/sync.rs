@@ -0,0 +1,71 @@
+//! Reads program state and transactions from a JSON-RPC endpoint.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use shipment_sc_client::{
+    accounts,
+    rpc::{self, RpcClient, SignatureInfo},
+    state::{CollectionPool, NftDeposit, UserPool},
+    ID,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use crate::db::Db;
+
+/// Signatures requested per `getSignaturesForAddress` page.
+const SIGNATURE_PAGE: usize = 1000;
+
+/// Every indexed program account at `slot`.
+#[derive(Default)]
+pub struct Snapshot {
+    pub slot: u64,
+    /// Unix time the snapshot was taken at.
+    pub time: i64,
+    pub deposits: Vec<(Pubkey, NftDeposit)>,
+    pub user_pools: Vec<(Pubkey, UserPool)>,
+    pub collection_pools: Vec<(Pubkey, CollectionPool)>,
+}
+
+pub fn fetch_snapshot(rpc: &RpcClient) -> rpc::Result<Snapshot> {
+    Ok(Snapshot {
+        slot: rpc.get_slot()?,
+        time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64),
+        deposits: accounts::fetch_all(rpc, &[])?,
+        user_pools: accounts::fetch_all(rpc, &[])?,
+        collection_pools: accounts::fetch_all(rpc, &[])?,
+    })
+}
+
+/// Program transactions newer than the last indexed one, oldest first.
+pub fn fetch_new_signatures(
+    rpc: &RpcClient,
+    until: Option<&str>,
+) -> rpc::Result<Vec<SignatureInfo>> {
+    let mut signatures: Vec<SignatureInfo> = vec![];
+    loop {
+        let before = signatures.last().map(|info| info.signature.as_str());
+        let page = rpc.get_signatures_for_address(&ID, before, until, SIGNATURE_PAGE)?;
+        let done = page.len() < SIGNATURE_PAGE;
+        signatures.extend(page);
+        if done {
+            break;
+        }
+    }
+    signatures.reverse();
+    Ok(signatures)
+}
+
+/// Indexes new program transactions with their logs, returns how many.
+pub fn sync_transactions(rpc: &RpcClient, db: &Db) -> anyhow::Result<usize> {
+    let signatures = fetch_new_signatures(rpc, db.last_signature()?.as_deref())?;
+    for info in &signatures {
+        let signature: Signature = info.signature.parse()?;
+        let transaction = rpc
+            .get_confirmed_transaction(&signature)?
+            .unwrap_or_default();
+        db.insert_transaction(info, &transaction)?;
+    }
+    Ok(signatures.len())
+}
@@ -0,0 +1,422 @@
+use anchor_lang::{AnchorSerialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use shipment_indexer::{
+    db::{Db, DepositFilter},
+    sync::Snapshot,
+};
+use shipment_sc::{constant::MAX_DAILY_DEPOSITS, event::DepositFinalized, state::*};
+use shipment_sc_client::{
+    instructions::{self, DepositOptions, DepositUpdate},
+    rpc::{ConfirmedTransaction, SignatureInfo},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const NOW: i64 = 1_700_000_000;
+const DAY: i64 = 24 * 60 * 60;
+
+fn deposit(user: &str, created: i64, locked: bool, locked_until: i64) -> (Pubkey, NftDeposit) {
+    let deposit = NftDeposit {
+        owner: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        created,
+        status: 1,
+        locked,
+        lock_reason: 0,
+        locked_until,
+        withdrawal_requested_at: 0,
+        disputed: false,
+        receipt: false,
+        collection: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        index_page: 0,
+        user: user.to_string(),
+    };
+    (Pubkey::new_unique(), deposit)
+}
+
+fn user_pool(admin: bool, updater: bool) -> (Pubkey, UserPool) {
+    let pool = UserPool {
+        address: Pubkey::new_unique(),
+        deposit_count: 0,
+        admin,
+        updater,
+        recent_deposits: [0; MAX_DAILY_DEPOSITS],
+        web2_user: [0; 32],
+    };
+    (Pubkey::new_unique(), pool)
+}
+
+fn signature(slot: u64, block_time: i64) -> SignatureInfo {
+    SignatureInfo {
+        signature: format!("signature-{slot}"),
+        slot,
+        err: None,
+        block_time: Some(block_time),
+    }
+}
+
+fn transaction(instructions: Vec<Instruction>, logs: Vec<String>) -> ConfirmedTransaction {
+    ConfirmedTransaction { instructions, logs }
+}
+
+fn finalized_logs(deposit: &NftDeposit, delivery_proof: [u8; 32]) -> Vec<String> {
+    let mut data = DepositFinalized::DISCRIMINATOR.to_vec();
+    DepositFinalized {
+        mint: deposit.mint,
+        owner: deposit.owner,
+        collection: deposit.collection,
+        user: deposit.user.clone(),
+        delivery_proof,
+    }
+    .serialize(&mut data)
+    .unwrap();
+    vec![format!("Program data: {}", STANDARD.encode(&data))]
+}
+
+fn lock(deposit: &NftDeposit, locked: bool) -> Instruction {
+    instructions::update_deposit(
+        &Pubkey::new_unique(),
+        &deposit.owner,
+        &deposit.mint,
+        DepositUpdate {
+            locked: Some(locked),
+            ..DepositUpdate::default()
+        },
+    )
+}
+
+fn mints(rows: &[serde_json::Value]) -> Vec<String> {
+    rows.iter()
+        .map(|row| row["mint"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn deposits_of_web2_user() {
+    let mut db = Db::open_in_memory().unwrap();
+    let alice = [
+        deposit("alice", NOW, false, 0),
+        deposit("alice", NOW, false, 0),
+    ];
+    let bob = deposit("bob", NOW, false, 0);
+    db.write_snapshot(&Snapshot {
+        slot: 1,
+        deposits: vec![alice[0].clone(), bob, alice[1].clone()],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    let rows = db
+        .deposits(&DepositFilter {
+            user: Some("alice".to_string()),
+            ..DepositFilter::default()
+        })
+        .unwrap();
+    let mut expected: Vec<String> = alice.iter().map(|(_, d)| d.mint.to_string()).collect();
+    let mut found = mints(&rows);
+    expected.sort();
+    found.sort();
+    assert_eq!(found, expected);
+    assert_eq!(rows[0]["user"], "alice");
+    assert_eq!(rows[0]["status"], 1);
+    assert_eq!(rows[0]["locked"], 0);
+}
+
+#[test]
+fn locked_deposits_older_than_a_week() {
+    let mut db = Db::open_in_memory().unwrap();
+    // Old deposits, some locked recently
+    let old_lock = deposit("a", NOW - 30 * DAY, false, 0);
+    let old_lock_expired = deposit("b", NOW - 30 * DAY, false, 0);
+    let new_lock = deposit("c", NOW - 30 * DAY, false, 0);
+    let relocked = deposit("d", NOW - 30 * DAY, false, 0);
+    let unlocked = deposit("e", NOW - 30 * DAY, false, 0);
+    let seen_locked = deposit("f", NOW - 30 * DAY, true, 0);
+    db.write_snapshot(&Snapshot {
+        slot: 1,
+        time: NOW - 20 * DAY,
+        deposits: vec![
+            old_lock.clone(),
+            old_lock_expired.clone(),
+            new_lock.clone(),
+            relocked.clone(),
+            unlocked.clone(),
+        ],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    let locks = [
+        (lock(&old_lock.1, true), NOW - 9 * DAY),
+        (lock(&old_lock_expired.1, true), NOW - 9 * DAY),
+        (lock(&new_lock.1, true), NOW - DAY),
+        (lock(&relocked.1, true), NOW - 10 * DAY),
+        (lock(&relocked.1, false), NOW - 8 * DAY),
+        (lock(&relocked.1, true), NOW - 2 * DAY),
+        (lock(&unlocked.1, true), NOW - 9 * DAY),
+        (lock(&unlocked.1, false), NOW - 8 * DAY),
+        // Locking again keeps the first lock time
+        (lock(&old_lock.1, true), NOW - DAY),
+    ];
+    for (slot, (ix, time)) in locks.into_iter().enumerate() {
+        db.insert_transaction(
+            &signature(slot as u64 + 2, time),
+            &transaction(vec![ix], vec![]),
+        )
+        .unwrap();
+    }
+
+    // Lock expiry comes with the next snapshot, which keeps the lock times
+    let locked = |(key, deposit): &(Pubkey, NftDeposit), locked: bool, locked_until: i64| {
+        let deposit = NftDeposit {
+            locked,
+            locked_until,
+            ..deposit.clone()
+        };
+        (*key, deposit)
+    };
+    db.write_snapshot(&Snapshot {
+        slot: 20,
+        time: NOW - 8 * DAY,
+        deposits: vec![
+            locked(&old_lock, true, 0),
+            locked(&old_lock_expired, true, NOW - DAY),
+            locked(&new_lock, true, 0),
+            locked(&relocked, true, 0),
+            locked(&unlocked, false, 0),
+            seen_locked.clone(),
+        ],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    let rows = db
+        .deposits(&DepositFilter {
+            locked_at: Some(NOW),
+            locked_before: Some(NOW - 7 * DAY),
+            ..DepositFilter::default()
+        })
+        .unwrap();
+    // Deposits first seen locked by a snapshot are locked since it was taken
+    let mut expected = vec![old_lock.1.mint.to_string(), seen_locked.1.mint.to_string()];
+    let mut found = mints(&rows);
+    expected.sort();
+    found.sort();
+    assert_eq!(found, expected);
+    let row = rows
+        .iter()
+        .find(|row| row["mint"] == old_lock.1.mint.to_string())
+        .unwrap();
+    assert_eq!(row["lockedSince"], NOW - 9 * DAY);
+}
+
+#[test]
+fn deposit_created_and_finalized_between_snapshots() {
+    let mut db = Db::open_in_memory().unwrap();
+    db.write_snapshot(&Snapshot {
+        slot: 1,
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+    let deposit_ix = instructions::deposit_nft(
+        &owner,
+        &mint,
+        &collection,
+        "alice",
+        DepositOptions::default(),
+    );
+    db.insert_transaction(&signature(2, NOW), &transaction(vec![deposit_ix], vec![]))
+        .unwrap();
+    let rows = db
+        .deposits(&DepositFilter {
+            user: Some("alice".to_string()),
+            ..DepositFilter::default()
+        })
+        .unwrap();
+    assert_eq!(mints(&rows), vec![mint.to_string()]);
+    assert_eq!(rows[0]["owner"], owner.to_string());
+    assert_eq!(rows[0]["payer"], owner.to_string());
+    assert_eq!(rows[0]["collection"], collection.to_string());
+    assert_eq!(rows[0]["created"], NOW);
+    assert_eq!(rows[0]["status"], 1);
+    assert_eq!(rows[0]["receipt"], 0);
+
+    let finalized = NftDeposit {
+        owner,
+        mint,
+        collection,
+        user: "alice".to_string(),
+        ..deposit("alice", NOW, false, 0).1
+    };
+    db.insert_transaction(
+        &signature(3, NOW + DAY),
+        &transaction(vec![], finalized_logs(&finalized, [0xcd; 32])),
+    )
+    .unwrap();
+    db.write_snapshot(&Snapshot {
+        slot: 4,
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    assert!(db.deposits(&DepositFilter::default()).unwrap().is_empty());
+    let rows = db
+        .deposits(&DepositFilter {
+            include_closed: true,
+            ..DepositFilter::default()
+        })
+        .unwrap();
+    assert_eq!(mints(&rows), vec![mint.to_string()]);
+    assert_eq!(rows[0]["closed"], 1);
+    assert_eq!(rows[0]["user"], "alice");
+    assert_eq!(rows[0]["deliveryProof"], "cd".repeat(32));
+}
+
+#[test]
+fn delegated_deposit_belongs_to_beneficiary() {
+    let db = Db::open_in_memory().unwrap();
+    let depositor = Pubkey::new_unique();
+    let beneficiary = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let ix = instructions::deposit_nft_for(
+        &depositor,
+        &beneficiary,
+        &mint,
+        &Pubkey::new_unique(),
+        "bob",
+        true,
+        DepositOptions {
+            receipt: true,
+            ..DepositOptions::default()
+        },
+    );
+    db.insert_transaction(&signature(2, NOW), &transaction(vec![ix], vec![]))
+        .unwrap();
+
+    let rows = db.deposits(&DepositFilter::default()).unwrap();
+    assert_eq!(mints(&rows), vec![mint.to_string()]);
+    assert_eq!(rows[0]["owner"], beneficiary.to_string());
+    assert_eq!(rows[0]["payer"], depositor.to_string());
+    assert_eq!(rows[0]["user"], "bob");
+    assert_eq!(rows[0]["receipt"], 1);
+}
+
+#[test]
+fn missing_accounts_are_flagged_closed() {
+    let mut db = Db::open_in_memory().unwrap();
+    let (key, mut withdrawn) = deposit("a", NOW, false, 0);
+    let finalized = deposit("a", NOW, false, 0);
+    db.write_snapshot(&Snapshot {
+        slot: 1,
+        deposits: vec![(key, withdrawn.clone()), finalized.clone()],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    // Withdrawn deposits stay open with status 0, finalized ones are closed
+    withdrawn.status = 0;
+    db.write_snapshot(&Snapshot {
+        slot: 2,
+        deposits: vec![(key, withdrawn.clone())],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    assert!(db.deposits(&DepositFilter::default()).unwrap().is_empty());
+    let rows = db
+        .deposits(&DepositFilter {
+            include_closed: true,
+            ..DepositFilter::default()
+        })
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    for row in rows {
+        if row["mint"] == finalized.1.mint.to_string() {
+            assert_eq!(row["closed"], 1);
+            assert_eq!(row["status"], 1);
+        } else {
+            assert_eq!(row["closed"], 0);
+            assert_eq!(row["status"], 0);
+        }
+        assert_eq!(row["slot"], 2);
+    }
+}
+
+#[test]
+fn finalized_deposit_keeps_delivery_proof() {
+    let mut db = Db::open_in_memory().unwrap();
+    let (key, deposit) = deposit("a", NOW, false, 0);
+    db.write_snapshot(&Snapshot {
+        slot: 1,
+        deposits: vec![(key, deposit.clone())],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    let logs = finalized_logs(&deposit, [0xab; 32]);
+    // Failed transaction emitted nothing
+    let failed = SignatureInfo {
+        err: Some("err".into()),
+        ..signature(2, NOW)
+    };
+    db.insert_transaction(&failed, &transaction(vec![], logs.clone()))
+        .unwrap();
+    let rows = db.deposits(&DepositFilter::default()).unwrap();
+    assert_eq!(rows[0]["deliveryProof"], "");
+
+    db.insert_transaction(&signature(3, NOW), &transaction(vec![], logs))
+        .unwrap();
+    // A snapshot still listing the deposit does not wipe the proof
+    db.write_snapshot(&Snapshot {
+        slot: 3,
+        deposits: vec![(key, deposit)],
+        ..Snapshot::default()
+    })
+    .unwrap();
+    let rows = db.deposits(&DepositFilter::default()).unwrap();
+    assert_eq!(rows[0]["deliveryProof"], "ab".repeat(32));
+
+    db.write_snapshot(&Snapshot {
+        slot: 4,
+        ..Snapshot::default()
+    })
+    .unwrap();
+    let rows = db
+        .deposits(&DepositFilter {
+            include_closed: true,
+            ..DepositFilter::default()
+        })
+        .unwrap();
+    assert_eq!(rows[0]["closed"], 1);
+    assert_eq!(rows[0]["deliveryProof"], "ab".repeat(32));
+}
+
+#[test]
+fn user_pools_with_roles() {
+    let mut db = Db::open_in_memory().unwrap();
+    let admin = user_pool(true, false);
+    let updater = user_pool(false, true);
+    let user = user_pool(false, false);
+    db.write_snapshot(&Snapshot {
+        slot: 1,
+        user_pools: vec![admin.clone(), updater.clone(), user],
+        ..Snapshot::default()
+    })
+    .unwrap();
+
+    assert_eq!(db.user_pools(false).unwrap().len(), 3);
+    let mut roles: Vec<String> = db
+        .user_pools(true)
+        .unwrap()
+        .iter()
+        .map(|row| row["address"].as_str().unwrap().to_string())
+        .collect();
+    let mut expected = vec![admin.1.address.to_string(), updater.1.address.to_string()];
+    roles.sort();
+    expected.sort();
+    assert_eq!(roles, expected);
+}
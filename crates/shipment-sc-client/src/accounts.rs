@@ -0,0 +1,153 @@
+//! Fetching and decoding of program accounts.
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use shipment_sc::{constant::WEB2_USER_INDEX_PAGE_SIZE, state::*, ID};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::ClientError,
+    pda,
+    rpc::{Result, RpcClient, RpcFilter},
+};
+
+/// Decodes account `key` checking its Anchor discriminator.
+pub fn decode<T: AccountDeserialize>(key: &Pubkey, data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|err| ClientError::Decode(*key, err.to_string()))
+}
+
+/// Account `key` decoded as `T`, `None` if it does not exist.
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, key: &Pubkey) -> Result<Option<T>> {
+    rpc.get_account(key)?
+        .map(|account| decode(key, &account.data))
+        .transpose()
+}
+
+/// Every program account of type `T`.
+pub fn fetch_all<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+    filters: &[RpcFilter],
+) -> Result<Vec<(Pubkey, T)>> {
+    let mut filters = filters.to_vec();
+    filters.push(RpcFilter::Memcmp {
+        offset: 0,
+        bytes: T::DISCRIMINATOR.to_vec(),
+    });
+    rpc.get_program_accounts(&ID, &filters)?
+        .into_iter()
+        .map(|(key, account)| Ok((key, decode(&key, &account.data)?)))
+        .collect()
+}
+
+/// Global pool, which exists once the program is initialized.
+pub fn fetch_global_pool(rpc: &RpcClient) -> Result<GlobalPool> {
+    let key = pda::global_pool().0;
+    fetch(rpc, &key)?.ok_or(ClientError::AccountNotFound(key))
+}
+
+pub fn fetch_user_pool(rpc: &RpcClient, user: &Pubkey) -> Result<Option<UserPool>> {
+    fetch(rpc, &pda::user_pool(user).0)
+}
+
+pub fn fetch_collection_pool(
+    rpc: &RpcClient,
+    collection: &Pubkey,
+) -> Result<Option<CollectionPool>> {
+    fetch(rpc, &pda::collection_pool(collection).0)
+}
+
+pub fn fetch_collection_registry(rpc: &RpcClient, page: u64) -> Result<Option<CollectionRegistry>> {
+    fetch(rpc, &pda::collection_registry(page).0)
+}
+
+pub fn fetch_mint_allowlist(rpc: &RpcClient, mint: &Pubkey) -> Result<Option<MintAllowlist>> {
+    fetch(rpc, &pda::mint_allowlist(mint).0)
+}
+
+pub fn fetch_nft_deposit(rpc: &RpcClient, mint: &Pubkey) -> Result<Option<NftDeposit>> {
+    fetch(rpc, &pda::nft_deposit(mint).0)
+}
+
+pub fn fetch_web2_user_index(
+    rpc: &RpcClient,
+    uuid: &str,
+    page: u64,
+) -> Result<Option<Web2UserIndex>> {
+    fetch(rpc, &pda::web2_user_index(uuid, page).0)
+}
+
+/// First web2 user index page of `uuid` with a free slot for a new deposit.
+pub fn free_web2_user_index_page(rpc: &RpcClient, uuid: &str) -> Result<u64> {
+    let mut page = 0;
+    while let Some(index) = fetch_web2_user_index(rpc, uuid, page)? {
+        if index.count < WEB2_USER_INDEX_PAGE_SIZE as u64 {
+            break;
+        }
+        page += 1;
+    }
+    Ok(page)
+}
+
+/// Mints of the active deposits of web2 user `uuid`, read page by page up to
+/// the first page that was never created.
+pub fn fetch_web2_user_deposits(rpc: &RpcClient, uuid: &str) -> Result<Vec<Pubkey>> {
+    let mut mints = vec![];
+    let mut page = 0;
+    while let Some(index) = fetch_web2_user_index(rpc, uuid, page)? {
+        mints.extend(
+            index
+                .mints
+                .into_iter()
+                .filter(|mint| *mint != Pubkey::default()),
+        );
+        page += 1;
+    }
+    Ok(mints)
+}
+
+pub fn fetch_deposit_delegate(
+    rpc: &RpcClient,
+    beneficiary: &Pubkey,
+    delegate: &Pubkey,
+) -> Result<Option<DepositDelegate>> {
+    fetch(rpc, &pda::deposit_delegate(beneficiary, delegate).0)
+}
+
+pub fn fetch_dispute(rpc: &RpcClient, mint: &Pubkey) -> Result<Option<Dispute>> {
+    fetch(rpc, &pda::dispute(mint).0)
+}
+
+/// Registered collections, in registry order.
+pub fn fetch_all_collections(rpc: &RpcClient) -> Result<Vec<Pubkey>> {
+    let global_pool = fetch_global_pool(rpc)?;
+    let mut collections = vec![];
+    if global_pool.collection_count == 0 {
+        return Ok(collections);
+    }
+    for page in 0..=CollectionRegistry::page_of(global_pool.collection_count - 1) {
+        if let Some(registry) = fetch_collection_registry(rpc, page)? {
+            collections.extend(
+                registry
+                    .collections
+                    .into_iter()
+                    .filter(|collection| *collection != Pubkey::default()),
+            );
+        }
+    }
+    Ok(collections)
+}
+
+/// Deposits owned by `owner`, or every deposit.
+pub fn fetch_all_deposits(
+    rpc: &RpcClient,
+    owner: Option<&Pubkey>,
+) -> Result<Vec<(Pubkey, NftDeposit)>> {
+    // owner is the first field after the discriminator
+    let filters: Vec<RpcFilter> = owner
+        .map(|owner| RpcFilter::Memcmp {
+            offset: 8,
+            bytes: owner.to_bytes().to_vec(),
+        })
+        .into_iter()
+        .collect();
+    fetch_all(rpc, &filters)
+}
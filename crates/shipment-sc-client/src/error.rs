@@ -0,0 +1,163 @@
+//! Client errors and decoding of program errors from transaction logs.
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use shipment_sc::error::ShipmentError;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("rpc transport error: {0}")]
+    Transport(String),
+    #[error("rpc error {code}: {message}")]
+    Rpc {
+        code: i64,
+        message: String,
+        logs: Vec<String>,
+    },
+    #[error("failed to encode transaction: {0}")]
+    Encode(String),
+    #[error("invalid rpc response: {0}")]
+    InvalidResponse(String),
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("failed to decode account {0}: {1}")]
+    Decode(Pubkey, String),
+    #[error("transaction failed: {err}")]
+    Transaction { err: String, logs: Vec<String> },
+    #[error("transaction {0} was not confirmed")]
+    Unconfirmed(String),
+}
+
+impl ClientError {
+    /// Program logs of a failed simulation or transaction, empty otherwise.
+    pub fn logs(&self) -> &[String] {
+        match self {
+            ClientError::Rpc { logs, .. } | ClientError::Transaction { logs, .. } => logs,
+            _ => &[],
+        }
+    }
+
+    /// Program error the transaction failed with, if the logs carry one.
+    pub fn program_error(&self) -> Option<ProgramError> {
+        program_error_from_logs(self.logs())
+    }
+}
+
+/// Every `ShipmentError` in declaration order, code `ERROR_CODE_OFFSET + index`.
+pub const SHIPMENT_ERRORS: [ShipmentError; 45] = [
+    ShipmentError::InvalidAdmin,
+    ShipmentError::InvalidUpdater,
+    ShipmentError::InvalidMetadata,
+    ShipmentError::InvalidCollection,
+    ShipmentError::MetadataCreatorParseError,
+    ShipmentError::AlreadyDeposited,
+    ShipmentError::NotDeposited,
+    ShipmentError::InvalidOwner,
+    ShipmentError::InvalidNFTAddress,
+    ShipmentError::DisabledWithdrawal,
+    ShipmentError::MathOverflow,
+    ShipmentError::WithdrawalAlreadyRequested,
+    ShipmentError::WithdrawalNotRequested,
+    ShipmentError::InvalidGracePeriod,
+    ShipmentError::InvalidLockExpiry,
+    ShipmentError::DepositDisputed,
+    ShipmentError::NotDisputed,
+    ShipmentError::InvalidDestination,
+    ShipmentError::DeliveryProofRequired,
+    ShipmentError::InvalidDeliveryProof,
+    ShipmentError::ReceiptRequired,
+    ShipmentError::InvalidReceipt,
+    ShipmentError::InvalidNewOwner,
+    ShipmentError::RedemptionRequired,
+    ShipmentError::InvalidRedemption,
+    ShipmentError::InvalidRentRecipient,
+    ShipmentError::CollectionMetadataTooLong,
+    ShipmentError::CollectionHasDeposits,
+    ShipmentError::CollectionNotAllowed,
+    ShipmentError::InvalidMerkleProof,
+    ShipmentError::DepositorNotAllowed,
+    ShipmentError::QuotaExceeded,
+    ShipmentError::Web2UserIndexFull,
+    ShipmentError::IdentityAttestationRequired,
+    ShipmentError::InvalidIdentityAttestation,
+    ShipmentError::Web2UserNotLinked,
+    ShipmentError::UserHasDeposits,
+    ShipmentError::UserHasRoles,
+    ShipmentError::DepositConsentRequired,
+    ShipmentError::Web2UserTooLong,
+    ShipmentError::Web2UserIndexPageSkipped,
+    ShipmentError::InvalidDepositQuota,
+    ShipmentError::InvalidDepositStatus,
+    ShipmentError::InvalidLegacyAccount,
+    ShipmentError::InvalidWeb2User,
+];
+
+/// `ShipmentError` with the given custom error code.
+pub fn shipment_error(code: u32) -> Option<ShipmentError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    SHIPMENT_ERRORS.get(index as usize).copied()
+}
+
+/// Custom error a program instruction failed with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramError {
+    pub code: u32,
+    /// Error name logged by Anchor, e.g. `InvalidAdmin` or `ConstraintSeeds`.
+    pub name: Option<String>,
+    pub message: Option<String>,
+}
+
+impl ProgramError {
+    pub fn shipment_error(&self) -> Option<ShipmentError> {
+        shipment_error(self.code)
+    }
+}
+
+impl std::fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.name, &self.message) {
+            (Some(name), Some(message)) => write!(f, "{name} ({}): {message}", self.code),
+            (Some(name), None) => write!(f, "{name} ({})", self.code),
+            _ => write!(f, "custom program error {:#x}", self.code),
+        }
+    }
+}
+
+/// Reads the error of a failed instruction from its logs. Anchor logs
+/// `Error Code: <name>. Error Number: <code>. Error Message: <message>.`,
+/// the runtime logs `failed: custom program error: 0x<code>`.
+pub fn program_error_from_logs<S: AsRef<str>>(logs: &[S]) -> Option<ProgramError> {
+    for log in logs.iter().rev() {
+        let log = log.as_ref();
+        if let Some(rest) = log.split("Error Code: ").nth(1) {
+            let name = rest.split('.').next().map(str::to_string);
+            let code = rest
+                .split("Error Number: ")
+                .nth(1)
+                .and_then(|rest| rest.split('.').next())
+                .and_then(|code| code.parse().ok());
+            let message = rest
+                .split("Error Message: ")
+                .nth(1)
+                .map(|message| message.trim_end_matches('.').to_string());
+            if let Some(code) = code {
+                return Some(ProgramError {
+                    code,
+                    name,
+                    message,
+                });
+            }
+        }
+    }
+
+    logs.iter().rev().find_map(|log| {
+        let hex = log.as_ref().split("custom program error: 0x").nth(1)?;
+        let code = u32::from_str_radix(hex.trim(), 16).ok()?;
+        let error = shipment_error(code);
+        Some(ProgramError {
+            code,
+            name: error.map(|error| error.name()),
+            message: error.map(|error| error.to_string()),
+        })
+    })
+}
@@ -0,0 +1,19 @@
+//! Decoding of program events from transaction logs.
+
+use anchor_lang::Event;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+pub use shipment_sc::event::*;
+
+/// Events of type `T` in transaction logs. Anchor emits each event as a
+/// `Program data: <base64>` line holding its discriminator and borsh data.
+pub fn events_from_logs<T: Event, S: AsRef<str>>(logs: &[S]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.as_ref().strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|data| {
+            let data = data.strip_prefix(&T::DISCRIMINATOR)?;
+            T::try_from_slice(data).ok()
+        })
+        .collect()
+}
@@ -0,0 +1,1024 @@
+//! Typed builders for every shipment-sc instruction.
+//!
+//! Builders only derive addresses, they never touch the network. Account
+//! state a builder depends on (treasury, collection of a deposit, registry
+//! page, ...) is passed in by the caller, usually read with [`crate::accounts`].
+
+use anchor_lang::{
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::spl_token,
+    token_2022::spl_token_2022,
+};
+use shipment_sc::{
+    accounts, instruction,
+    state::{DepositQuota, DisputeOutcome, RentDestination},
+    ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::pda;
+
+fn program_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Arguments of `ix` if it is a program instruction of type `T`.
+pub fn decode<T: InstructionData + AnchorDeserialize>(ix: &Instruction) -> Option<T> {
+    if ix.program_id != ID {
+        return None;
+    }
+    let data = ix.data.strip_prefix(&T::DISCRIMINATOR)?;
+    T::try_from_slice(data).ok()
+}
+
+/// Optional receipt accounts of `owner`, filled when the deposit carries a receipt.
+struct ReceiptAccounts {
+    receipt_mint: Option<Pubkey>,
+    receipt_token_account: Option<Pubkey>,
+    token_2022_program: Option<Pubkey>,
+}
+
+impl ReceiptAccounts {
+    fn new(receipt: bool, owner: &Pubkey, mint: &Pubkey) -> Self {
+        ReceiptAccounts {
+            receipt_mint: receipt.then(|| pda::receipt_mint(mint).0),
+            receipt_token_account: receipt.then(|| pda::receipt_token_account(owner, mint)),
+            token_2022_program: receipt.then_some(spl_token_2022::ID),
+        }
+    }
+}
+
+pub fn initialize(admin: &Pubkey, treasury: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::Initialize {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::Initialize {
+            treasury: *treasury,
+        },
+    )
+}
+
+pub fn transfer_super_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::TransferSuperAdmin {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+        },
+        instruction::TransferSuperAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn change_treasury(admin: &Pubkey, new_treasury: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::ChangeTreasury {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeTreasury {
+            new_treasury: *new_treasury,
+        },
+    )
+}
+
+pub fn change_grace_period(admin: &Pubkey, grace_period: i64) -> Instruction {
+    program_ix(
+        accounts::ChangeGracePeriod {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeGracePeriod { grace_period },
+    )
+}
+
+pub fn change_delivery_proof(
+    admin: &Pubkey,
+    required: bool,
+    courier_oracle: &Pubkey,
+) -> Instruction {
+    program_ix(
+        accounts::ChangeDeliveryProof {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeDeliveryProof {
+            required,
+            courier_oracle: *courier_oracle,
+        },
+    )
+}
+
+pub fn change_transfer_policy(admin: &Pubkey, cosign_required: bool) -> Instruction {
+    program_ix(
+        accounts::ChangeTransferPolicy {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeTransferPolicy { cosign_required },
+    )
+}
+
+/// `redemption_mint` of `None` disables redemption for the collection.
+pub fn change_redemption_mint(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    redemption_mint: Option<Pubkey>,
+) -> Instruction {
+    program_ix(
+        accounts::ChangeRedemptionMint {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+            redemption_mint,
+        },
+        instruction::ChangeRedemptionMint {
+            collection: *collection,
+        },
+    )
+}
+
+pub fn change_rent_destination(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    rent_destination: RentDestination,
+) -> Instruction {
+    program_ix(
+        accounts::ChangeRentDestination {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+        },
+        instruction::ChangeRentDestination {
+            collection: *collection,
+            rent_destination,
+        },
+    )
+}
+
+pub fn change_collection_metadata(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    name: &str,
+    uri: &str,
+) -> Instruction {
+    program_ix(
+        accounts::ChangeCollectionMetadata {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+        },
+        instruction::ChangeCollectionMetadata {
+            collection: *collection,
+            name: name.to_string(),
+            uri: uri.to_string(),
+        },
+    )
+}
+
+pub fn init_user(payer: &Pubkey, user: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::InitUser {
+            payer: *payer,
+            user: *user,
+            user_pool: pda::user_pool(user).0,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitUser {},
+    )
+}
+
+/// Closing by `authority`, an admin unless it is `user` itself. The user pool
+/// rent goes to `recipient`.
+pub fn close_user(authority: &Pubkey, user: &Pubkey, recipient: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::CloseUser {
+            authority: *authority,
+            user: *user,
+            global_pool: pda::global_pool().0,
+            admin_pool: (authority != user).then(|| pda::user_pool(authority).0),
+            user_pool: pda::user_pool(user).0,
+            recipient: *recipient,
+        },
+        instruction::CloseUser {},
+    )
+}
+
+/// Roles left `None` are unchanged.
+pub fn change_role(
+    admin: &Pubkey,
+    user: &Pubkey,
+    is_admin: Option<bool>,
+    is_updater: Option<bool>,
+) -> Instruction {
+    program_ix(
+        accounts::ChangeRole {
+            admin: *admin,
+            user: *user,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            user_pool: pda::user_pool(user).0,
+        },
+        instruction::ChangeRole {
+            admin: is_admin,
+            updater: is_updater,
+        },
+    )
+}
+
+/// `collection_count` is `GlobalPool::collection_count`, it selects the
+/// registry page the collection is appended to.
+pub fn register_collection(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    collection_count: u64,
+) -> Instruction {
+    let page = shipment_sc::state::CollectionRegistry::page_of(collection_count);
+    program_ix(
+        accounts::RegisterCollection {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+            collection_registry: pda::collection_registry(page).0,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::RegisterCollection {
+            collection: *collection,
+        },
+    )
+}
+
+pub fn revoke_collection(admin: &Pubkey, collection: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::RevokeCollection {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+        },
+        instruction::RevokeCollection {
+            collection: *collection,
+        },
+    )
+}
+
+/// `registry_index` is `CollectionPool::registry_index` of the closed collection.
+pub fn close_collection(admin: &Pubkey, collection: &Pubkey, registry_index: u64) -> Instruction {
+    let page = shipment_sc::state::CollectionRegistry::page_of(registry_index);
+    program_ix(
+        accounts::CloseCollection {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+            collection_registry: pda::collection_registry(page).0,
+        },
+        instruction::CloseCollection {
+            collection: *collection,
+        },
+    )
+}
+
+pub fn register_mint(admin: &Pubkey, mint: &Pubkey, collection: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::RegisterMint {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+            mint_allowlist: pda::mint_allowlist(mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterMint {
+            mint: *mint,
+            collection: *collection,
+        },
+    )
+}
+
+pub fn revoke_mint(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::RevokeMint {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            mint_allowlist: pda::mint_allowlist(mint).0,
+        },
+        instruction::RevokeMint { mint: *mint },
+    )
+}
+
+pub fn change_mint_merkle_root(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    mint_merkle_root: [u8; 32],
+) -> Instruction {
+    program_ix(
+        accounts::ChangeMintMerkleRoot {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+        },
+        instruction::ChangeMintMerkleRoot {
+            collection: *collection,
+            mint_merkle_root,
+        },
+    )
+}
+
+pub fn change_depositor_merkle_root(
+    admin: &Pubkey,
+    depositor_merkle_root: [u8; 32],
+) -> Instruction {
+    program_ix(
+        accounts::ChangeDepositorMerkleRoot {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeDepositorMerkleRoot {
+            depositor_merkle_root,
+        },
+    )
+}
+
+pub fn change_collection_depositor_merkle_root(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    depositor_merkle_root: [u8; 32],
+) -> Instruction {
+    program_ix(
+        accounts::ChangeCollectionDepositorMerkleRoot {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+        },
+        instruction::ChangeCollectionDepositorMerkleRoot {
+            collection: *collection,
+            depositor_merkle_root,
+        },
+    )
+}
+
+pub fn change_deposit_quota(admin: &Pubkey, deposit_quota: DepositQuota) -> Instruction {
+    program_ix(
+        accounts::ChangeDepositQuota {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeDepositQuota { deposit_quota },
+    )
+}
+
+pub fn change_collection_deposit_quota(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    deposit_quota: DepositQuota,
+) -> Instruction {
+    program_ix(
+        accounts::ChangeCollectionDepositQuota {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            collection_pool: pda::collection_pool(collection).0,
+        },
+        instruction::ChangeCollectionDepositQuota {
+            collection: *collection,
+            deposit_quota,
+        },
+    )
+}
+
+/// Default `identity_authority` stops checking deposit uuids.
+pub fn change_identity_authority(admin: &Pubkey, identity_authority: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::ChangeIdentityAuthority {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+        },
+        instruction::ChangeIdentityAuthority {
+            identity_authority: *identity_authority,
+        },
+    )
+}
+
+/// Must be preceded by an ed25519 verification of the identity authority
+/// signing [`shipment_sc::validations::web2_identity_message`].
+pub fn link_web2_identity(user: &Pubkey, uuid: &str) -> Instruction {
+    program_ix(
+        accounts::LinkWeb2Identity {
+            user: *user,
+            global_pool: pda::global_pool().0,
+            user_pool: pda::user_pool(user).0,
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::LinkWeb2Identity {
+            uuid: uuid.to_string(),
+        },
+    )
+}
+
+/// Lets `delegate` deposit on behalf of `beneficiary` without its signature.
+pub fn register_deposit_delegate(beneficiary: &Pubkey, delegate: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::RegisterDepositDelegate {
+            beneficiary: *beneficiary,
+            deposit_delegate: pda::deposit_delegate(beneficiary, delegate).0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterDepositDelegate {
+            delegate: *delegate,
+        },
+    )
+}
+
+pub fn revoke_deposit_delegate(beneficiary: &Pubkey, delegate: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::RevokeDepositDelegate {
+            beneficiary: *beneficiary,
+            deposit_delegate: pda::deposit_delegate(beneficiary, delegate).0,
+        },
+        instruction::RevokeDepositDelegate {
+            delegate: *delegate,
+        },
+    )
+}
+
+/// Optional parts of a deposit.
+#[derive(Debug, Clone, Default)]
+pub struct DepositOptions {
+    /// Mint has a `MintAllowlist` account.
+    pub mint_allowlist: bool,
+    /// Proof of the mint against the collection mint merkle root.
+    pub mint_proof: Option<Vec<[u8; 32]>>,
+    /// Proof of the depositor against the depositor merkle root.
+    pub depositor_proof: Option<Vec<[u8; 32]>>,
+    /// Mint a custody receipt to the deposit owner.
+    pub receipt: bool,
+    /// Page of the web2 user index listing the deposit, it must have a free slot.
+    pub index_page: u64,
+}
+
+/// Deposit of `mint` held in the associated token account of `user`.
+pub fn deposit_nft(
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    uuid: &str,
+    options: DepositOptions,
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(options.receipt, user, mint);
+    program_ix(
+        accounts::DepositNft {
+            user: *user,
+            global_pool: pda::global_pool().0,
+            user_pool: pda::user_pool(user).0,
+            collection_pool: pda::collection_pool(collection).0,
+            token_mint: *mint,
+            mint_allowlist: options.mint_allowlist.then(|| pda::mint_allowlist(mint).0),
+            mint_metadata: pda::metadata(mint),
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: pda::web2_user_index(uuid, options.index_page).0,
+            previous_web2_user_index: options
+                .index_page
+                .checked_sub(1)
+                .map(|page| pda::web2_user_index(uuid, page).0),
+            token_account: get_associated_token_address(user, mint),
+            dest_token_account: pda::custody_token_account(mint),
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: system_program::ID,
+        },
+        instruction::DepositNft {
+            collection: *collection,
+            user: uuid.to_string(),
+            mint_proof: options.mint_proof,
+            depositor_proof: options.depositor_proof,
+            index_page: options.index_page,
+        },
+    )
+}
+
+/// Deposit of `mint` held in the associated token account of `depositor`,
+/// owned by `beneficiary`. With `delegated` the beneficiary consents through
+/// its [`register_deposit_delegate`] account, otherwise it must co-sign.
+pub fn deposit_nft_for(
+    depositor: &Pubkey,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    uuid: &str,
+    delegated: bool,
+    options: DepositOptions,
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(options.receipt, beneficiary, mint);
+    let mut ix = program_ix(
+        accounts::DepositNftFor {
+            depositor: *depositor,
+            beneficiary: *beneficiary,
+            deposit_delegate: delegated.then(|| pda::deposit_delegate(beneficiary, depositor).0),
+            global_pool: pda::global_pool().0,
+            user_pool: pda::user_pool(beneficiary).0,
+            collection_pool: pda::collection_pool(collection).0,
+            token_mint: *mint,
+            mint_allowlist: options.mint_allowlist.then(|| pda::mint_allowlist(mint).0),
+            mint_metadata: pda::metadata(mint),
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: pda::web2_user_index(uuid, options.index_page).0,
+            previous_web2_user_index: options
+                .index_page
+                .checked_sub(1)
+                .map(|page| pda::web2_user_index(uuid, page).0),
+            token_account: get_associated_token_address(depositor, mint),
+            dest_token_account: pda::custody_token_account(mint),
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: system_program::ID,
+        },
+        instruction::DepositNftFor {
+            collection: *collection,
+            user: uuid.to_string(),
+            mint_proof: options.mint_proof,
+            depositor_proof: options.depositor_proof,
+            index_page: options.index_page,
+        },
+    );
+    ix.accounts[1].is_signer = !delegated;
+    ix
+}
+
+/// Fields left `None` are unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DepositUpdate {
+    pub status: Option<u8>,
+    pub locked: Option<bool>,
+    pub lock_reason: Option<u8>,
+    pub locked_until: Option<i64>,
+}
+
+pub fn update_deposit(
+    updater: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    update: DepositUpdate,
+) -> Instruction {
+    program_ix(
+        accounts::UpdateDeposit {
+            updater: *updater,
+            user: *owner,
+            global_pool: pda::global_pool().0,
+            updater_pool: pda::user_pool(updater).0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+        },
+        instruction::UpdateDeposit {
+            status: update.status,
+            locked: update.locked,
+            lock_reason: update.lock_reason,
+            locked_until: update.locked_until,
+        },
+    )
+}
+
+/// Withdrawal to `owner`, signed by the owner or a payer with the updater role.
+/// `web2_user_index` is [`pda::deposit_web2_user_index`] of the deposit.
+pub fn withdraw_owner(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    web2_user_index: &Pubkey,
+    collection: &Pubkey,
+    receipt: bool,
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(receipt, owner, mint);
+    program_ix(
+        accounts::WithdrawOwner {
+            payer: *payer,
+            user: *owner,
+            global_pool: pda::global_pool().0,
+            payer_pool: pda::user_pool(payer).0,
+            user_pool: pda::user_pool(owner).0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: *web2_user_index,
+            collection_pool: pda::collection_pool(collection).0,
+            dest_token_account: pda::custody_token_account(mint),
+            user_token_account: get_associated_token_address(owner, mint),
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawOwner {},
+    )
+}
+
+/// `web2_user_index` is [`pda::deposit_web2_user_index`] of the deposit, it
+/// moves to page `new_index_page` of the `new_user` index. `collection` is the
+/// collection the deposit was made under.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_deposit_ownership(
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    web2_user_index: &Pubkey,
+    admin: Option<&Pubkey>,
+    receipt: bool,
+    new_user: &str,
+    new_index_page: u64,
+) -> Instruction {
+    program_ix(
+        accounts::TransferDepositOwnership {
+            owner: *owner,
+            new_owner: *new_owner,
+            admin: admin.copied(),
+            global_pool: pda::global_pool().0,
+            admin_pool: admin.map(|admin| pda::user_pool(admin).0),
+            owner_pool: pda::user_pool(owner).0,
+            new_owner_pool: pda::user_pool(new_owner).0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: *web2_user_index,
+            new_web2_user_index: pda::web2_user_index(new_user, new_index_page).0,
+            previous_web2_user_index: new_index_page
+                .checked_sub(1)
+                .map(|page| pda::web2_user_index(new_user, page).0),
+            collection_pool: pda::collection_pool(collection).0,
+            receipt_mint: receipt.then(|| pda::receipt_mint(mint).0),
+            receipt_token_account: receipt.then(|| pda::receipt_token_account(owner, mint)),
+            new_receipt_token_account: receipt.then(|| pda::receipt_token_account(new_owner, mint)),
+            token_2022_program: receipt.then_some(spl_token_2022::ID),
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::TransferDepositOwnership {
+            new_user: new_user.to_string(),
+            new_index_page,
+        },
+    )
+}
+
+pub fn request_withdrawal(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::RequestWithdrawal {
+            owner: *owner,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+        },
+        instruction::RequestWithdrawal {},
+    )
+}
+
+/// `web2_user_index` is [`pda::deposit_web2_user_index`] of the deposit.
+pub fn approve_withdrawal(
+    updater: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    web2_user_index: &Pubkey,
+    collection: &Pubkey,
+    receipt: bool,
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(receipt, owner, mint);
+    program_ix(
+        accounts::ApproveWithdrawal {
+            updater: *updater,
+            user: *owner,
+            global_pool: pda::global_pool().0,
+            updater_pool: pda::user_pool(updater).0,
+            user_pool: pda::user_pool(owner).0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: *web2_user_index,
+            collection_pool: pda::collection_pool(collection).0,
+            dest_token_account: pda::custody_token_account(mint),
+            user_token_account: get_associated_token_address(owner, mint),
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ApproveWithdrawal {},
+    )
+}
+
+pub fn deny_withdrawal(updater: &Pubkey, mint: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::DenyWithdrawal {
+            updater: *updater,
+            global_pool: pda::global_pool().0,
+            updater_pool: pda::user_pool(updater).0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+        },
+        instruction::DenyWithdrawal {},
+    )
+}
+
+/// `web2_user_index` is [`pda::deposit_web2_user_index`] of the deposit.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_treasury(
+    admin: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    web2_user_index: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    receipt: bool,
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(receipt, owner, mint);
+    program_ix(
+        accounts::WithdrawTreasury {
+            admin: *admin,
+            user: *owner,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            user_pool: pda::user_pool(owner).0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: *web2_user_index,
+            collection_pool: pda::collection_pool(collection).0,
+            treasury: *treasury,
+            dest_token_account: pda::custody_token_account(mint),
+            treasury_token_account: get_associated_token_address(treasury, mint),
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawTreasury {},
+    )
+}
+
+pub fn open_dispute(owner: &Pubkey, mint: &Pubkey, evidence_hash: [u8; 32]) -> Instruction {
+    program_ix(
+        accounts::OpenDispute {
+            owner: *owner,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+            dispute: pda::dispute(mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::OpenDispute { evidence_hash },
+    )
+}
+
+/// Accounts of a dispute resolution besides the admin and the outcome.
+#[derive(Debug, Clone, Copy)]
+pub struct DisputedDeposit {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// [`pda::deposit_web2_user_index`] of the deposit.
+    pub web2_user_index: Pubkey,
+    pub collection: Pubkey,
+    pub treasury: Pubkey,
+    /// Receives the deposit rent of a finalize outcome, chosen by the
+    /// collection rent destination.
+    pub rent_recipient: Option<Pubkey>,
+    /// Redemption of a finalize outcome, when the collection redeems.
+    pub redemption: Option<Redemption>,
+    pub receipt: bool,
+}
+
+/// `destination_token_account` receives the nft when the outcome returns it
+/// to the owner or sends it to the treasury.
+pub fn resolve_dispute(
+    admin: &Pubkey,
+    deposit: &DisputedDeposit,
+    destination_token_account: Option<Pubkey>,
+    outcome: DisputeOutcome,
+    resolution_hash: [u8; 32],
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(deposit.receipt, &deposit.owner, &deposit.mint);
+    let redemption = deposit.redemption.as_ref();
+    program_ix(
+        accounts::ResolveDispute {
+            admin: *admin,
+            user: deposit.owner,
+            global_pool: pda::global_pool().0,
+            admin_pool: pda::user_pool(admin).0,
+            user_pool: pda::user_pool(&deposit.owner).0,
+            token_mint: deposit.mint,
+            deposit_state: pda::nft_deposit(&deposit.mint).0,
+            web2_user_index: deposit.web2_user_index,
+            collection_pool: pda::collection_pool(&deposit.collection).0,
+            dispute: pda::dispute(&deposit.mint).0,
+            treasury: deposit.treasury,
+            rent_recipient: deposit.rent_recipient,
+            dest_token_account: pda::custody_token_account(&deposit.mint),
+            destination_token_account,
+            redemption_mint: redemption.map(|redemption| redemption.mint),
+            redemption_authority: redemption
+                .map(|_| pda::redemption_authority(&deposit.collection).0),
+            redemption_source: redemption.and_then(|redemption| redemption.source),
+            redemption_token_account: redemption
+                .map(|redemption| get_associated_token_address(&deposit.owner, &redemption.mint)),
+            associated_token_program: redemption.map(|_| associated_token::ID),
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            system_program: system_program::ID,
+        },
+        instruction::ResolveDispute {
+            outcome,
+            resolution_hash,
+        },
+    )
+}
+
+/// Redemption token handed out by a collection on finalize, including a
+/// dispute resolved by finalizing.
+#[derive(Debug, Clone, Copy)]
+pub struct Redemption {
+    pub mint: Pubkey,
+    /// Token account the redemption token is transferred from, `None` when
+    /// the redemption authority mints it.
+    pub source: Option<Pubkey>,
+}
+
+/// Accounts of a finalize besides the updater and the proof.
+#[derive(Debug, Clone, Copy)]
+pub struct FinalizedDeposit {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// [`pda::deposit_web2_user_index`] of the deposit.
+    pub web2_user_index: Pubkey,
+    pub collection: Pubkey,
+    pub treasury: Pubkey,
+    /// Receives the deposit rent, chosen by the collection rent destination.
+    pub rent_recipient: Pubkey,
+    pub redemption: Option<Redemption>,
+    pub receipt: bool,
+}
+
+pub fn finalize_deposit(
+    updater: &Pubkey,
+    deposit: &FinalizedDeposit,
+    proof_hash: [u8; 32],
+) -> Instruction {
+    let receipt = ReceiptAccounts::new(deposit.receipt, &deposit.owner, &deposit.mint);
+    let redemption = deposit.redemption.as_ref();
+    program_ix(
+        accounts::FinalizeDeposit {
+            updater: *updater,
+            user: deposit.owner,
+            global_pool: pda::global_pool().0,
+            updater_pool: pda::user_pool(updater).0,
+            user_pool: pda::user_pool(&deposit.owner).0,
+            token_mint: deposit.mint,
+            deposit_state: pda::nft_deposit(&deposit.mint).0,
+            web2_user_index: deposit.web2_user_index,
+            collection_pool: pda::collection_pool(&deposit.collection).0,
+            treasury: deposit.treasury,
+            rent_recipient: deposit.rent_recipient,
+            dest_token_account: pda::custody_token_account(&deposit.mint),
+            redemption_mint: redemption.map(|redemption| redemption.mint),
+            redemption_authority: redemption
+                .map(|_| pda::redemption_authority(&deposit.collection).0),
+            redemption_source: redemption.and_then(|redemption| redemption.source),
+            redemption_token_account: redemption
+                .map(|redemption| get_associated_token_address(&deposit.owner, &redemption.mint)),
+            associated_token_program: redemption.map(|_| associated_token::ID),
+            instructions: sysvar::instructions::ID,
+            receipt_mint: receipt.receipt_mint,
+            receipt_token_account: receipt.receipt_token_account,
+            token_program: spl_token::ID,
+            token_2022_program: receipt.token_2022_program,
+            system_program: system_program::ID,
+        },
+        instruction::FinalizeDeposit { proof_hash },
+    )
+}
+
+pub fn migrate_global_pool(admin: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::MigrateGlobalPool {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGlobalPool {},
+    )
+}
+
+pub fn migrate_user_pool(admin: &Pubkey, user: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::MigrateUserPool {
+            admin: *admin,
+            user: *user,
+            global_pool: pda::global_pool().0,
+            user_pool: pda::user_pool(user).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateUserPool {},
+    )
+}
+
+/// `collection_count` is the global pool's count once the global pool is
+/// migrated, the collection is appended to the registry page holding it.
+pub fn migrate_collection_pool(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    collection_count: u64,
+) -> Instruction {
+    let page = shipment_sc::state::CollectionRegistry::page_of(collection_count);
+    program_ix(
+        accounts::MigrateCollectionPool {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            collection_pool: pda::collection_pool(collection).0,
+            collection_registry: pda::collection_registry(page).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateCollectionPool {
+            collection: *collection,
+        },
+    )
+}
+
+/// Migrates the live legacy deposit of `mint` in place. `uuid` is the web2
+/// user it was made for, it is listed in page `index_page` of its index.
+pub fn migrate_nft_deposit(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    uuid: &str,
+    index_page: u64,
+) -> Instruction {
+    program_ix(
+        accounts::MigrateNftDeposit {
+            admin: *admin,
+            global_pool: pda::global_pool().0,
+            collection_pool: pda::collection_pool(collection).0,
+            token_mint: *mint,
+            mint_metadata: pda::metadata(mint),
+            deposit_state: pda::nft_deposit(mint).0,
+            web2_user_index: pda::web2_user_index(uuid, index_page).0,
+            previous_web2_user_index: index_page
+                .checked_sub(1)
+                .map(|page| pda::web2_user_index(uuid, page).0),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateNftDeposit {
+            collection: *collection,
+            user: uuid.to_string(),
+            index_page,
+        },
+    )
+}
+
+/// Closes the withdrawn legacy deposit of `mint`, its rent goes back to `owner`.
+pub fn close_legacy_deposit(admin: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    program_ix(
+        accounts::CloseLegacyDeposit {
+            admin: *admin,
+            owner: *owner,
+            global_pool: pda::global_pool().0,
+            token_mint: *mint,
+            deposit_state: pda::nft_deposit(mint).0,
+        },
+        instruction::CloseLegacyDeposit {},
+    )
+}
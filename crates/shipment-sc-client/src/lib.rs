@@ -0,0 +1,20 @@
+//! Rust client for the shipment-sc program: PDA derivation, instruction
+//! builders, account decoding and decoding of program errors and events.
+//!
+//! ```no_run
+//! use shipment_sc_client::{accounts, instructions, rpc::RpcClient};
+//! # let user = solana_sdk::pubkey::Pubkey::new_unique();
+//!
+//! let rpc = RpcClient::new("https://api.devnet.solana.com");
+//! let global_pool = accounts::fetch_global_pool(&rpc).unwrap();
+//! let ix = instructions::init_user(&user, &user);
+//! ```
+
+pub mod accounts;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod rpc;
+
+pub use shipment_sc::{constant, error::ShipmentError, state, ID};
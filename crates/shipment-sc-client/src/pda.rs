@@ -0,0 +1,98 @@
+//! Program derived addresses for every seed in `shipment_sc::constant`.
+
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address, get_associated_token_address_with_program_id,
+    },
+    token_2022::spl_token_2022,
+};
+use shipment_sc::{
+    constant::*,
+    state::{NftDeposit, Web2UserIndex},
+    ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn global_pool() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_AUTHORITY_SEED.as_ref()], &ID)
+}
+
+pub fn user_pool(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_POOL_SEED.as_ref(), user.as_ref()], &ID)
+}
+
+pub fn collection_pool(collection: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COLLECTION_POOL_SEED.as_ref(), collection.as_ref()], &ID)
+}
+
+pub fn collection_registry(page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COLLECTION_REGISTRY_SEED.as_ref(), &page.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn mint_allowlist(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_ALLOWLIST_SEED.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn nft_deposit(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NFT_DEPOSIT_SEED.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn web2_user_index(uuid: &str, page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            WEB2_USER_INDEX_SEED.as_ref(),
+            &Web2UserIndex::user_seed(uuid),
+            &page.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+pub fn deposit_delegate(beneficiary: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DEPOSIT_DELEGATE_SEED.as_ref(),
+            beneficiary.as_ref(),
+            delegate.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn dispute(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_SEED.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn receipt_mint(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_SEED.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn redemption_authority(collection: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REDEMPTION_AUTHORITY_SEED.as_ref(), collection.as_ref()],
+        &ID,
+    )
+}
+
+/// Web2 user index page listing `deposit`.
+pub fn deposit_web2_user_index(deposit: &NftDeposit) -> Pubkey {
+    web2_user_index(&deposit.user, deposit.index_page).0
+}
+
+/// Token account holding deposited `mint` in custody of the global pool.
+pub fn custody_token_account(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&global_pool().0, mint)
+}
+
+/// Token-2022 account of `owner` holding the custody receipt of `mint`.
+pub fn receipt_token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, &receipt_mint(mint).0, &spl_token_2022::ID)
+}
+
+/// Token metadata account of `mint`.
+pub fn metadata(mint: &Pubkey) -> Pubkey {
+    mpl_token_metadata::accounts::Metadata::find_pda(mint).0
+}
@@ -0,0 +1,498 @@
+//! Minimal blocking JSON-RPC client covering the calls the program needs.
+
+use std::{str::FromStr, thread, time::Duration};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::{
+    account::Account,
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::Transaction,
+};
+
+use crate::error::ClientError;
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Attempts, one per `CONFIRM_INTERVAL`, before a sent transaction is
+/// reported unconfirmed.
+const CONFIRM_ATTEMPTS: usize = 60;
+const CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Filter of `getProgramAccounts`.
+#[derive(Debug, Clone)]
+pub enum RpcFilter {
+    DataSize(u64),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl RpcFilter {
+    fn to_json(&self) -> Value {
+        match self {
+            RpcFilter::DataSize(size) => json!({ "dataSize": size }),
+            RpcFilter::Memcmp { offset, bytes } => json!({
+                "memcmp": {
+                    "offset": offset,
+                    "bytes": bs58::encode(bytes).into_string(),
+                }
+            }),
+        }
+    }
+}
+
+/// Outcome of `simulateTransaction`.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// Transaction error, `None` if the simulation succeeded.
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+/// Entry of `getSignaturesForAddress`, newest first.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<Value>,
+    pub block_time: Option<i64>,
+}
+
+/// Confirmed transaction: its top level instructions, with the accounts of
+/// address lookup tables resolved, and its logs.
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmedTransaction {
+    pub instructions: Vec<Instruction>,
+    pub logs: Vec<String>,
+}
+
+impl ConfirmedTransaction {
+    /// Decodes a `getTransaction` result in `json` encoding.
+    pub fn from_json(transaction: Value) -> Result<Self> {
+        let UiTransaction { transaction, meta } = from_value(transaction)?;
+        let UiMessage {
+            account_keys,
+            header,
+            instructions,
+        } = transaction.message;
+        let meta = meta.unwrap_or_default();
+        let loaded = meta.loaded_addresses.unwrap_or_default();
+
+        // Static keys are ordered signers first, writable before readonly in
+        // both groups, and followed by the writable then readonly loaded keys
+        let signed = header.num_required_signatures;
+        let static_keys = account_keys.len();
+        let mut accounts = vec![];
+        for (index, key) in account_keys
+            .iter()
+            .chain(&loaded.writable)
+            .chain(&loaded.readonly)
+            .enumerate()
+        {
+            let is_writable = if index < signed {
+                index < signed - header.num_readonly_signed_accounts
+            } else if index < static_keys {
+                index < static_keys - header.num_readonly_unsigned_accounts
+            } else {
+                index < static_keys + loaded.writable.len()
+            };
+            accounts.push(AccountMeta {
+                pubkey: parse_pubkey(key)?,
+                is_signer: index < signed,
+                is_writable,
+            });
+        }
+
+        let account = |index: usize| {
+            accounts.get(index).cloned().ok_or_else(|| {
+                ClientError::InvalidResponse(format!("account index {index} out of range"))
+            })
+        };
+        let instructions = instructions
+            .into_iter()
+            .map(|ix| {
+                Ok(Instruction {
+                    program_id: account(ix.program_id_index)?.pubkey,
+                    accounts: ix
+                        .accounts
+                        .into_iter()
+                        .map(account)
+                        .collect::<Result<_>>()?,
+                    data: bs58::decode(&ix.data)
+                        .into_vec()
+                        .map_err(|err| ClientError::InvalidResponse(err.to_string()))?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(ConfirmedTransaction {
+            instructions,
+            logs: meta.log_messages.unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct UiTransaction {
+    transaction: UiSignedTransaction,
+    meta: Option<UiTransactionMeta>,
+}
+
+#[derive(Deserialize)]
+struct UiSignedTransaction {
+    message: UiMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiMessage {
+    account_keys: Vec<String>,
+    header: UiMessageHeader,
+    instructions: Vec<UiInstruction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiMessageHeader {
+    num_required_signatures: usize,
+    num_readonly_signed_accounts: usize,
+    num_readonly_unsigned_accounts: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiInstruction {
+    program_id_index: usize,
+    accounts: Vec<usize>,
+    data: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiTransactionMeta {
+    log_messages: Option<Vec<String>>,
+    loaded_addresses: Option<UiLoadedAddresses>,
+}
+
+#[derive(Default, Deserialize)]
+struct UiLoadedAddresses {
+    writable: Vec<String>,
+    readonly: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiAccount {
+    lamports: u64,
+    data: (String, String),
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+impl UiAccount {
+    fn decode(self) -> Result<Account> {
+        Ok(Account {
+            lamports: self.lamports,
+            data: STANDARD
+                .decode(self.data.0)
+                .map_err(|err| ClientError::InvalidResponse(err.to_string()))?,
+            owner: parse_pubkey(&self.owner)?,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+        })
+    }
+}
+
+fn parse_pubkey(key: &str) -> Result<Pubkey> {
+    Pubkey::from_str(key).map_err(|err| ClientError::InvalidResponse(err.to_string()))
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(value: Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|err| ClientError::InvalidResponse(err.to_string()))
+}
+
+pub struct RpcClient {
+    url: String,
+    commitment: CommitmentConfig,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::new_with_commitment(url, CommitmentConfig::confirmed())
+    }
+
+    pub fn new_with_commitment(url: impl Into<String>, commitment: CommitmentConfig) -> Self {
+        RpcClient {
+            url: url.into(),
+            commitment,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn commitment(&self) -> Value {
+        json!(self.commitment.commitment)
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: Value = match self.agent.post(&self.url).send_json(request) {
+            Ok(response) => response.into_json(),
+            // JSON-RPC errors may come with an error status
+            Err(ureq::Error::Status(_, response)) => response.into_json(),
+            Err(err) => return Err(ClientError::Transport(err.to_string())),
+        }
+        .map_err(|err| ClientError::Transport(err.to_string()))?;
+
+        if let Some(error) = response.get("error") {
+            let logs = error
+                .pointer("/data/logs")
+                .cloned()
+                .map(from_value)
+                .transpose()?
+                .unwrap_or_default();
+            return Err(ClientError::Rpc {
+                code: error
+                    .get("code")
+                    .and_then(Value::as_i64)
+                    .unwrap_or_default(),
+                message: error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                logs,
+            });
+        }
+
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| ClientError::InvalidResponse(format!("{method} returned no result")))
+    }
+
+    pub fn get_account(&self, key: &Pubkey) -> Result<Option<Account>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([key.to_string(), { "encoding": "base64", "commitment": self.commitment() }]),
+        )?;
+        let account: Option<UiAccount> = from_value(result["value"].clone())?;
+        account.map(UiAccount::decode).transpose()
+    }
+
+    pub fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        let keys: Vec<String> = keys.iter().map(Pubkey::to_string).collect();
+        let result = self.call(
+            "getMultipleAccounts",
+            json!([keys, { "encoding": "base64", "commitment": self.commitment() }]),
+        )?;
+        let accounts: Vec<Option<UiAccount>> = from_value(result["value"].clone())?;
+        accounts
+            .into_iter()
+            .map(|account| account.map(UiAccount::decode).transpose())
+            .collect()
+    }
+
+    pub fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[RpcFilter],
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        let filters: Vec<Value> = filters.iter().map(RpcFilter::to_json).collect();
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), {
+                "encoding": "base64",
+                "commitment": self.commitment(),
+                "filters": filters,
+            }]),
+        )?;
+
+        #[derive(Deserialize)]
+        struct KeyedAccount {
+            pubkey: String,
+            account: UiAccount,
+        }
+        let accounts: Vec<KeyedAccount> = from_value(result)?;
+        accounts
+            .into_iter()
+            .map(|keyed| Ok((parse_pubkey(&keyed.pubkey)?, keyed.account.decode()?)))
+            .collect()
+    }
+
+    pub fn get_balance(&self, key: &Pubkey) -> Result<u64> {
+        let result = self.call(
+            "getBalance",
+            json!([key.to_string(), { "commitment": self.commitment() }]),
+        )?;
+        from_value(result["value"].clone())
+    }
+
+    pub fn get_slot(&self) -> Result<u64> {
+        from_value(self.call("getSlot", json!([{ "commitment": self.commitment() }]))?)
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call(
+            "getLatestBlockhash",
+            json!([{ "commitment": self.commitment() }]),
+        )?;
+        let blockhash: String = from_value(result["value"]["blockhash"].clone())?;
+        Hash::from_str(&blockhash).map_err(|err| ClientError::InvalidResponse(err.to_string()))
+    }
+
+    fn encode_transaction(transaction: &Transaction) -> Result<String> {
+        let bytes =
+            bincode::serialize(transaction).map_err(|err| ClientError::Encode(err.to_string()))?;
+        Ok(STANDARD.encode(bytes))
+    }
+
+    /// Sends with preflight, a failed preflight returns `ClientError::Rpc`
+    /// carrying the simulation logs.
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let result = self.call(
+            "sendTransaction",
+            json!([Self::encode_transaction(transaction)?, {
+                "encoding": "base64",
+                "preflightCommitment": self.commitment(),
+            }]),
+        )?;
+        let signature: String = from_value(result)?;
+        Signature::from_str(&signature).map_err(|err| ClientError::InvalidResponse(err.to_string()))
+    }
+
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<Simulation> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([Self::encode_transaction(transaction)?, {
+                "encoding": "base64",
+                "commitment": self.commitment(),
+                "sigVerify": false,
+            }]),
+        )?;
+        let value = &result["value"];
+        Ok(Simulation {
+            err: value.get("err").filter(|err| !err.is_null()).cloned(),
+            logs: from_value(value.get("logs").cloned().unwrap_or(Value::Null))
+                .ok()
+                .unwrap_or_default(),
+            units_consumed: value.get("unitsConsumed").and_then(Value::as_u64),
+        })
+    }
+
+    /// Status of a processed transaction, `None` while it is unknown or below
+    /// the client commitment.
+    pub fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<std::result::Result<(), Value>>> {
+        let result = self.call(
+            "getSignatureStatuses",
+            json!([[signature.to_string()], { "searchTransactionHistory": true }]),
+        )?;
+        let status = &result["value"][0];
+        if status.is_null() {
+            return Ok(None);
+        }
+        let reached = match self.commitment.commitment {
+            CommitmentLevel::Finalized => status["confirmationStatus"] == "finalized",
+            CommitmentLevel::Confirmed => status["confirmationStatus"] != "processed",
+            _ => true,
+        };
+        if !reached {
+            return Ok(None);
+        }
+        Ok(Some(match status.get("err") {
+            Some(err) if !err.is_null() => Err(err.clone()),
+            _ => Ok(()),
+        }))
+    }
+
+    pub fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = self.send_transaction(transaction)?;
+        for _ in 0..CONFIRM_ATTEMPTS {
+            match self.get_signature_status(&signature)? {
+                Some(Ok(())) => return Ok(signature),
+                Some(Err(err)) => {
+                    return Err(ClientError::Transaction {
+                        err: err.to_string(),
+                        logs: self.get_transaction_logs(&signature)?,
+                    })
+                }
+                None => thread::sleep(CONFIRM_INTERVAL),
+            }
+        }
+        Err(ClientError::Unconfirmed(signature.to_string()))
+    }
+
+    /// Log messages of a confirmed transaction, empty if it is not found.
+    pub fn get_transaction_logs(&self, signature: &Signature) -> Result<Vec<String>> {
+        let result = self.get_transaction(signature)?;
+        Ok(result
+            .and_then(|transaction| transaction.pointer("/meta/logMessages").cloned())
+            .map(from_value)
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Instructions and logs of a confirmed transaction, `None` if it is not found.
+    pub fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransaction>> {
+        self.get_transaction(signature)?
+            .map(ConfirmedTransaction::from_json)
+            .transpose()
+    }
+
+    /// Confirmed transaction in `json` encoding.
+    pub fn get_transaction(&self, signature: &Signature) -> Result<Option<Value>> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature.to_string(), {
+                "encoding": "json",
+                "commitment": self.commitment(),
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+        Ok((!result.is_null()).then_some(result))
+    }
+
+    /// Signatures of transactions referencing `address`, newest first,
+    /// starting before `before` and stopping at `until`.
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>> {
+        let result = self.call(
+            "getSignaturesForAddress",
+            json!([address.to_string(), {
+                "commitment": self.commitment(),
+                "before": before,
+                "until": until,
+                "limit": limit,
+            }]),
+        )?;
+        from_value(result)
+    }
+}
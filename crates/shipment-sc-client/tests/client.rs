@@ -0,0 +1,285 @@
+use anchor_lang::{AccountSerialize, AnchorSerialize, Discriminator, InstructionData};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use shipment_sc::{constant::MAX_DAILY_DEPOSITS, error::ShipmentError, state::*};
+use shipment_sc_client::{
+    accounts::decode,
+    error::{program_error_from_logs, shipment_error, ClientError, SHIPMENT_ERRORS},
+    events::{events_from_logs, DepositFinalized},
+    instructions::{self, DepositOptions},
+    pda,
+    rpc::ConfirmedTransaction,
+};
+use solana_sdk::{message::Message, pubkey::Pubkey};
+
+#[test]
+fn shipment_errors_cover_every_variant() {
+    let declared = include_str!("../../../programs/shipment-sc/src/error.rs")
+        .matches("#[msg(")
+        .count();
+    assert_eq!(SHIPMENT_ERRORS.len(), declared);
+    for (index, error) in SHIPMENT_ERRORS.iter().enumerate() {
+        assert_eq!(u32::from(*error), 6000 + index as u32);
+        assert_eq!(
+            shipment_error(u32::from(*error)).unwrap().name(),
+            error.name()
+        );
+    }
+    assert!(shipment_error(5999).is_none());
+    assert!(shipment_error(6000 + declared as u32).is_none());
+}
+
+#[test]
+fn program_error_from_anchor_logs() {
+    let logs = [
+        "Program 37oMYvqhv2jsMbQw8ZPKKfxmRvFwvRyi6joH5ueP8mAr invoke [1]",
+        "Program log: Instruction: DepositNft",
+        "Program log: AnchorError thrown in programs/shipment-sc/src/instructions/deposit_nft.rs:104. Error Code: CollectionNotAllowed. Error Number: 6028. Error Message: Collection is not allowed.",
+        "Program 37oMYvqhv2jsMbQw8ZPKKfxmRvFwvRyi6joH5ueP8mAr failed: custom program error: 0x178c",
+    ];
+    let error = program_error_from_logs(&logs).unwrap();
+    assert_eq!(error.code, 6028);
+    assert_eq!(error.name.as_deref(), Some("CollectionNotAllowed"));
+    assert_eq!(error.message.as_deref(), Some("Collection is not allowed"));
+    assert_eq!(
+        error.shipment_error().map(|error| error.name()),
+        Some(ShipmentError::CollectionNotAllowed.name())
+    );
+
+    // Framework errors decode without a shipment error
+    let logs = ["Program log: AnchorError caused by account: user_pool. Error Code: ConstraintSeeds. Error Number: 2006. Error Message: A seeds constraint was violated."];
+    let error = program_error_from_logs(&logs).unwrap();
+    assert_eq!(error.code, 2006);
+    assert_eq!(error.name.as_deref(), Some("ConstraintSeeds"));
+    assert!(error.shipment_error().is_none());
+}
+
+#[test]
+fn program_error_from_runtime_logs() {
+    let logs = vec![
+        "Program 37oMYvqhv2jsMbQw8ZPKKfxmRvFwvRyi6joH5ueP8mAr failed: custom program error: 0x1770"
+            .to_string(),
+    ];
+    let error = ClientError::Transaction {
+        err: "InstructionError".to_string(),
+        logs,
+    }
+    .program_error()
+    .unwrap();
+    assert_eq!(error.code, 6000);
+    assert_eq!(error.name, Some(ShipmentError::InvalidAdmin.name()));
+    assert_eq!(
+        error.to_string(),
+        "InvalidAdmin (6000): Admin address dismatch"
+    );
+
+    assert!(program_error_from_logs(&["Program log: Instruction: InitUser"]).is_none());
+}
+
+#[test]
+fn deposit_finalized_from_logs() {
+    let event = DepositFinalized {
+        mint: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        collection: Pubkey::new_unique(),
+        user: "alice".to_string(),
+        delivery_proof: [5; 32],
+    };
+    let mut data = DepositFinalized::DISCRIMINATOR.to_vec();
+    event.serialize(&mut data).unwrap();
+    let logs = [
+        "Program log: Instruction: FinalizeDeposit".to_string(),
+        format!("Program data: {}", STANDARD.encode([1; 40])),
+        format!("Program data: {}", STANDARD.encode(&data)),
+    ];
+
+    let events: Vec<DepositFinalized> = events_from_logs(&logs);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].mint, event.mint);
+    assert_eq!(events[0].user, "alice");
+    assert_eq!(events[0].delivery_proof, [5; 32]);
+}
+
+#[test]
+fn decode_checks_discriminator() {
+    let pool = UserPool {
+        address: Pubkey::new_unique(),
+        deposit_count: 3,
+        admin: false,
+        updater: true,
+        recent_deposits: [0; MAX_DAILY_DEPOSITS],
+        web2_user: [0; 32],
+    };
+    let mut data = vec![];
+    pool.try_serialize(&mut data).unwrap();
+    assert_eq!(&data[..8], UserPool::DISCRIMINATOR);
+
+    let key = pda::user_pool(&pool.address).0;
+    let decoded: UserPool = decode(&key, &data).unwrap();
+    assert_eq!(decoded.address, pool.address);
+    assert_eq!(decoded.deposit_count, 3);
+    assert!(decoded.updater);
+
+    let result = decode::<NftDeposit>(&key, &data);
+    assert!(matches!(result, Err(ClientError::Decode(account, _)) if account == key));
+}
+
+#[test]
+fn instructions_from_transaction_json() {
+    let payer = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+    let ix = instructions::deposit_nft(
+        &payer,
+        &mint,
+        &collection,
+        "uuid",
+        DepositOptions::default(),
+    );
+    let message = Message::new(std::slice::from_ref(&ix), Some(&payer));
+    let compiled = &message.instructions[0];
+    let transaction = serde_json::json!({
+        "slot": 1,
+        "transaction": {
+            "signatures": ["sig"],
+            "message": {
+                "accountKeys": message.account_keys.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+                "header": {
+                    "numRequiredSignatures": message.header.num_required_signatures,
+                    "numReadonlySignedAccounts": message.header.num_readonly_signed_accounts,
+                    "numReadonlyUnsignedAccounts": message.header.num_readonly_unsigned_accounts,
+                },
+                "instructions": [{
+                    "programIdIndex": compiled.program_id_index,
+                    "accounts": compiled.accounts,
+                    "data": bs58::encode(&compiled.data).into_string(),
+                }],
+            },
+        },
+        "meta": { "err": null, "logMessages": ["Program log: deposited"] },
+    });
+
+    let transaction = ConfirmedTransaction::from_json(transaction).unwrap();
+    assert_eq!(transaction.instructions, vec![ix]);
+    assert_eq!(transaction.logs, vec!["Program log: deposited"]);
+
+    let args: shipment_sc::instruction::DepositNft =
+        instructions::decode(&transaction.instructions[0]).unwrap();
+    assert_eq!(args.collection, collection);
+    assert_eq!(args.user, "uuid");
+    assert!(
+        instructions::decode::<shipment_sc::instruction::DepositNftFor>(
+            &transaction.instructions[0]
+        )
+        .is_none()
+    );
+}
+
+#[test]
+fn deposit_builder_derives_accounts() {
+    let user = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+
+    let ix =
+        instructions::deposit_nft(&user, &mint, &collection, "uuid", DepositOptions::default());
+    assert_eq!(ix.program_id, shipment_sc::ID);
+    assert_eq!(ix.accounts[0].pubkey, user);
+    assert!(ix.accounts[0].is_signer);
+    assert_eq!(ix.accounts[2].pubkey, pda::user_pool(&user).0);
+    assert_eq!(ix.accounts[3].pubkey, pda::collection_pool(&collection).0);
+    assert_eq!(ix.accounts[6].pubkey, pda::metadata(&mint));
+    assert_eq!(ix.accounts[7].pubkey, pda::nft_deposit(&mint).0);
+    assert_eq!(ix.accounts[8].pubkey, pda::web2_user_index("uuid", 0).0);
+    // Absent optional accounts are replaced by the program id
+    assert_eq!(ix.accounts[9].pubkey, shipment_sc::ID);
+    assert_eq!(ix.accounts[11].pubkey, pda::custody_token_account(&mint));
+    assert_eq!(ix.accounts[12].pubkey, shipment_sc::ID);
+
+    let data = shipment_sc::instruction::DepositNft {
+        collection,
+        user: "uuid".to_string(),
+        mint_proof: None,
+        depositor_proof: None,
+        index_page: 0,
+    }
+    .data();
+    assert_eq!(ix.data, data);
+
+    let ix = instructions::deposit_nft(
+        &user,
+        &mint,
+        &collection,
+        "uuid",
+        DepositOptions {
+            receipt: true,
+            index_page: 2,
+            ..DepositOptions::default()
+        },
+    );
+    assert_eq!(ix.accounts[8].pubkey, pda::web2_user_index("uuid", 2).0);
+    assert_eq!(ix.accounts[9].pubkey, pda::web2_user_index("uuid", 1).0);
+    assert_eq!(ix.accounts[12].pubkey, pda::receipt_mint(&mint).0);
+    assert_eq!(
+        ix.accounts[13].pubkey,
+        pda::receipt_token_account(&user, &mint)
+    );
+}
+
+#[test]
+fn delegated_deposit_builder_attributes_beneficiary() {
+    let depositor = Pubkey::new_unique();
+    let beneficiary = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+
+    let ix = instructions::deposit_nft_for(
+        &depositor,
+        &beneficiary,
+        &mint,
+        &collection,
+        "uuid",
+        false,
+        DepositOptions {
+            receipt: true,
+            ..DepositOptions::default()
+        },
+    );
+    assert!(ix.accounts[0].is_signer);
+    assert_eq!(ix.accounts[1].pubkey, beneficiary);
+    assert!(ix.accounts[1].is_signer);
+    // Absent delegate account is replaced by the program id
+    assert_eq!(ix.accounts[2].pubkey, shipment_sc::ID);
+    assert_eq!(ix.accounts[4].pubkey, pda::user_pool(&beneficiary).0);
+    assert_eq!(
+        ix.accounts[15].pubkey,
+        pda::receipt_token_account(&beneficiary, &mint)
+    );
+
+    let ix = instructions::deposit_nft_for(
+        &depositor,
+        &beneficiary,
+        &mint,
+        &collection,
+        "uuid",
+        true,
+        DepositOptions::default(),
+    );
+    assert!(!ix.accounts[1].is_signer);
+    assert_eq!(
+        ix.accounts[2].pubkey,
+        pda::deposit_delegate(&beneficiary, &depositor).0
+    );
+}
+
+#[test]
+fn registry_page_follows_collection_index() {
+    let admin = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+
+    let ix = instructions::register_collection(&admin, &collection, 31);
+    assert_eq!(ix.accounts[4].pubkey, pda::collection_registry(0).0);
+    let ix = instructions::register_collection(&admin, &collection, 32);
+    assert_eq!(ix.accounts[4].pubkey, pda::collection_registry(1).0);
+    let ix = instructions::close_collection(&admin, &collection, 40);
+    assert_eq!(ix.accounts[4].pubkey, pda::collection_registry(1).0);
+}
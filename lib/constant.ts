@@ -4,6 +4,17 @@ export const GLOBAL_AUTHORITY_SEED = "global-authority";
 export const USER_POOL_SEED = "user-pool";
 export const COLLECTION_POOL_SEED = "collection-pool";
 export const NFT_DEPOSIT_SEED = "nft-deposit";
+export const RECEIPT_SEED = "receipt";
+export const REDEMPTION_AUTHORITY_SEED = "redemption-authority";
+export const DISPUTE_SEED = "dispute";
+export const COLLECTION_REGISTRY_SEED = "collection-registry";
+export const MINT_ALLOWLIST_SEED = "mint-allowlist";
+export const WEB2_USER_INDEX_SEED = "web2-user-index";
+export const DEPOSIT_DELEGATE_SEED = "deposit-delegate";
+
+export const COLLECTION_REGISTRY_PAGE_SIZE = 32;
+export const WEB2_USER_INDEX_PAGE_SIZE = 16;
+export const DEPOSIT_QUOTA_WINDOW = 24 * 60 * 60; // 1 day
 
 export const PROGRAM_ID = new PublicKey(
   "37oMYvqhv2jsMbQw8ZPKKfxmRvFwvRyi6joH5ueP8mAr"
@@ -2,7 +2,9 @@ import * as anchor from '@coral-xyz/anchor';
 import {
   PublicKey,
   Connection,
+  Ed25519Program,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   SYSVAR_RENT_PUBKEY,
   Transaction,
 } from '@solana/web3.js';
@@ -10,26 +12,52 @@ import { Wallet } from '@coral-xyz/anchor';
 import { Metadata } from '@metaplex-foundation/mpl-token-metadata';
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getMint,
 } from '@solana/spl-token';
 
 import { METAPLEX, getAssociatedTokenAccount, getMetadata } from './util';
 import {
   COLLECTION_POOL_SEED,
+  COLLECTION_REGISTRY_PAGE_SIZE,
+  COLLECTION_REGISTRY_SEED,
+  DEPOSIT_DELEGATE_SEED,
+  DISPUTE_SEED,
   GLOBAL_AUTHORITY_SEED,
+  MINT_ALLOWLIST_SEED,
   NFT_DEPOSIT_SEED,
+  RECEIPT_SEED,
+  REDEMPTION_AUTHORITY_SEED,
   USER_POOL_SEED,
+  WEB2_USER_INDEX_PAGE_SIZE,
+  WEB2_USER_INDEX_SEED,
 } from './constant';
 import {
   CollectionPool,
+  CollectionRegistry,
+  DepositDelegate,
   DepositStatus,
+  Dispute,
+  DisputeOutcome,
   GlobalPool,
+  LEGACY_COLLECTION_POOL_SIZE,
+  LEGACY_GLOBAL_POOL_SIZE,
+  LEGACY_NFT_DEPOSIT_SIZE,
+  LEGACY_USER_POOL_SIZE,
+  LockReason,
+  MintAllowlist,
+  MintProof,
   NFT_DEPOSIT_SIZE,
   NftDeposit,
+  RentDestination,
   USER_POOL_SIZE,
   UserPool,
+  Web2UserIndex,
 } from './types';
 import { bs58 } from '@coral-xyz/anchor/dist/cjs/utils/bytes';
+import { createHash } from 'crypto';
 
 export const createInitializeIx = async (
   admin: PublicKey,
@@ -95,6 +123,180 @@ export const changeTreasuryIx = async (
   return ix;
 };
 
+/**
+ * Change withdrawal request grace period as admin
+ */
+export const changeGracePeriodIx = async (
+  admin: PublicKey,
+  gracePeriod: number,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+
+  const ix = await program.methods
+    .changeGracePeriod(new anchor.BN(gracePeriod))
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change delivery proof mode and courier oracle as admin
+ */
+export const changeDeliveryProofIx = async (
+  admin: PublicKey,
+  required: boolean,
+  courierOracle: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+
+  const ix = await program.methods
+    .changeDeliveryProof(required, courierOracle)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change key attesting web2 identity links as admin
+ * default public key stops checking deposit uuids
+ */
+export const changeIdentityAuthorityIx = async (
+  admin: PublicKey,
+  identityAuthority: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+
+  const ix = await program.methods
+    .changeIdentityAuthority(identityAuthority)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change whether deposit ownership transfers need admin co-sign as admin
+ */
+export const changeTransferPolicyIx = async (
+  admin: PublicKey,
+  cosignRequired: boolean,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+
+  const ix = await program.methods
+    .changeTransferPolicy(cosignRequired)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change who receives rent of finalized collection deposits as admin
+ */
+export const changeRentDestinationIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  rentDestination: RentDestination,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const ix = await program.methods
+    .changeRentDestination(collection, rentDestination)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change redeemed token of a collection as admin, null disables redemption
+ */
+export const changeRedemptionMintIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  redemptionMint: PublicKey | null,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const ix = await program.methods
+    .changeRedemptionMint(collection)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+      redemptionMint,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change display name and uri of a collection as admin
+ */
+export const changeCollectionMetadataIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  name: string,
+  uri: string,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const ix = await program.methods
+    .changeCollectionMetadata(collection, name, uri)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
 /**
  * Initialize UserPool PDA
  */
@@ -120,6 +322,90 @@ export const createInitUserIx = async (
   return ix;
 };
 
+/**
+ * Close UserPool PDA without deposits or roles as user or admin
+ * rent is returned to recipient
+ */
+export const createCloseUserIx = async (
+  authority: PublicKey,
+  user: PublicKey,
+  recipient: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const userPool = findUserPoolKey(user, program.programId);
+  console.log('userPool: ', userPool.toString());
+
+  const ix = await program.methods
+    .closeUser()
+    .accounts({
+      authority,
+      user,
+      globalPool,
+      adminPool: authority.equals(user)
+        ? null
+        : findUserPoolKey(authority, program.programId),
+      userPool,
+      recipient,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * User can allow a delegate to deposit nfts on its behalf
+ */
+export const createRegisterDepositDelegateIx = async (
+  beneficiary: PublicKey,
+  delegate: PublicKey,
+  program: anchor.Program
+) => {
+  const depositDelegate = findDepositDelegateKey(
+    beneficiary,
+    delegate,
+    program.programId
+  );
+  console.log('depositDelegate: ', depositDelegate.toString());
+
+  const ix = await program.methods
+    .registerDepositDelegate(delegate)
+    .accounts({
+      beneficiary,
+      depositDelegate,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * User can revoke a deposit delegate, rent is returned to the user
+ */
+export const createRevokeDepositDelegateIx = async (
+  beneficiary: PublicKey,
+  delegate: PublicKey,
+  program: anchor.Program
+) => {
+  const depositDelegate = findDepositDelegateKey(
+    beneficiary,
+    delegate,
+    program.programId
+  );
+  console.log('depositDelegate: ', depositDelegate.toString());
+
+  const ix = await program.methods
+    .revokeDepositDelegate(delegate)
+    .accounts({
+      beneficiary,
+      depositDelegate,
+    })
+    .instruction();
+
+  return ix;
+};
+
 /**
  * Change admin / updater role of user as admin
  */
@@ -168,6 +454,14 @@ export const createRegisterCollectionIx = async (
   const collectionPool = findCollectionPoolKey(collection, program.programId);
   console.log(`collectionPool: ${collectionPool.toString()}`);
 
+  // New collections are appended to the page of the next registry index
+  const { data } = await getGlobalState(program);
+  const collectionRegistry = findCollectionRegistryKey(
+    Math.floor(data.collectionCount.toNumber() / COLLECTION_REGISTRY_PAGE_SIZE),
+    program.programId
+  );
+  console.log(`collectionRegistry: ${collectionRegistry.toString()}`);
+
   const ix = await program.methods
     .registerCollection(collection)
     .accounts({
@@ -175,6 +469,7 @@ export const createRegisterCollectionIx = async (
       globalPool,
       adminPool,
       collectionPool,
+      collectionRegistry,
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,
     })
@@ -206,23 +501,284 @@ export const createRevokeCollectionIx = async (
       globalPool,
       adminPool,
       collectionPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Close collection without active deposits as admin, rent is returned to admin
+ */
+export const createCloseCollectionIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+
+  const adminPool = findUserPoolKey(admin, program.programId);
+  console.log(`adminPool: ${adminPool.toString()}`);
+
+  const { key: collectionPool, data } = await getCollectionPoolState(
+    collection,
+    program
+  );
+  console.log(`collectionPool: ${collectionPool.toString()}`);
+
+  const collectionRegistry = findCollectionRegistryKey(
+    Math.floor(data.registryIndex.toNumber() / COLLECTION_REGISTRY_PAGE_SIZE),
+    program.programId
+  );
+  console.log(`collectionRegistry: ${collectionRegistry.toString()}`);
+
+  const ix = await program.methods
+    .closeCollection(collection)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+      collectionRegistry,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Allow a single mint to be deposited under a collection as admin
+ */
+export const createRegisterMintIx = async (
+  admin: PublicKey,
+  mint: PublicKey,
+  collection: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const mintAllowlist = findMintAllowlistKey(mint, program.programId);
+  console.log(`mintAllowlist: ${mintAllowlist.toString()}`);
+
+  const ix = await program.methods
+    .registerMint(mint, collection)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+      mintAllowlist,
       systemProgram: SystemProgram.programId,
-      rent: SYSVAR_RENT_PUBKEY,
     })
     .instruction();
 
   return ix;
 };
 
+/**
+ * Revoke allowed mint as admin, rent is returned to admin
+ */
+export const createRevokeMintIx = async (
+  admin: PublicKey,
+  mint: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const mintAllowlist = findMintAllowlistKey(mint, program.programId);
+
+  const ix = await program.methods
+    .revokeMint(mint)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      mintAllowlist,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change merkle root of mints allowed under a collection as admin, zero root disables it
+ */
+export const changeMintMerkleRootIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  mintMerkleRoot: number[],
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const ix = await program.methods
+    .changeMintMerkleRoot(collection, mintMerkleRoot)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change merkle root of wallets approved to deposit as admin, zero root opens deposits
+ */
+export const changeDepositorMerkleRootIx = async (
+  admin: PublicKey,
+  depositorMerkleRoot: number[],
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+
+  const ix = await program.methods
+    .changeDepositorMerkleRoot(depositorMerkleRoot)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change merkle root of wallets approved to deposit under a collection as admin
+ * zero root falls back to the global root
+ */
+export const changeCollectionDepositorMerkleRootIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  depositorMerkleRoot: number[],
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const ix = await program.methods
+    .changeCollectionDepositorMerkleRoot(collection, depositorMerkleRoot)
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Change per-user deposit limits as admin, zero means unlimited
+ */
+export const changeDepositQuotaIx = async (
+  admin: PublicKey,
+  maxConcurrentDeposits: number,
+  maxDailyDeposits: number,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+
+  const ix = await program.methods
+    .changeDepositQuota({
+      maxConcurrentDeposits: new anchor.BN(maxConcurrentDeposits),
+      maxDailyDeposits: new anchor.BN(maxDailyDeposits),
+    })
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Override per-user deposit limits of a collection as admin
+ * zero limits fall back to the global quota
+ */
+export const changeCollectionDepositQuotaIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  maxConcurrentDeposits: number,
+  maxDailyDeposits: number,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const adminPool = findUserPoolKey(admin, program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+
+  const ix = await program.methods
+    .changeCollectionDepositQuota(collection, {
+      maxConcurrentDeposits: new anchor.BN(maxConcurrentDeposits),
+      maxDailyDeposits: new anchor.BN(maxDailyDeposits),
+    })
+    .accounts({
+      admin,
+      globalPool,
+      adminPool,
+      collectionPool,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Collection a mint is deposited under
+ * allowlisted mints are deposited under the collection they were allowed in
+ */
+export const findDepositCollection = async (
+  nftMint: PublicKey,
+  program: anchor.Program,
+  connection: Connection,
+  mintProof: MintProof | null = null
+) => {
+  const mintMetadata = await getMetadata(nftMint);
+  const mintAllowlistKey = findMintAllowlistKey(nftMint, program.programId);
+  const mintAllowlist = (await program.account.mintAllowlist.fetchNullable(
+    mintAllowlistKey
+  )) as unknown as MintAllowlist | null;
+
+  let collection: PublicKey;
+  if (mintAllowlist) {
+    collection = mintAllowlist.collection;
+  } else if (mintProof) {
+    collection = mintProof.collection;
+  } else {
+    const metadataAccount = await connection.getAccountInfo(mintMetadata);
+    const [metadata] = Metadata.fromAccountInfo(metadataAccount);
+    collection = metadata.collection?.key || metadata.data.creators[0].address;
+  }
+
+  return { collection, mintAllowlistKey, mintAllowlist };
+};
+
 /**
  * User deposit NFT
+ * depositorProof is required while a depositor merkle root is set
  */
 export const createDepositNftTx = async (
   wallet: Wallet,
   userId: string,
   nftMint: PublicKey,
   program: anchor.Program,
-  connection: Connection
+  connection: Connection,
+  withReceipt = false,
+  mintProof: MintProof | null = null,
+  depositorProof: number[][] | null = null
 ) => {
   const user = wallet.publicKey;
 
@@ -239,11 +795,117 @@ export const createDepositNftTx = async (
   const mintMetadata = await getMetadata(nftMint);
   console.log('mintMetadata: ', mintMetadata.toBase58());
 
-  const metadataAccount = await connection.getAccountInfo(mintMetadata);
+  const { collection, mintAllowlistKey, mintAllowlist } =
+    await findDepositCollection(nftMint, program, connection, mintProof);
+  console.log('collection: ', collection.toBase58());
+
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+  console.log('collectionPool: ', collectionPool.toBase58());
+
+  const depositState = findNftDepositKey(nftMint, program.programId);
+  console.log('depositState: ', depositState.toBase58());
+
+  const indexPage = await findFreeWeb2UserIndexPage(userId, program);
+  const web2UserIndex = findWeb2UserIndexKey(
+    userId,
+    indexPage,
+    program.programId
+  );
+  console.log('web2UserIndex: ', web2UserIndex.toBase58());
+  const previousWeb2UserIndex = findPreviousWeb2UserIndexKey(
+    userId,
+    indexPage,
+    program.programId
+  );
+
+  // User pool is created by the first deposit
+  const tx = new Transaction();
+
+  const ix = await program.methods
+    .depositNft(
+      collection,
+      userId,
+      mintAllowlist ? null : mintProof?.proof ?? null,
+      depositorProof,
+      new anchor.BN(indexPage)
+    )
+    .accounts({
+      user,
+      globalPool,
+      userPool,
+      collectionPool,
+      tokenMint: nftMint,
+      mintAllowlist: mintAllowlist ? mintAllowlistKey : null,
+      mintMetadata,
+      depositState,
+      web2UserIndex,
+      previousWeb2UserIndex,
+      tokenAccount,
+      destTokenAccount,
+      ...findReceiptAccounts(user, nftMint, withReceipt, program.programId),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: METAPLEX,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  tx.add(ix);
+
+  tx.feePayer = user;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  const txData = await wallet.signTransaction(tx);
+
+  console.log('signed user: ', user.toBase58());
+
+  return txData.serialize({ requireAllSignatures: false });
+};
+
+/**
+ * Depositor can deposit nft it holds on behalf of a beneficiary wallet
+ * without a registered deposit delegate, the beneficiary must co-sign the returned tx
+ */
+export const createDepositNftForTx = async (
+  wallet: Wallet,
+  beneficiary: PublicKey,
+  userId: string,
+  nftMint: PublicKey,
+  program: anchor.Program,
+  connection: Connection,
+  withReceipt = false,
+  mintProof: MintProof | null = null,
+  depositorProof: number[][] | null = null
+) => {
+  const depositor = wallet.publicKey;
+
+  const globalPool = findGlobalPoolKey(program.programId);
+  const userPool = findUserPoolKey(beneficiary, program.programId);
+  console.log('userPool: ', userPool.toBase58());
+
+  const depositDelegateKey = findDepositDelegateKey(
+    beneficiary,
+    depositor,
+    program.programId
+  );
+  const depositDelegate = await getDepositDelegateState(
+    beneficiary,
+    depositor,
+    program
+  );
+  console.log('delegated: ', !!depositDelegate);
+
+  const tokenAccount = await getAssociatedTokenAccount(depositor, nftMint);
+  console.log('tokenAccount: ', tokenAccount.toBase58());
+
+  const destTokenAccount = await getAssociatedTokenAccount(globalPool, nftMint);
+  console.log('destTokenAccount: ', destTokenAccount.toBase58());
 
-  const [metadata] = Metadata.fromAccountInfo(metadataAccount);
-  const collection =
-    metadata.collection?.key || metadata.data.creators[0].address;
+  const mintMetadata = await getMetadata(nftMint);
+  console.log('mintMetadata: ', mintMetadata.toBase58());
+
+  const { collection, mintAllowlistKey, mintAllowlist } =
+    await findDepositCollection(nftMint, program, connection, mintProof);
   console.log('collection: ', collection.toBase58());
 
   const collectionPool = findCollectionPoolKey(collection, program.programId);
@@ -252,27 +914,50 @@ export const createDepositNftTx = async (
   const depositState = findNftDepositKey(nftMint, program.programId);
   console.log('depositState: ', depositState.toBase58());
 
+  const indexPage = await findFreeWeb2UserIndexPage(userId, program);
+  const web2UserIndex = findWeb2UserIndexKey(
+    userId,
+    indexPage,
+    program.programId
+  );
+  console.log('web2UserIndex: ', web2UserIndex.toBase58());
+  const previousWeb2UserIndex = findPreviousWeb2UserIndexKey(
+    userId,
+    indexPage,
+    program.programId
+  );
+
   const tx = new Transaction();
 
-  const poolAccount = await connection.getAccountInfo(userPool);
-  if (poolAccount === null || poolAccount.data === null) {
-    console.log('init User Pool');
-    const ix_initUserPool = await createInitUserIx(user, user, program);
-    tx.add(ix_initUserPool);
-  }
-
   const ix = await program.methods
-    .depositNft(collection, userId)
+    .depositNftFor(
+      collection,
+      userId,
+      mintAllowlist ? null : mintProof?.proof ?? null,
+      depositorProof,
+      new anchor.BN(indexPage)
+    )
     .accounts({
-      user,
+      depositor,
+      beneficiary,
+      depositDelegate: depositDelegate ? depositDelegateKey : null,
       globalPool,
       userPool,
       collectionPool,
       tokenMint: nftMint,
+      mintAllowlist: mintAllowlist ? mintAllowlistKey : null,
       mintMetadata,
       depositState,
+      web2UserIndex,
+      previousWeb2UserIndex,
       tokenAccount,
       destTokenAccount,
+      ...findReceiptAccounts(
+        beneficiary,
+        nftMint,
+        withReceipt,
+        program.programId
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       tokenMetadataProgram: METAPLEX,
@@ -280,26 +965,34 @@ export const createDepositNftTx = async (
     })
     .instruction();
 
+  // Beneficiary consents by signature when it registered no delegate
+  if (!depositDelegate) {
+    ix.keys.find((key) => key.pubkey.equals(beneficiary)).isSigner = true;
+  }
+
   tx.add(ix);
 
-  tx.feePayer = user;
+  tx.feePayer = depositor;
   tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
 
   const txData = await wallet.signTransaction(tx);
 
-  console.log('signed user: ', user.toBase58());
+  console.log('signed depositor: ', depositor.toBase58());
 
   return txData.serialize({ requireAllSignatures: false });
 };
 
 /**
  * Update able to update the nft deposit status & locked
+ * lockedUntil is a unix timestamp, 0 keeps the lock until released
  */
 export const createUpdateDepositIx = async (
   updater: PublicKey,
   depositPda: PublicKey,
   status: DepositStatus | null,
   locked: boolean | null,
+  lockReason: LockReason | null,
+  lockedUntil: number | null,
   program: anchor.Program
 ) => {
   const globalPool = findGlobalPoolKey(program.programId);
@@ -312,7 +1005,12 @@ export const createUpdateDepositIx = async (
   console.log('depositData: ', depositData);
 
   const ix = await program.methods
-    .updateDeposit(status, locked)
+    .updateDeposit(
+      status,
+      locked,
+      lockReason,
+      lockedUntil === null ? null : new anchor.BN(lockedUntil)
+    )
     .accounts({
       updater,
       user: depositData.owner,
@@ -368,8 +1066,179 @@ export const createWithdrawOwnerIx = async (
       userPool,
       tokenMint: depositData.mint,
       depositState: depositPda,
+      web2UserIndex: findDepositWeb2UserIndexKey(depositData, program.programId),
+      collectionPool: findCollectionPoolKey(
+        depositData.collection,
+        program.programId
+      ),
+      destTokenAccount,
+      userTokenAccount,
+      ...findReceiptAccounts(
+        depositData.owner,
+        depositData.mint,
+        depositData.receipt,
+        program.programId
+      ),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      SystemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Transfer deposit claim to new wallet and web2 user as owner
+ * admin co-signer is required when transfer policy asks for it
+ */
+export const createTransferDepositOwnershipIx = async (
+  owner: PublicKey,
+  depositPda: PublicKey,
+  newOwner: PublicKey,
+  newUserId: string,
+  admin: PublicKey | null,
+  program: anchor.Program
+) => {
+  const newIndexPage = await findFreeWeb2UserIndexPage(newUserId, program);
+
+  const globalPool = findGlobalPoolKey(program.programId);
+
+  const depositData = await getNftDepositData(depositPda, program);
+  console.log('depositData: ', depositData);
+
+  const ownerPool = findUserPoolKey(owner, program.programId);
+  const newOwnerPool = findUserPoolKey(newOwner, program.programId);
+  console.log('new owner pool: ', newOwnerPool.toBase58());
+
+  const receipt = findReceiptAccounts(
+    owner,
+    depositData.mint,
+    depositData.receipt,
+    program.programId
+  );
+  const newReceipt = findReceiptAccounts(
+    newOwner,
+    depositData.mint,
+    depositData.receipt,
+    program.programId
+  );
+
+  const ix = await program.methods
+    .transferDepositOwnership(newUserId, new anchor.BN(newIndexPage))
+    .accounts({
+      owner,
+      newOwner,
+      admin,
+      globalPool,
+      adminPool: admin ? findUserPoolKey(admin, program.programId) : null,
+      ownerPool,
+      newOwnerPool,
+      tokenMint: depositData.mint,
+      depositState: depositPda,
+      web2UserIndex: findDepositWeb2UserIndexKey(depositData, program.programId),
+      newWeb2UserIndex: findWeb2UserIndexKey(
+        newUserId,
+        newIndexPage,
+        program.programId
+      ),
+      previousWeb2UserIndex: findPreviousWeb2UserIndexKey(
+        newUserId,
+        newIndexPage,
+        program.programId
+      ),
+      collectionPool: findCollectionPoolKey(
+        depositData.collection,
+        program.programId
+      ),
+      ...receipt,
+      newReceiptTokenAccount: newReceipt.receiptTokenAccount,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Request withdrawal of deposited Nft as owner
+ */
+export const createRequestWithdrawalIx = async (
+  owner: PublicKey,
+  depositPda: PublicKey,
+  program: anchor.Program
+) => {
+  const depositData = await getNftDepositData(depositPda, program);
+  console.log('depositData: ', depositData);
+
+  const ix = await program.methods
+    .requestWithdrawal()
+    .accounts({
+      owner,
+      tokenMint: depositData.mint,
+      depositState: depositPda,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Approve withdrawal request as updater, Nft is sent to owner
+ */
+export const createApproveWithdrawalIx = async (
+  updater: PublicKey,
+  depositPda: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  console.log('globalPool =', globalPool.toBase58());
+
+  const updaterPool = findUserPoolKey(updater, program.programId);
+  console.log('updater pool: ', updaterPool.toBase58());
+
+  const depositData = await getNftDepositData(depositPda, program);
+  console.log('depositData: ', depositData);
+
+  const userPool = findUserPoolKey(depositData.owner, program.programId);
+  console.log('user pool: ', userPool.toBase58());
+
+  const userTokenAccount = await getAssociatedTokenAccount(
+    depositData.owner,
+    depositData.mint
+  );
+  console.log('user token account: ', userTokenAccount);
+
+  const destTokenAccount = await getAssociatedTokenAccount(
+    globalPool,
+    depositData.mint
+  );
+  console.log('dest token account: ', destTokenAccount);
+
+  const ix = await program.methods
+    .approveWithdrawal()
+    .accounts({
+      updater,
+      user: depositData.owner,
+      globalPool,
+      updaterPool,
+      userPool,
+      tokenMint: depositData.mint,
+      depositState: depositPda,
+      web2UserIndex: findDepositWeb2UserIndexKey(depositData, program.programId),
+      collectionPool: findCollectionPoolKey(
+        depositData.collection,
+        program.programId
+      ),
       destTokenAccount,
       userTokenAccount,
+      ...findReceiptAccounts(
+        depositData.owner,
+        depositData.mint,
+        depositData.receipt,
+        program.programId
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       SystemProgram: SystemProgram.programId,
@@ -379,6 +1248,34 @@ export const createWithdrawOwnerIx = async (
   return ix;
 };
 
+/**
+ * Deny withdrawal request as updater
+ */
+export const createDenyWithdrawalIx = async (
+  updater: PublicKey,
+  depositPda: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const updaterPool = findUserPoolKey(updater, program.programId);
+
+  const depositData = await getNftDepositData(depositPda, program);
+  console.log('depositData: ', depositData);
+
+  const ix = await program.methods
+    .denyWithdrawal()
+    .accounts({
+      updater,
+      globalPool,
+      updaterPool,
+      tokenMint: depositData.mint,
+      depositState: depositPda,
+    })
+    .instruction();
+
+  return ix;
+};
+
 /**
  * Withdraw deposited Nft to treasury as admin
  */
@@ -422,9 +1319,20 @@ export const createWithdrawTreasuryIx = async (
       userPool,
       tokenMint: depositData.mint,
       depositState: depositPda,
+      web2UserIndex: findDepositWeb2UserIndexKey(depositData, program.programId),
+      collectionPool: findCollectionPoolKey(
+        depositData.collection,
+        program.programId
+      ),
       treasury: data.treasury,
       destTokenAccount,
       treasuryTokenAccount,
+      ...findReceiptAccounts(
+        depositData.owner,
+        depositData.mint,
+        depositData.receipt,
+        program.programId
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       SystemProgram: SystemProgram.programId,
@@ -440,6 +1348,7 @@ export const createWithdrawTreasuryIx = async (
 export const createFinalizeDepositIx = async (
   updater: PublicKey,
   depositPda: PublicKey,
+  proofHash: number[],
   program: anchor.Program
 ) => {
   const globalPool = findGlobalPoolKey(program.programId);
@@ -456,26 +1365,292 @@ export const createFinalizeDepositIx = async (
   const userPool = findUserPoolKey(depositData.owner, program.programId);
   console.log('user pool: ', userPool.toBase58());
 
-  const destTokenAccount = await getAssociatedTokenAccount(
-    globalPool,
-    depositData.mint
+  const destTokenAccount = await getAssociatedTokenAccount(
+    globalPool,
+    depositData.mint
+  );
+  console.log('dest token account: ', destTokenAccount);
+
+  const { key: collectionPool, data: collectionData } =
+    await getCollectionPoolState(depositData.collection, program);
+  const redemption = await findRedemptionAccounts(
+    depositData.owner,
+    depositData.collection,
+    collectionData.redemptionMint,
+    program
+  );
+  console.log('redemption: ', redemption);
+
+  const rentRecipient = findRentRecipient(
+    collectionData.rentDestination,
+    depositData.payer,
+    updater,
+    data.treasury
+  );
+  console.log('rent recipient: ', rentRecipient.toBase58());
+
+  const ix = await program.methods
+    .finalizeDeposit(proofHash)
+    .accounts({
+      updater,
+      user: depositData.owner,
+      globalPool,
+      updaterPool,
+      userPool,
+      tokenMint: depositData.mint,
+      depositState: depositPda,
+      web2UserIndex: findDepositWeb2UserIndexKey(depositData, program.programId),
+      collectionPool,
+      treasury: data.treasury,
+      rentRecipient,
+      destTokenAccount,
+      ...redemption,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      ...findReceiptAccounts(
+        depositData.owner,
+        depositData.mint,
+        depositData.receipt,
+        program.programId
+      ),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      SystemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Receiver of a finalized deposit rent, chosen by the collection rent destination
+ */
+export const findRentRecipient = (
+  rentDestination: RentDestination,
+  payer: PublicKey,
+  finalizer: PublicKey,
+  treasury: PublicKey
+) =>
+  'depositor' in rentDestination
+    ? payer
+    : 'updater' in rentDestination
+    ? finalizer
+    : treasury;
+
+/**
+ * Optional redemption accounts of a collection, null when redemption is disabled
+ * tokens are minted when the authority controls the mint, otherwise sent from its stock
+ */
+export const findRedemptionAccounts = async (
+  owner: PublicKey,
+  collection: PublicKey,
+  redemptionMint: PublicKey,
+  program: anchor.Program
+) => {
+  if (redemptionMint.equals(PublicKey.default)) {
+    return {
+      redemptionMint: null,
+      redemptionAuthority: null,
+      redemptionSource: null,
+      redemptionTokenAccount: null,
+      associatedTokenProgram: null,
+    };
+  }
+
+  const redemptionAuthority = findRedemptionAuthorityKey(
+    collection,
+    program.programId
+  );
+  const mint = await getMint(program.provider.connection, redemptionMint);
+  const redemptionSource = mint.mintAuthority?.equals(redemptionAuthority)
+    ? null
+    : await getAssociatedTokenAccount(redemptionAuthority, redemptionMint);
+
+  return {
+    redemptionMint,
+    redemptionAuthority,
+    redemptionSource,
+    redemptionTokenAccount: await getAssociatedTokenAccount(
+      owner,
+      redemptionMint
+    ),
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+  };
+};
+
+/**
+ * Message signed by the courier oracle to attest a delivery
+ */
+export const deliveryProofMessage = (mint: PublicKey, proofHash: number[]) =>
+  Buffer.concat([mint.toBuffer(), Buffer.from(proofHash)]);
+
+/**
+ * Message signed by the identity authority to link a wallet to a web2 user
+ */
+export const web2IdentityMessage = (wallet: PublicKey, userId: string) =>
+  Buffer.concat([wallet.toBuffer(), Buffer.from(userId)]);
+
+/**
+ * Link wallet to web2 user as user, with the identity authority signature
+ * of web2IdentityMessage returned by the backend
+ */
+export const createLinkWeb2IdentityIxs = async (
+  user: PublicKey,
+  userId: string,
+  identityAuthority: PublicKey,
+  signature: Uint8Array,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const userPool = findUserPoolKey(user, program.programId);
+
+  // Attestation is verified by the instruction right before the link
+  const verifyIx = Ed25519Program.createInstructionWithPublicKey({
+    publicKey: identityAuthority.toBytes(),
+    message: web2IdentityMessage(user, userId),
+    signature,
+  });
+
+  const ix = await program.methods
+    .linkWeb2Identity(userId)
+    .accounts({
+      user,
+      globalPool,
+      userPool,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return [verifyIx, ix];
+};
+
+/**
+ * Open dispute on deposited Nft as owner
+ */
+export const createOpenDisputeIx = async (
+  owner: PublicKey,
+  depositPda: PublicKey,
+  evidenceHash: number[],
+  program: anchor.Program
+) => {
+  const depositData = await getNftDepositData(depositPda, program);
+  console.log('depositData: ', depositData);
+
+  const dispute = findDisputeKey(depositData.mint, program.programId);
+  console.log('dispute: ', dispute.toBase58());
+
+  const ix = await program.methods
+    .openDispute(evidenceHash)
+    .accounts({
+      owner,
+      tokenMint: depositData.mint,
+      depositState: depositPda,
+      dispute,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Resolve dispute as admin, Nft is sent to owner, treasury or finalized
+ */
+export const createResolveDisputeIx = async (
+  admin: PublicKey,
+  depositPda: PublicKey,
+  outcome: DisputeOutcome,
+  resolutionHash: number[],
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  console.log('globalPool =', globalPool.toBase58());
+
+  const adminPool = findUserPoolKey(admin, program.programId);
+  console.log('admin pool: ', adminPool.toBase58());
+
+  const depositData = await getNftDepositData(depositPda, program);
+  console.log('depositData: ', depositData);
+
+  const userPool = findUserPoolKey(depositData.owner, program.programId);
+  console.log('user pool: ', userPool.toBase58());
+
+  const dispute = findDisputeKey(depositData.mint, program.programId);
+  console.log('dispute: ', dispute.toBase58());
+
+  const destTokenAccount = await getAssociatedTokenAccount(
+    globalPool,
+    depositData.mint
+  );
+  console.log('dest token account: ', destTokenAccount);
+
+  const { data } = await getGlobalState(program);
+
+  let destinationTokenAccount: PublicKey | null = null;
+  if ('returnToOwner' in outcome) {
+    destinationTokenAccount = await getAssociatedTokenAccount(
+      depositData.owner,
+      depositData.mint
+    );
+  } else if ('sendToTreasury' in outcome) {
+    destinationTokenAccount = await getAssociatedTokenAccount(
+      data.treasury,
+      depositData.mint
+    );
+  }
+  console.log('destination token account: ', destinationTokenAccount);
+
+  // Rent recipient and redemption only apply to the finalize outcome
+  const { data: collectionData } = await getCollectionPoolState(
+    depositData.collection,
+    program
   );
-  console.log('dest token account: ', destTokenAccount);
+  const finalize = 'finalize' in outcome;
+  const rentRecipient = finalize
+    ? findRentRecipient(
+        collectionData.rentDestination,
+        depositData.payer,
+        admin,
+        data.treasury
+      )
+    : null;
+  console.log('rent recipient: ', rentRecipient);
+  const redemption = await findRedemptionAccounts(
+    depositData.owner,
+    depositData.collection,
+    finalize ? collectionData.redemptionMint : PublicKey.default,
+    program
+  );
+  console.log('redemption: ', redemption);
 
   const ix = await program.methods
-    .finalizeDeposit()
+    .resolveDispute(outcome, resolutionHash)
     .accounts({
-      updater,
+      admin,
       user: depositData.owner,
       globalPool,
-      updaterPool,
+      adminPool,
       userPool,
       tokenMint: depositData.mint,
       depositState: depositPda,
+      web2UserIndex: findDepositWeb2UserIndexKey(depositData, program.programId),
+      collectionPool: findCollectionPoolKey(
+        depositData.collection,
+        program.programId
+      ),
+      dispute,
       treasury: data.treasury,
+      rentRecipient,
       destTokenAccount,
+      destinationTokenAccount,
+      ...redemption,
+      ...findReceiptAccounts(
+        depositData.owner,
+        depositData.mint,
+        depositData.receipt,
+        program.programId
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
-      SystemProgram: SystemProgram.programId,
+      systemProgram: SystemProgram.programId,
     })
     .instruction();
 
@@ -582,6 +1757,111 @@ export const getCollectionPoolState = async (
   };
 };
 
+/**
+ * Fetch collection registry page PDA data
+ */
+
+export const findCollectionRegistryKey = (page: number, programId: PublicKey) => {
+  const [collectionRegistry] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(COLLECTION_REGISTRY_SEED),
+      new anchor.BN(page).toArrayLike(Buffer, 'le', 8),
+    ],
+    programId
+  );
+  return collectionRegistry;
+};
+export const getCollectionRegistryState = async (
+  page: number,
+  program: anchor.Program
+) => {
+  const collectionRegistry = findCollectionRegistryKey(page, program.programId);
+  const collectionRegistryData = await program.account.collectionRegistry.fetch(
+    collectionRegistry
+  );
+
+  return {
+    key: collectionRegistry,
+    data: collectionRegistryData as unknown as CollectionRegistry,
+  };
+};
+
+export const findAllCollections = async (program: anchor.Program) => {
+  const { data } = await getGlobalState(program);
+  const count = data.collectionCount.toNumber();
+
+  const collections: PublicKey[] = [];
+  for (let page = 0; page * COLLECTION_REGISTRY_PAGE_SIZE < count; page++) {
+    const { data: registry } = await getCollectionRegistryState(page, program);
+    // Closed collections leave an empty slot
+    collections.push(
+      ...registry.collections.filter(
+        (collection) => !collection.equals(PublicKey.default)
+      )
+    );
+  }
+
+  return Promise.all(
+    collections.map(async (collection) => {
+      const { key, data } = await getCollectionPoolState(collection, program);
+      return {
+        key: key.toBase58(),
+        collection: collection.toBase58(),
+        allowed: data.allowed,
+        depositCount: data.depositCount.toNumber(),
+        name: data.name,
+        uri: data.uri,
+      };
+    })
+  );
+};
+
+/**
+ * Fetch mint allowlist PDA data
+ */
+
+export const findMintAllowlistKey = (mint: PublicKey, programId: PublicKey) => {
+  const [mintAllowlist] = PublicKey.findProgramAddressSync(
+    [Buffer.from(MINT_ALLOWLIST_SEED), mint.toBytes()],
+    programId
+  );
+  return mintAllowlist;
+};
+
+export const findDepositDelegateKey = (
+  beneficiary: PublicKey,
+  delegate: PublicKey,
+  programId: PublicKey
+) => {
+  const [depositDelegate] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(DEPOSIT_DELEGATE_SEED),
+      beneficiary.toBytes(),
+      delegate.toBytes(),
+    ],
+    programId
+  );
+  return depositDelegate;
+};
+
+/**
+ * Fetch deposit delegate PDA data, null if the delegate is not registered
+ */
+export const getDepositDelegateState = async (
+  beneficiary: PublicKey,
+  delegate: PublicKey,
+  program: anchor.Program
+): Promise<DepositDelegate | null> => {
+  const depositDelegate = findDepositDelegateKey(
+    beneficiary,
+    delegate,
+    program.programId
+  );
+  return (await program.account.depositDelegate.fetchNullable(
+    depositDelegate
+  )) as unknown as DepositDelegate | null;
+};
+
 /**
  * Fetch nft deposit state PDA data
  */
@@ -606,6 +1886,161 @@ export const getNftDepositState = async (
   };
 };
 
+/**
+ * Fetch web2 user index page PDA data, pages are seeded by the sha256 of the uuid
+ */
+
+export const findWeb2UserIndexKey = (
+  userId: string,
+  page: number,
+  programId: PublicKey
+) => {
+  const [web2UserIndex] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(WEB2_USER_INDEX_SEED),
+      createHash('sha256').update(userId).digest(),
+      new anchor.BN(page).toArrayLike(Buffer, 'le', 8),
+    ],
+    programId
+  );
+  return web2UserIndex;
+};
+/**
+ * Page before `page`, required by the program to open a new page, null for page 0
+ */
+export const findPreviousWeb2UserIndexKey = (
+  userId: string,
+  page: number,
+  programId: PublicKey
+) => (page > 0 ? findWeb2UserIndexKey(userId, page - 1, programId) : null);
+export const findDepositWeb2UserIndexKey = (
+  deposit: NftDeposit,
+  programId: PublicKey
+) =>
+  findWeb2UserIndexKey(deposit.user, deposit.indexPage.toNumber(), programId);
+export const getWeb2UserIndexState = async (
+  userId: string,
+  page: number,
+  program: anchor.Program
+) => {
+  const web2UserIndex = findWeb2UserIndexKey(userId, page, program.programId);
+  const web2UserIndexData = await program.account.web2UserIndex.fetchNullable(
+    web2UserIndex
+  );
+
+  return {
+    key: web2UserIndex,
+    data: web2UserIndexData as unknown as Web2UserIndex | null,
+  };
+};
+
+/**
+ * First web2 user index page with a free slot for a new deposit
+ */
+export const findFreeWeb2UserIndexPage = async (
+  userId: string,
+  program: anchor.Program
+) => {
+  let page = 0;
+  for (;;) {
+    const { data } = await getWeb2UserIndexState(userId, page, program);
+    if (!data || data.count.toNumber() < WEB2_USER_INDEX_PAGE_SIZE) {
+      return page;
+    }
+    page++;
+  }
+};
+
+/**
+ * Mints of the active deposits of a web2 user, read page by page
+ */
+export const findWeb2UserDeposits = async (
+  userId: string,
+  program: anchor.Program
+) => {
+  const mints: PublicKey[] = [];
+  for (let page = 0; ; page++) {
+    const { data } = await getWeb2UserIndexState(userId, page, program);
+    if (!data) {
+      return mints;
+    }
+    // Removed deposits leave an empty slot
+    mints.push(...data.mints.filter((mint) => !mint.equals(PublicKey.default)));
+  }
+};
+
+export const findRedemptionAuthorityKey = (
+  collection: PublicKey,
+  programId: PublicKey
+) => {
+  const [redemptionAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from(REDEMPTION_AUTHORITY_SEED), collection.toBytes()],
+    programId
+  );
+  return redemptionAuthority;
+};
+
+export const findReceiptMintKey = (mint: PublicKey, programId: PublicKey) => {
+  const [receiptMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from(RECEIPT_SEED), mint.toBytes()],
+    programId
+  );
+  return receiptMint;
+};
+
+/**
+ * Optional receipt accounts of a deposit, null when no receipt is used
+ */
+export const findReceiptAccounts = (
+  owner: PublicKey,
+  mint: PublicKey,
+  enabled: boolean,
+  programId: PublicKey
+) => {
+  if (!enabled) {
+    return {
+      receiptMint: null,
+      receiptTokenAccount: null,
+      token2022Program: null,
+    };
+  }
+
+  const receiptMint = findReceiptMintKey(mint, programId);
+  const receiptTokenAccount = getAssociatedTokenAddressSync(
+    receiptMint,
+    owner,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  return {
+    receiptMint,
+    receiptTokenAccount,
+    token2022Program: TOKEN_2022_PROGRAM_ID,
+  };
+};
+
+export const findDisputeKey = (mint: PublicKey, programId: PublicKey) => {
+  const [dispute] = PublicKey.findProgramAddressSync(
+    [Buffer.from(DISPUTE_SEED), mint.toBytes()],
+    programId
+  );
+  return dispute;
+};
+
+export const getDisputeState = async (
+  mint: PublicKey,
+  program: anchor.Program
+) => {
+  const dispute = findDisputeKey(mint, program.programId);
+  const disputeData = await program.account.dispute.fetch(dispute);
+
+  return {
+    key: dispute,
+    data: disputeData as unknown as Dispute,
+  };
+};
+
 export const getNftDepositData = async (
   pda: PublicKey,
   program: anchor.Program
@@ -670,3 +2105,190 @@ export const findAllDeposits = async (
     };
   });
 };
+
+/**
+ * Migrate the legacy global pool as super admin
+ * runs before every other migration
+ */
+export const createMigrateGlobalPoolIx = async (
+  admin: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+
+  const ix = await program.methods
+    .migrateGlobalPool()
+    .accounts({
+      admin,
+      globalPool,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Migrate a legacy user pool as super admin
+ */
+export const createMigrateUserPoolIx = async (
+  admin: PublicKey,
+  user: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const userPool = findUserPoolKey(user, program.programId);
+  console.log('userPool: ', userPool.toString());
+
+  const ix = await program.methods
+    .migrateUserPool()
+    .accounts({
+      admin,
+      user,
+      globalPool,
+      userPool,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Migrate a legacy collection pool as super admin
+ * the collection is appended to the page of the next registry index
+ */
+export const createMigrateCollectionPoolIx = async (
+  admin: PublicKey,
+  collection: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+  console.log(`collectionPool: ${collectionPool.toString()}`);
+
+  const { data } = await getGlobalState(program);
+  const collectionRegistry = findCollectionRegistryKey(
+    Math.floor(data.collectionCount.toNumber() / COLLECTION_REGISTRY_PAGE_SIZE),
+    program.programId
+  );
+
+  const ix = await program.methods
+    .migrateCollectionPool(collection)
+    .accounts({
+      admin,
+      globalPool,
+      collectionPool,
+      collectionRegistry,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Migrate a live legacy deposit in place as super admin
+ * it is listed in the first web2 user index page with a free slot
+ */
+export const createMigrateNftDepositIx = async (
+  admin: PublicKey,
+  mint: PublicKey,
+  collection: PublicKey,
+  userId: string,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const collectionPool = findCollectionPoolKey(collection, program.programId);
+  const mintMetadata = await getMetadata(mint);
+  const depositState = findNftDepositKey(mint, program.programId);
+  console.log('depositState: ', depositState.toString());
+
+  const indexPage = await findFreeWeb2UserIndexPage(userId, program);
+  const web2UserIndex = findWeb2UserIndexKey(
+    userId,
+    indexPage,
+    program.programId
+  );
+  const previousWeb2UserIndex = findPreviousWeb2UserIndexKey(
+    userId,
+    indexPage,
+    program.programId
+  );
+
+  const ix = await program.methods
+    .migrateNftDeposit(collection, userId, new anchor.BN(indexPage))
+    .accounts({
+      admin,
+      globalPool,
+      collectionPool,
+      tokenMint: mint,
+      mintMetadata,
+      depositState,
+      web2UserIndex,
+      previousWeb2UserIndex,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Close a withdrawn legacy deposit as super admin, rent is returned to its owner
+ */
+export const createCloseLegacyDepositIx = async (
+  admin: PublicKey,
+  owner: PublicKey,
+  mint: PublicKey,
+  program: anchor.Program
+) => {
+  const globalPool = findGlobalPoolKey(program.programId);
+  const depositState = findNftDepositKey(mint, program.programId);
+  console.log('depositState: ', depositState.toString());
+
+  const ix = await program.methods
+    .closeLegacyDeposit()
+    .accounts({
+      admin,
+      owner,
+      globalPool,
+      tokenMint: mint,
+      depositState,
+    })
+    .instruction();
+
+  return ix;
+};
+
+/**
+ * Find accounts still in the legacy layout, by their legacy size
+ * addresses are read from the raw data as the legacy layouts have no IDL
+ */
+export const findLegacyAccounts = async (program: anchor.Program) => {
+  const find = (dataSize: number) =>
+    program.provider.connection.getProgramAccounts(program.programId, {
+      filters: [{ dataSize }],
+    });
+  const address = (data: Buffer, offset: number) =>
+    new PublicKey(data.subarray(offset, offset + 32));
+
+  const globalPools = await find(LEGACY_GLOBAL_POOL_SIZE);
+  const userPools = await find(LEGACY_USER_POOL_SIZE);
+  const collectionPools = await find(LEGACY_COLLECTION_POOL_SIZE);
+  const deposits = await find(LEGACY_NFT_DEPOSIT_SIZE);
+
+  return {
+    globalPool: globalPools.length !== 0,
+    users: userPools.map(({ account }) => address(account.data, 8)),
+    collections: collectionPools.map(({ account }) => address(account.data, 8)),
+    deposits: deposits.map(({ account }) => ({
+      owner: address(account.data, 8),
+      mint: address(account.data, 40),
+      status: account.data[80],
+      user: account.data
+        .subarray(86, 86 + account.data.readUInt32LE(82))
+        .toString(),
+    })),
+  };
+};
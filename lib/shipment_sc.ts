@@ -84,82 +84,89 @@ export type ShipmentSc = {
       ]
     },
     {
-      "name": "initUser",
+      "name": "changeGracePeriod",
       "accounts": [
         {
-          "name": "payer",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "user",
-          "isMut": false,
+          "name": "globalPool",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userPool",
-          "isMut": true,
+          "name": "adminPool",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "gracePeriod",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "changeDeliveryProof",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "globalPool",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "required",
+          "type": "bool"
+        },
+        {
+          "name": "courierOracle",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "name": "changeRole",
+      "name": "changeTransferPolicy",
       "accounts": [
         {
           "name": "admin",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "user",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "globalPool",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "adminPool",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "userPool",
-          "isMut": true,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "admin",
-          "type": {
-            "option": "bool"
-          }
-        },
-        {
-          "name": "updater",
-          "type": {
-            "option": "bool"
-          }
+          "name": "cosignRequired",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "registerCollection",
+      "name": "changeRedemptionMint",
       "accounts": [
         {
           "name": "admin",
@@ -182,14 +189,10 @@ export type ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
+          "name": "redemptionMint",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         }
       ],
       "args": [
@@ -200,7 +203,7 @@ export type ShipmentSc = {
       ]
     },
     {
-      "name": "revokeCollection",
+      "name": "changeRentDestination",
       "accounts": [
         {
           "name": "admin",
@@ -221,116 +224,133 @@ export type ShipmentSc = {
           "name": "collectionPool",
           "isMut": true,
           "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "collection",
           "type": "publicKey"
+        },
+        {
+          "name": "rentDestination",
+          "type": {
+            "defined": "RentDestination"
+          }
         }
       ]
     },
     {
-      "name": "depositNft",
-      "docs": [
-        "* User can deposit NFT"
-      ],
+      "name": "changeCollectionMetadata",
       "accounts": [
         {
-          "name": "user",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
           "name": "globalPool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userPool",
-          "isMut": true,
+          "name": "adminPool",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "collectionPool",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
         },
         {
-          "name": "tokenMint",
-          "isMut": false,
-          "isSigner": false
+          "name": "name",
+          "type": "string"
         },
         {
-          "name": "mintMetadata",
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "initUser",
+      "accounts": [
+        {
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "depositState",
-          "isMut": true,
+          "name": "user",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAccount",
+          "name": "userPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destTokenAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeUser",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "user",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenMetadataProgram",
+          "name": "globalPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "adminPool",
           "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
+          "isSigner": false,
+          "isOptional": true
+        },
         {
-          "name": "collection",
-          "type": "publicKey"
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "user",
-          "type": "string"
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "updateDeposit",
-      "docs": [
-        "* Admin & updater can update nft deposit status"
-      ],
+      "name": "changeRole",
       "accounts": [
         {
-          "name": "updater",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
@@ -345,30 +365,25 @@ export type ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "updaterPool",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMint",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "depositState",
+          "name": "userPool",
           "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "status",
+          "name": "admin",
           "type": {
-            "option": "u8"
+            "option": "bool"
           }
         },
         {
-          "name": "locked",
+          "name": "updater",
           "type": {
             "option": "bool"
           }
@@ -376,93 +391,93 @@ export type ShipmentSc = {
       ]
     },
     {
-      "name": "withdrawOwner",
-      "docs": [
-        "* Admin / Owner can withdraw nft to owner"
-      ],
+      "name": "registerCollection",
       "accounts": [
         {
-          "name": "payer",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "user",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "globalPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payerPool",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userPool",
+          "name": "collectionPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
-          "isMut": false,
+          "name": "collectionRegistry",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "depositState",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "destTokenAccount",
-          "isMut": true,
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "userTokenAccount",
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "revokeCollection",
+      "accounts": [
+        {
+          "name": "admin",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "globalPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "collectionPool",
+          "isMut": true,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "name": "withdrawTreasury",
-      "docs": [
-        "* Admin can withdraw nft to treasury"
-      ],
+      "name": "closeCollection",
       "accounts": [
         {
           "name": "admin",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "user",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "globalPool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -471,106 +486,324 @@ export type ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "userPool",
+          "name": "collectionPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
+          "name": "collectionRegistry",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "registerMint",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "depositState",
-          "isMut": true,
+          "name": "adminPool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "treasury",
+          "name": "collectionPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "destTokenAccount",
+          "name": "mintAllowlist",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "treasuryTokenAccount",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "mint",
+          "type": "publicKey"
+        },
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "revokeMint",
+      "accounts": [
+        {
+          "name": "admin",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "mintAllowlist",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "mint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "changeMintMerkleRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "mintMerkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
     },
     {
-      "name": "finalizeDeposit",
-      "docs": [
-        "* Admin & updater can finalize deposit"
+      "name": "changeDepositorMerkleRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
       ],
+      "args": [
+        {
+          "name": "depositorMerkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeCollectionDepositorMerkleRoot",
       "accounts": [
         {
-          "name": "updater",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "user",
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "depositorMerkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeDepositQuota",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "globalPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "updaterPool",
+          "name": "adminPool",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "userPool",
+          "name": "depositQuota",
+          "type": {
+            "defined": "DepositQuota"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeCollectionDepositQuota",
+      "accounts": [
+        {
+          "name": "admin",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
           "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
         },
         {
-          "name": "depositState",
+          "name": "depositQuota",
+          "type": {
+            "defined": "DepositQuota"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeIdentityAuthority",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "treasury",
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "identityAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "linkWeb2Identity",
+      "docs": [
+        "* User can link its wallet to a web2 user attested by the identity authority\n     * the ed25519 verification of the attestation must precede this instruction\n     * the user pool is created with no roles if the wallet has none yet"
+      ],
+      "accounts": [
+        {
+          "name": "user",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "destTokenAccount",
+          "name": "userPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
         },
@@ -580,174 +813,26 @@ export type ShipmentSc = {
           "isSigner": false
         }
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "globalPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "superAdmin",
-            "type": "publicKey"
-          },
-          {
-            "name": "treasury",
-            "type": "publicKey"
-          },
-          {
-            "name": "totalDepositCount",
-            "type": "u64"
-          }
-        ]
-      }
+      "args": [
+        {
+          "name": "uuid",
+          "type": "string"
+        }
+      ]
     },
     {
-      "name": "collectionPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "address",
-            "type": "publicKey"
-          },
-          {
-            "name": "allowed",
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "userPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "address",
-            "type": "publicKey"
-          },
-          {
-            "name": "depositCount",
-            "type": "u64"
-          },
-          {
-            "name": "admin",
-            "type": "bool"
-          },
-          {
-            "name": "updater",
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "nftDeposit",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "owner",
-            "type": "publicKey"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "created",
-            "type": "i64"
-          },
-          {
-            "name": "status",
-            "type": "u8"
-          },
-          {
-            "name": "locked",
-            "type": "bool"
-          },
-          {
-            "name": "user",
-            "type": "string"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "InvalidAdmin",
-      "msg": "Admin address dismatch"
-    },
-    {
-      "code": 6001,
-      "name": "InvalidUpdater",
-      "msg": "Updater address dismatch"
-    },
-    {
-      "code": 6002,
-      "name": "InvalidMetadata",
-      "msg": "Metadata address is invalid"
-    },
-    {
-      "code": 6003,
-      "name": "InvalidCollection",
-      "msg": "Collection is invalid"
-    },
-    {
-      "code": 6004,
-      "name": "MetadataCreatorParseError",
-      "msg": "Can not parse creators in metadata"
-    },
-    {
-      "code": 6005,
-      "name": "AlreadyDeposited",
-      "msg": "Nft is already deposited"
-    },
-    {
-      "code": 6006,
-      "name": "NotDeposited",
-      "msg": "Nft is not deposited already"
-    },
-    {
-      "code": 6007,
-      "name": "InvalidOwner",
-      "msg": "NFT Owner key mismatch"
-    },
-    {
-      "code": 6008,
-      "name": "InvalidNFTAddress",
-      "msg": "No Matching NFT to withdraw"
-    },
-    {
-      "code": 6009,
-      "name": "DisabledWithdrawal",
-      "msg": "Withdrawal is disabled"
-    }
-  ]
-};
-
-export const IDL: ShipmentSc = {
-  "version": "0.1.0",
-  "name": "shipment_sc",
-  "instructions": [
-    {
-      "name": "initialize",
+      "name": "registerDepositDelegate",
       "docs": [
-        "* Initialize global pool\n     * super admin sets to the caller of this instruction"
+        "* User can allow a delegate to deposit NFTs on its behalf"
       ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "beneficiary",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "globalPool",
+          "name": "depositDelegate",
           "isMut": true,
           "isSigner": false
         },
@@ -755,46 +840,47 @@ export const IDL: ShipmentSc = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "treasury",
+          "name": "delegate",
           "type": "publicKey"
         }
       ]
     },
     {
-      "name": "transferSuperAdmin",
+      "name": "revokeDepositDelegate",
+      "docs": [
+        "* User can revoke a deposit delegate, rent is returned to the user"
+      ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "beneficiary",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "globalPool",
+          "name": "depositDelegate",
           "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "newAdmin",
+          "name": "delegate",
           "type": "publicKey"
         }
       ]
     },
     {
-      "name": "changeTreasury",
+      "name": "depositNft",
+      "docs": [
+        "* User can deposit NFT of an allowed collection\n     * or an allowlisted mint, by allowlist account or merkle proof\n     * the user pool is created with no roles on the first deposit\n     * when a depositor root is set, the user must prove it is an approved wallet\n     * when an identity authority is set, `user` must be the web2 user linked to the wallet\n     * the deposit is listed in page `index_page` of its web2 user index"
+      ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "user",
           "isMut": true,
           "isSigner": true
         },
@@ -804,109 +890,167 @@ export const IDL: ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "adminPool",
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "newTreasury",
-          "type": "publicKey"
-        }
-      ]
-    },
-    {
-      "name": "initUser",
-      "accounts": [
+          "name": "mintAllowlist",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
-          "name": "payer",
+          "name": "mintMetadata",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "user",
-          "isMut": false,
+          "name": "depositState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userPool",
+          "name": "web2UserIndex",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "previousWeb2UserIndex",
           "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "destTokenAccount",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "changeRole",
-      "accounts": [
+        },
         {
-          "name": "admin",
+          "name": "receiptMint",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "user",
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "globalPool",
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "adminPool",
+          "name": "tokenMetadataProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userPool",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "admin",
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "user",
+          "type": "string"
+        },
+        {
+          "name": "mintProof",
           "type": {
-            "option": "bool"
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
           }
         },
         {
-          "name": "updater",
+          "name": "depositorProof",
           "type": {
-            "option": "bool"
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
           }
+        },
+        {
+          "name": "indexPage",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "registerCollection",
+      "name": "depositNftFor",
+      "docs": [
+        "* Depositor can deposit NFT it holds on behalf of a beneficiary wallet\n     * the beneficiary must co-sign or have registered the depositor as its delegate\n     * the deposit is owned by the beneficiary, rent is paid by the depositor\n     * when a depositor root is set, the depositor must prove it is an approved wallet\n     * when an identity authority is set, `user` must be the web2 user linked to the beneficiary"
+      ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "depositor",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "globalPool",
+          "name": "beneficiary",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "adminPool",
+          "name": "depositDelegate",
           "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -915,120 +1059,70 @@ export const IDL: ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "mintAllowlist",
           "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "collection",
-          "type": "publicKey"
-        }
-      ]
-    },
-    {
-      "name": "revokeCollection",
-      "accounts": [
-        {
-          "name": "admin",
-          "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "globalPool",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "adminPool",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collectionPool",
+          "name": "mintMetadata",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "collection",
-          "type": "publicKey"
-        }
-      ]
-    },
-    {
-      "name": "depositNft",
-      "docs": [
-        "* User can deposit NFT"
-      ],
-      "accounts": [
-        {
-          "name": "user",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "globalPool",
+          "name": "depositState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userPool",
+          "name": "web2UserIndex",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collectionPool",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMint",
+          "name": "previousWeb2UserIndex",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "mintMetadata",
+          "name": "tokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "depositState",
+          "name": "destTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenAccount",
+          "name": "receiptMint",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "destTokenAccount",
+          "name": "receiptTokenAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "associatedTokenProgram",
           "isMut": false,
@@ -1053,6 +1147,36 @@ export const IDL: ShipmentSc = {
         {
           "name": "user",
           "type": "string"
+        },
+        {
+          "name": "mintProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "depositorProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "indexPage",
+          "type": "u64"
         }
       ]
     },
@@ -1105,6 +1229,18 @@ export const IDL: ShipmentSc = {
           "type": {
             "option": "bool"
           }
+        },
+        {
+          "name": "lockReason",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "lockedUntil",
+          "type": {
+            "option": "i64"
+          }
         }
       ]
     },
@@ -1149,6 +1285,16 @@ export const IDL: ShipmentSc = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "destTokenAccount",
           "isMut": true,
@@ -1159,11 +1305,29 @@ export const IDL: ShipmentSc = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "associatedTokenProgram",
           "isMut": false,
@@ -1178,13 +1342,157 @@ export const IDL: ShipmentSc = {
       "args": []
     },
     {
-      "name": "withdrawTreasury",
+      "name": "transferDepositOwnership",
       "docs": [
-        "* Admin can withdraw nft to treasury"
+        "* Owner can transfer deposit claim to a new wallet and web2 user\n     * admin must co-sign when transfer policy requires it\n     * the deposit moves to page `new_index_page` of the new web2 user index"
       ],
       "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "newOwner",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "admin",
+          "isMut": false,
+          "isSigner": true,
+          "isOptional": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "ownerPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newOwnerPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newWeb2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousWeb2UserIndex",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "collectionPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "newReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newUser",
+          "type": "string"
+        },
+        {
+          "name": "newIndexPage",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "requestWithdrawal",
+      "docs": [
+        "* Owner can request withdrawal of a locked nft"
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "approveWithdrawal",
+      "docs": [
+        "* Admin & updater can approve withdrawal request, nft is sent to owner"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
           "isMut": true,
           "isSigner": true
         },
@@ -1199,7 +1507,7 @@ export const IDL: ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "adminPool",
+          "name": "updaterPool",
           "isMut": false,
           "isSigner": false
         },
@@ -1219,8 +1527,13 @@ export const IDL: ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "treasury",
-          "isMut": false,
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1229,15 +1542,33 @@ export const IDL: ShipmentSc = {
           "isSigner": false
         },
         {
-          "name": "treasuryTokenAccount",
+          "name": "userTokenAccount",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "associatedTokenProgram",
           "isMut": false,
@@ -1252,73 +1583,4096 @@ export const IDL: ShipmentSc = {
       "args": []
     },
     {
-      "name": "finalizeDeposit",
-      "docs": [
-        "* Admin & updater can finalize deposit"
-      ],
-      "accounts": [
-        {
-          "name": "updater",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "user",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "globalPool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "updaterPool",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "userPool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMint",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "depositState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "treasury",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "destTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
+      "name": "denyWithdrawal",
+      "docs": [
+        "* Admin & updater can deny withdrawal request"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updaterPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawTreasury",
+      "docs": [
+        "* Admin can withdraw nft to treasury"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openDispute",
+      "docs": [
+        "* Owner can dispute a deposit, freezing withdrawal & finalize until resolved"
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "dispute",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "evidenceHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "resolveDispute",
+      "docs": [
+        "* Admin can resolve a dispute by returning, sending to treasury or finalizing the nft\n     * the dispute account is closed to its owner, the resolution is emitted as an event"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "dispute",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rentRecipient",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionAuthority",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionSource",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "outcome",
+          "type": {
+            "defined": "DisputeOutcome"
+          }
+        },
+        {
+          "name": "resolutionHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "finalizeDeposit",
+      "docs": [
+        "* Admin & updater can finalize deposit\n     * with delivery proof mode, owner must co-sign or courier oracle attestation must precede"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updaterPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rentRecipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "redemptionMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionAuthority",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionSource",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "proofHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "migrateGlobalPool",
+      "docs": [
+        "* Super admin can migrate the global pool left in the legacy layout by an upgrade\n     * it runs before the other migrations, live deposits stay counted"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateCollectionPool",
+      "docs": [
+        "* Super admin can migrate a collection pool left in the legacy layout\n     * the collection is appended to the registry"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionRegistry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "migrateUserPool",
+      "docs": [
+        "* Super admin can migrate a user pool left in the legacy layout, roles are kept"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateNftDeposit",
+      "docs": [
+        "* Super admin can migrate a live legacy deposit in place, its collection is checked\n     * against the nft metadata and it is listed in the web2 user index"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousWeb2UserIndex",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "user",
+          "type": "string"
+        },
+        {
+          "name": "indexPage",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "closeLegacyDeposit",
+      "docs": [
+        "* Super admin can close a withdrawn legacy deposit, rent is returned to its owner"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "globalPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "superAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalDepositCount",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalGracePeriod",
+            "type": "i64"
+          },
+          {
+            "name": "deliveryProofRequired",
+            "type": "bool"
+          },
+          {
+            "name": "courierOracle",
+            "type": "publicKey"
+          },
+          {
+            "name": "transferCosignRequired",
+            "type": "bool"
+          },
+          {
+            "name": "collectionCount",
+            "type": "u64"
+          },
+          {
+            "name": "depositorMerkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "depositQuota",
+            "type": {
+              "defined": "DepositQuota"
+            }
+          },
+          {
+            "name": "identityAuthority",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "collectionPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowed",
+            "type": "bool"
+          },
+          {
+            "name": "redemptionMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rentDestination",
+            "type": {
+              "defined": "RentDestination"
+            }
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "registryIndex",
+            "type": "u64"
+          },
+          {
+            "name": "mintMerkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "depositorMerkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "depositQuota",
+            "type": {
+              "defined": "DepositQuota"
+            }
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "collectionRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "page",
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          },
+          {
+            "name": "collections",
+            "type": {
+              "array": [
+                "publicKey",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "web2UserIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "page",
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          },
+          {
+            "name": "mints",
+            "type": {
+              "array": [
+                "publicKey",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintAllowlist",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositDelegate",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "beneficiary",
+            "type": "publicKey"
+          },
+          {
+            "name": "delegate",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "admin",
+            "type": "bool"
+          },
+          {
+            "name": "updater",
+            "type": "bool"
+          },
+          {
+            "name": "recentDeposits",
+            "type": {
+              "array": [
+                "i64",
+                16
+              ]
+            }
+          },
+          {
+            "name": "web2User",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "nftDeposit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "created",
+            "type": "i64"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "locked",
+            "type": "bool"
+          },
+          {
+            "name": "lockReason",
+            "type": "u8"
+          },
+          {
+            "name": "lockedUntil",
+            "type": "i64"
+          },
+          {
+            "name": "withdrawalRequestedAt",
+            "type": "i64"
+          },
+          {
+            "name": "disputed",
+            "type": "bool"
+          },
+          {
+            "name": "receipt",
+            "type": "bool"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "indexPage",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "dispute",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "opened",
+            "type": "i64"
+          },
+          {
+            "name": "evidenceHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "LegacyGlobalPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "superAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalDepositCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyCollectionPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowed",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyUserPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "admin",
+            "type": "bool"
+          },
+          {
+            "name": "updater",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyNftDeposit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "created",
+            "type": "i64"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "locked",
+            "type": "bool"
+          },
+          {
+            "name": "user",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DepositQuota",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxConcurrentDeposits",
+            "type": "u64"
+          },
+          {
+            "name": "maxDailyDeposits",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RentDestination",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Treasury"
+          },
+          {
+            "name": "Depositor"
+          },
+          {
+            "name": "Updater"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DisputeOutcome",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "ReturnToOwner"
+          },
+          {
+            "name": "SendToTreasury"
+          },
+          {
+            "name": "Finalize"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "DepositFinalized",
+      "fields": [
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "deliveryProof",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DisputeResolved",
+      "fields": [
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "opened",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "resolved",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "evidenceHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "resolutionHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "outcome",
+          "type": {
+            "defined": "DisputeOutcome"
+          },
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InvalidAdmin",
+      "msg": "Admin address dismatch"
+    },
+    {
+      "code": 6001,
+      "name": "InvalidUpdater",
+      "msg": "Updater address dismatch"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidMetadata",
+      "msg": "Metadata address is invalid"
+    },
+    {
+      "code": 6003,
+      "name": "InvalidCollection",
+      "msg": "Collection is invalid"
+    },
+    {
+      "code": 6004,
+      "name": "MetadataCreatorParseError",
+      "msg": "Can not parse creators in metadata"
+    },
+    {
+      "code": 6005,
+      "name": "AlreadyDeposited",
+      "msg": "Nft is already deposited"
+    },
+    {
+      "code": 6006,
+      "name": "NotDeposited",
+      "msg": "Nft is not deposited already"
+    },
+    {
+      "code": 6007,
+      "name": "InvalidOwner",
+      "msg": "NFT Owner key mismatch"
+    },
+    {
+      "code": 6008,
+      "name": "InvalidNFTAddress",
+      "msg": "No Matching NFT to withdraw"
+    },
+    {
+      "code": 6009,
+      "name": "DisabledWithdrawal",
+      "msg": "Withdrawal is disabled"
+    },
+    {
+      "code": 6010,
+      "name": "MathOverflow",
+      "msg": "Math operation overflow"
+    },
+    {
+      "code": 6011,
+      "name": "WithdrawalAlreadyRequested",
+      "msg": "Withdrawal is already requested"
+    },
+    {
+      "code": 6012,
+      "name": "WithdrawalNotRequested",
+      "msg": "Withdrawal is not requested"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidGracePeriod",
+      "msg": "Grace period is invalid"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidLockExpiry",
+      "msg": "Lock expiry is invalid"
+    },
+    {
+      "code": 6015,
+      "name": "DepositDisputed",
+      "msg": "Deposit is under dispute"
+    },
+    {
+      "code": 6016,
+      "name": "NotDisputed",
+      "msg": "Deposit is not disputed"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidDestination",
+      "msg": "Destination token account is invalid"
+    },
+    {
+      "code": 6018,
+      "name": "DeliveryProofRequired",
+      "msg": "Delivery proof signature is required"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidDeliveryProof",
+      "msg": "Delivery proof attestation is invalid"
+    },
+    {
+      "code": 6020,
+      "name": "ReceiptRequired",
+      "msg": "Receipt accounts are required"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidReceipt",
+      "msg": "Receipt token account is invalid"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidNewOwner",
+      "msg": "New owner is invalid"
+    },
+    {
+      "code": 6023,
+      "name": "RedemptionRequired",
+      "msg": "Redemption accounts are required"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidRedemption",
+      "msg": "Redemption account is invalid"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidRentRecipient",
+      "msg": "Rent recipient is invalid"
+    },
+    {
+      "code": 6026,
+      "name": "CollectionMetadataTooLong",
+      "msg": "Collection name or uri is too long"
+    },
+    {
+      "code": 6027,
+      "name": "CollectionHasDeposits",
+      "msg": "Collection still has active deposits"
+    },
+    {
+      "code": 6028,
+      "name": "CollectionNotAllowed",
+      "msg": "Collection is not allowed"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidMerkleProof",
+      "msg": "Merkle proof is invalid"
+    },
+    {
+      "code": 6030,
+      "name": "DepositorNotAllowed",
+      "msg": "Depositor is not approved"
+    },
+    {
+      "code": 6031,
+      "name": "QuotaExceeded",
+      "msg": "Deposit quota exceeded"
+    },
+    {
+      "code": 6032,
+      "name": "Web2UserIndexFull",
+      "msg": "Web2 user index page is full"
+    },
+    {
+      "code": 6033,
+      "name": "IdentityAttestationRequired",
+      "msg": "Web2 identity attestation is required"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidIdentityAttestation",
+      "msg": "Web2 identity attestation is invalid"
+    },
+    {
+      "code": 6035,
+      "name": "Web2UserNotLinked",
+      "msg": "Web2 user is not linked to the wallet"
+    },
+    {
+      "code": 6036,
+      "name": "UserHasDeposits",
+      "msg": "User still has deposits in custody or in the current quota window"
+    },
+    {
+      "code": 6037,
+      "name": "UserHasRoles",
+      "msg": "User still holds a role"
+    },
+    {
+      "code": 6038,
+      "name": "DepositConsentRequired",
+      "msg": "Beneficiary must sign or register the depositor as its delegate"
+    },
+    {
+      "code": 6039,
+      "name": "Web2UserTooLong",
+      "msg": "Web2 user uuid is too long"
+    },
+    {
+      "code": 6040,
+      "name": "Web2UserIndexPageSkipped",
+      "msg": "Web2 user index page can only be opened once the previous page is full"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidDepositQuota",
+      "msg": "Daily deposit quota exceeds the deposits tracked per user"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidDepositStatus",
+      "msg": "Deposit status can not be cleared while the nft is in custody"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidLegacyAccount",
+      "msg": "Account is in neither the legacy nor the current layout"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidWeb2User",
+      "msg": "Web2 user does not match the deposit"
+    }
+  ]
+};
+
+export const IDL: ShipmentSc = {
+  "version": "0.1.0",
+  "name": "shipment_sc",
+  "instructions": [
+    {
+      "name": "initialize",
+      "docs": [
+        "* Initialize global pool\n     * super admin sets to the caller of this instruction"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "treasury",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "transferSuperAdmin",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "changeTreasury",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newTreasury",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "changeGracePeriod",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "gracePeriod",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "changeDeliveryProof",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "required",
+          "type": "bool"
+        },
+        {
+          "name": "courierOracle",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "changeTransferPolicy",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "cosignRequired",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "changeRedemptionMint",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "redemptionMint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "changeRentDestination",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "rentDestination",
+          "type": {
+            "defined": "RentDestination"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeCollectionMetadata",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "initUser",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeUser",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "changeRole",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "admin",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "updater",
+          "type": {
+            "option": "bool"
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerCollection",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionRegistry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "revokeCollection",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "closeCollection",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionRegistry",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "registerMint",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintAllowlist",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "mint",
+          "type": "publicKey"
+        },
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "revokeMint",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintAllowlist",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "mint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "changeMintMerkleRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "mintMerkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeDepositorMerkleRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositorMerkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeCollectionDepositorMerkleRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "depositorMerkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeDepositQuota",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositQuota",
+          "type": {
+            "defined": "DepositQuota"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeCollectionDepositQuota",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "depositQuota",
+          "type": {
+            "defined": "DepositQuota"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeIdentityAuthority",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "identityAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "linkWeb2Identity",
+      "docs": [
+        "* User can link its wallet to a web2 user attested by the identity authority\n     * the ed25519 verification of the attestation must precede this instruction\n     * the user pool is created with no roles if the wallet has none yet"
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "uuid",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "registerDepositDelegate",
+      "docs": [
+        "* User can allow a delegate to deposit NFTs on its behalf"
+      ],
+      "accounts": [
+        {
+          "name": "beneficiary",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositDelegate",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "delegate",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "revokeDepositDelegate",
+      "docs": [
+        "* User can revoke a deposit delegate, rent is returned to the user"
+      ],
+      "accounts": [
+        {
+          "name": "beneficiary",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositDelegate",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "delegate",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "depositNft",
+      "docs": [
+        "* User can deposit NFT of an allowed collection\n     * or an allowlisted mint, by allowlist account or merkle proof\n     * the user pool is created with no roles on the first deposit\n     * when a depositor root is set, the user must prove it is an approved wallet\n     * when an identity authority is set, `user` must be the web2 user linked to the wallet\n     * the deposit is listed in page `index_page` of its web2 user index"
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintAllowlist",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "mintMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousWeb2UserIndex",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "user",
+          "type": "string"
+        },
+        {
+          "name": "mintProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "depositorProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "indexPage",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositNftFor",
+      "docs": [
+        "* Depositor can deposit NFT it holds on behalf of a beneficiary wallet\n     * the beneficiary must co-sign or have registered the depositor as its delegate\n     * the deposit is owned by the beneficiary, rent is paid by the depositor\n     * when a depositor root is set, the depositor must prove it is an approved wallet\n     * when an identity authority is set, `user` must be the web2 user linked to the beneficiary"
+      ],
+      "accounts": [
+        {
+          "name": "depositor",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "beneficiary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositDelegate",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintAllowlist",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "mintMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousWeb2UserIndex",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "user",
+          "type": "string"
+        },
+        {
+          "name": "mintProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "depositorProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "indexPage",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateDeposit",
+      "docs": [
+        "* Admin & updater can update nft deposit status"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updaterPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "status",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "locked",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "lockReason",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "lockedUntil",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdrawOwner",
+      "docs": [
+        "* Admin / Owner can withdraw nft to owner"
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "transferDepositOwnership",
+      "docs": [
+        "* Owner can transfer deposit claim to a new wallet and web2 user\n     * admin must co-sign when transfer policy requires it\n     * the deposit moves to page `new_index_page` of the new web2 user index"
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "newOwner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true,
+          "isOptional": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "ownerPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newOwnerPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newWeb2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousWeb2UserIndex",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "collectionPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "newReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newUser",
+          "type": "string"
+        },
+        {
+          "name": "newIndexPage",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "requestWithdrawal",
+      "docs": [
+        "* Owner can request withdrawal of a locked nft"
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "approveWithdrawal",
+      "docs": [
+        "* Admin & updater can approve withdrawal request, nft is sent to owner"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updaterPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "denyWithdrawal",
+      "docs": [
+        "* Admin & updater can deny withdrawal request"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updaterPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawTreasury",
+      "docs": [
+        "* Admin can withdraw nft to treasury"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openDispute",
+      "docs": [
+        "* Owner can dispute a deposit, freezing withdrawal & finalize until resolved"
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "dispute",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "evidenceHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "resolveDispute",
+      "docs": [
+        "* Admin can resolve a dispute by returning, sending to treasury or finalizing the nft\n     * the dispute account is closed to its owner, the resolution is emitted as an event"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "dispute",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rentRecipient",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionAuthority",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionSource",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "outcome",
+          "type": {
+            "defined": "DisputeOutcome"
+          }
+        },
+        {
+          "name": "resolutionHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "finalizeDeposit",
+      "docs": [
+        "* Admin & updater can finalize deposit\n     * with delivery proof mode, owner must co-sign or courier oracle attestation must precede"
+      ],
+      "accounts": [
+        {
+          "name": "updater",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updaterPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rentRecipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "redemptionMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionAuthority",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionSource",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "redemptionTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "receiptMint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "receiptTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "token2022Program",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "proofHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "migrateGlobalPool",
+      "docs": [
+        "* Super admin can migrate the global pool left in the legacy layout by an upgrade\n     * it runs before the other migrations, live deposits stay counted"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateCollectionPool",
+      "docs": [
+        "* Super admin can migrate a collection pool left in the legacy layout\n     * the collection is appended to the registry"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionRegistry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "migrateUserPool",
+      "docs": [
+        "* Super admin can migrate a user pool left in the legacy layout, roles are kept"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateNftDeposit",
+      "docs": [
+        "* Super admin can migrate a live legacy deposit in place, its collection is checked\n     * against the nft metadata and it is listed in the web2 user index"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "web2UserIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousWeb2UserIndex",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collection",
+          "type": "publicKey"
+        },
+        {
+          "name": "user",
+          "type": "string"
+        },
+        {
+          "name": "indexPage",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "closeLegacyDeposit",
+      "docs": [
+        "* Super admin can close a withdrawn legacy deposit, rent is returned to its owner"
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "globalPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "superAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalDepositCount",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalGracePeriod",
+            "type": "i64"
+          },
+          {
+            "name": "deliveryProofRequired",
+            "type": "bool"
+          },
+          {
+            "name": "courierOracle",
+            "type": "publicKey"
+          },
+          {
+            "name": "transferCosignRequired",
+            "type": "bool"
+          },
+          {
+            "name": "collectionCount",
+            "type": "u64"
+          },
+          {
+            "name": "depositorMerkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "depositQuota",
+            "type": {
+              "defined": "DepositQuota"
+            }
+          },
+          {
+            "name": "identityAuthority",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "collectionPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowed",
+            "type": "bool"
+          },
+          {
+            "name": "redemptionMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rentDestination",
+            "type": {
+              "defined": "RentDestination"
+            }
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "registryIndex",
+            "type": "u64"
+          },
+          {
+            "name": "mintMerkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "depositorMerkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "depositQuota",
+            "type": {
+              "defined": "DepositQuota"
+            }
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "collectionRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "page",
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          },
+          {
+            "name": "collections",
+            "type": {
+              "array": [
+                "publicKey",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "web2UserIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "page",
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          },
+          {
+            "name": "mints",
+            "type": {
+              "array": [
+                "publicKey",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintAllowlist",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositDelegate",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "beneficiary",
+            "type": "publicKey"
+          },
+          {
+            "name": "delegate",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "admin",
+            "type": "bool"
+          },
+          {
+            "name": "updater",
+            "type": "bool"
+          },
+          {
+            "name": "recentDeposits",
+            "type": {
+              "array": [
+                "i64",
+                16
+              ]
+            }
+          },
+          {
+            "name": "web2User",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "nftDeposit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "created",
+            "type": "i64"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "locked",
+            "type": "bool"
+          },
+          {
+            "name": "lockReason",
+            "type": "u8"
+          },
+          {
+            "name": "lockedUntil",
+            "type": "i64"
+          },
+          {
+            "name": "withdrawalRequestedAt",
+            "type": "i64"
+          },
+          {
+            "name": "disputed",
+            "type": "bool"
+          },
+          {
+            "name": "receipt",
+            "type": "bool"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "indexPage",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "dispute",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "opened",
+            "type": "i64"
+          },
+          {
+            "name": "evidenceHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
     }
   ],
-  "accounts": [
+  "types": [
     {
-      "name": "globalPool",
+      "name": "LegacyGlobalPool",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1338,7 +5692,7 @@ export const IDL: ShipmentSc = {
       }
     },
     {
-      "name": "collectionPool",
+      "name": "LegacyCollectionPool",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1354,7 +5708,7 @@ export const IDL: ShipmentSc = {
       }
     },
     {
-      "name": "userPool",
+      "name": "LegacyUserPool",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1378,7 +5732,7 @@ export const IDL: ShipmentSc = {
       }
     },
     {
-      "name": "nftDeposit",
+      "name": "LegacyNftDeposit",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1408,6 +5762,145 @@ export const IDL: ShipmentSc = {
           }
         ]
       }
+    },
+    {
+      "name": "DepositQuota",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxConcurrentDeposits",
+            "type": "u64"
+          },
+          {
+            "name": "maxDailyDeposits",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RentDestination",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Treasury"
+          },
+          {
+            "name": "Depositor"
+          },
+          {
+            "name": "Updater"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DisputeOutcome",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "ReturnToOwner"
+          },
+          {
+            "name": "SendToTreasury"
+          },
+          {
+            "name": "Finalize"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "DepositFinalized",
+      "fields": [
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "deliveryProof",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DisputeResolved",
+      "fields": [
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "opened",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "resolved",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "evidenceHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "resolutionHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "outcome",
+          "type": {
+            "defined": "DisputeOutcome"
+          },
+          "index": false
+        }
+      ]
     }
   ],
   "errors": [
@@ -1460,6 +5953,181 @@ export const IDL: ShipmentSc = {
       "code": 6009,
       "name": "DisabledWithdrawal",
       "msg": "Withdrawal is disabled"
+    },
+    {
+      "code": 6010,
+      "name": "MathOverflow",
+      "msg": "Math operation overflow"
+    },
+    {
+      "code": 6011,
+      "name": "WithdrawalAlreadyRequested",
+      "msg": "Withdrawal is already requested"
+    },
+    {
+      "code": 6012,
+      "name": "WithdrawalNotRequested",
+      "msg": "Withdrawal is not requested"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidGracePeriod",
+      "msg": "Grace period is invalid"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidLockExpiry",
+      "msg": "Lock expiry is invalid"
+    },
+    {
+      "code": 6015,
+      "name": "DepositDisputed",
+      "msg": "Deposit is under dispute"
+    },
+    {
+      "code": 6016,
+      "name": "NotDisputed",
+      "msg": "Deposit is not disputed"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidDestination",
+      "msg": "Destination token account is invalid"
+    },
+    {
+      "code": 6018,
+      "name": "DeliveryProofRequired",
+      "msg": "Delivery proof signature is required"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidDeliveryProof",
+      "msg": "Delivery proof attestation is invalid"
+    },
+    {
+      "code": 6020,
+      "name": "ReceiptRequired",
+      "msg": "Receipt accounts are required"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidReceipt",
+      "msg": "Receipt token account is invalid"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidNewOwner",
+      "msg": "New owner is invalid"
+    },
+    {
+      "code": 6023,
+      "name": "RedemptionRequired",
+      "msg": "Redemption accounts are required"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidRedemption",
+      "msg": "Redemption account is invalid"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidRentRecipient",
+      "msg": "Rent recipient is invalid"
+    },
+    {
+      "code": 6026,
+      "name": "CollectionMetadataTooLong",
+      "msg": "Collection name or uri is too long"
+    },
+    {
+      "code": 6027,
+      "name": "CollectionHasDeposits",
+      "msg": "Collection still has active deposits"
+    },
+    {
+      "code": 6028,
+      "name": "CollectionNotAllowed",
+      "msg": "Collection is not allowed"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidMerkleProof",
+      "msg": "Merkle proof is invalid"
+    },
+    {
+      "code": 6030,
+      "name": "DepositorNotAllowed",
+      "msg": "Depositor is not approved"
+    },
+    {
+      "code": 6031,
+      "name": "QuotaExceeded",
+      "msg": "Deposit quota exceeded"
+    },
+    {
+      "code": 6032,
+      "name": "Web2UserIndexFull",
+      "msg": "Web2 user index page is full"
+    },
+    {
+      "code": 6033,
+      "name": "IdentityAttestationRequired",
+      "msg": "Web2 identity attestation is required"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidIdentityAttestation",
+      "msg": "Web2 identity attestation is invalid"
+    },
+    {
+      "code": 6035,
+      "name": "Web2UserNotLinked",
+      "msg": "Web2 user is not linked to the wallet"
+    },
+    {
+      "code": 6036,
+      "name": "UserHasDeposits",
+      "msg": "User still has deposits in custody or in the current quota window"
+    },
+    {
+      "code": 6037,
+      "name": "UserHasRoles",
+      "msg": "User still holds a role"
+    },
+    {
+      "code": 6038,
+      "name": "DepositConsentRequired",
+      "msg": "Beneficiary must sign or register the depositor as its delegate"
+    },
+    {
+      "code": 6039,
+      "name": "Web2UserTooLong",
+      "msg": "Web2 user uuid is too long"
+    },
+    {
+      "code": 6040,
+      "name": "Web2UserIndexPageSkipped",
+      "msg": "Web2 user index page can only be opened once the previous page is full"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidDepositQuota",
+      "msg": "Daily deposit quota exceeds the deposits tracked per user"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidDepositStatus",
+      "msg": "Deposit status can not be cleared while the nft is in custody"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidLegacyAccount",
+      "msg": "Account is in neither the legacy nor the current layout"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidWeb2User",
+      "msg": "Web2 user does not match the deposit"
     }
   ]
 };
@@ -5,6 +5,36 @@ export interface GlobalPool {
   superAdmin: PublicKey;
   treasury: PublicKey;
   totalDepositCount: anchor.BN;
+  withdrawalGracePeriod: anchor.BN;
+  deliveryProofRequired: boolean;
+  courierOracle: PublicKey;
+  transferCosignRequired: boolean;
+  collectionCount: anchor.BN;
+  depositorMerkleRoot: number[];
+  depositQuota: DepositQuota;
+  identityAuthority: PublicKey;
+}
+
+// Per-user deposit limits, zero means unlimited
+export interface DepositQuota {
+  maxConcurrentDeposits: anchor.BN;
+  maxDailyDeposits: anchor.BN;
+}
+
+export interface MintAllowlist {
+  mint: PublicKey;
+  collection: PublicKey;
+}
+
+export interface DepositDelegate {
+  beneficiary: PublicKey; // wallet the delegated deposits are attributed to
+  delegate: PublicKey; // wallet allowed to deposit on behalf of the beneficiary
+}
+
+// Merkle proof of a mint allowed under a collection without an allowlist account
+export interface MintProof {
+  collection: PublicKey;
+  proof: number[][];
 }
 
 export interface UserPool {
@@ -12,19 +42,55 @@ export interface UserPool {
   depositCount: anchor.BN;
   admin: boolean;
   updater: boolean;
+  recentDeposits: anchor.BN[];
+  web2User: number[];
 }
-export const USER_POOL_SIZE = 56;
+export const USER_POOL_SIZE = 216;
 export interface CollectionPool {
   address: PublicKey;
   allowed: boolean;
+  redemptionMint: PublicKey;
+  rentDestination: RentDestination;
+  depositCount: anchor.BN;
+  registryIndex: anchor.BN;
+  mintMerkleRoot: number[];
+  depositorMerkleRoot: number[];
+  depositQuota: DepositQuota;
+  name: string;
+  uri: string;
+}
+
+export interface CollectionRegistry {
+  page: anchor.BN;
+  count: anchor.BN;
+  collections: PublicKey[];
+}
+
+export interface Web2UserIndex {
+  page: anchor.BN;
+  count: anchor.BN;
+  mints: PublicKey[];
 }
 
+export type RentDestination =
+  | { treasury: {} }
+  | { depositor: {} }
+  | { updater: {} };
+
 export interface NftDeposit {
   owner: PublicKey;
   mint: PublicKey;
   created: anchor.BN;
   status: DepositStatus;
   locked: boolean;
+  lockReason: LockReason;
+  lockedUntil: anchor.BN;
+  withdrawalRequestedAt: anchor.BN;
+  disputed: boolean;
+  receipt: boolean;
+  collection: PublicKey;
+  payer: PublicKey;
+  indexPage: anchor.BN;
   user: string;
 }
 
@@ -34,4 +100,29 @@ export enum DepositStatus {
   SHIPPED,
   DELIVERED,
 }
-export const NFT_DEPOSIT_SIZE = 112;
+export enum LockReason {
+  UNSPECIFIED = 0,
+  PAYMENT_PENDING,
+  INSPECTION,
+  IN_TRANSIT,
+  COMPLIANCE_HOLD,
+}
+export const NFT_DEPOSIT_SIZE = 236;
+
+export interface Dispute {
+  mint: PublicKey;
+  owner: PublicKey;
+  opened: anchor.BN;
+  evidenceHash: number[];
+}
+
+export type DisputeOutcome =
+  | { returnToOwner: {} }
+  | { sendToTreasury: {} }
+  | { finalize: {} };
+
+// Sizes of accounts still in the legacy layout, moved by the migrate instructions
+export const LEGACY_GLOBAL_POOL_SIZE = 80;
+export const LEGACY_COLLECTION_POOL_SIZE = 41;
+export const LEGACY_USER_POOL_SIZE = 56;
+export const LEGACY_NFT_DEPOSIT_SIZE = 112;
@@ -2,3 +2,20 @@ pub const GLOBAL_AUTHORITY_SEED: &str = "global-authority";
 pub const USER_POOL_SEED: &str = "user-pool";
 pub const COLLECTION_POOL_SEED: &str = "collection-pool";
 pub const NFT_DEPOSIT_SEED: &str = "nft-deposit";
+pub const RECEIPT_SEED: &str = "receipt";
+pub const REDEMPTION_AUTHORITY_SEED: &str = "redemption-authority";
+pub const DISPUTE_SEED: &str = "dispute";
+pub const COLLECTION_REGISTRY_SEED: &str = "collection-registry";
+pub const MINT_ALLOWLIST_SEED: &str = "mint-allowlist";
+pub const WEB2_USER_INDEX_SEED: &str = "web2-user-index";
+pub const DEPOSIT_DELEGATE_SEED: &str = "deposit-delegate";
+
+pub const DEFAULT_WITHDRAWAL_GRACE_PERIOD: i64 = 3 * 24 * 60 * 60; // 3 days
+pub const DEPOSIT_QUOTA_WINDOW: i64 = 24 * 60 * 60; // 1 day
+pub const MAX_DAILY_DEPOSITS: usize = 16; // deposit timestamps kept for the rolling daily quota
+
+pub const COLLECTION_REGISTRY_PAGE_SIZE: usize = 32;
+pub const WEB2_USER_INDEX_PAGE_SIZE: usize = 16;
+pub const MAX_COLLECTION_NAME_LEN: usize = 32;
+pub const MAX_COLLECTION_URI_LEN: usize = 200;
+pub const MAX_UUID_LEN: usize = 36;
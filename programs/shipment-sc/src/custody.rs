@@ -0,0 +1,54 @@
+use crate::*;
+use anchor_spl::token_2022::Token2022;
+
+// End of custody shared by withdrawals, finalize and dispute resolution: burns
+// the owner's receipt, unlists the deposit from its web2 user index and
+// releases it from the user, global and collection counters.
+pub struct CustodyRelease<'a, 'info> {
+    pub global_pool: &'a mut Account<'info, GlobalPool>,
+    pub global_bump: u8,
+    pub user_pool: &'a mut Account<'info, UserPool>,
+    pub collection_pool: &'a mut Account<'info, CollectionPool>,
+    pub deposit_state: &'a Account<'info, NftDeposit>,
+    pub web2_user_index: &'a mut Account<'info, Web2UserIndex>,
+    pub receipt_mint: &'a Option<UncheckedAccount<'info>>,
+    pub receipt_token_account: &'a Option<UncheckedAccount<'info>>,
+    pub token_2022_program: &'a Option<Program<'info, Token2022>>,
+}
+
+impl CustodyRelease<'_, '_> {
+    pub fn process(self) -> Result<()> {
+        let global_pool = self.global_pool;
+        let user_pool = self.user_pool;
+        let collection_pool = self.collection_pool;
+        let deposit_state = self.deposit_state;
+
+        // Custody ends, burn the owner's receipt
+        burn_deposit_receipt(
+            deposit_state,
+            self.receipt_mint,
+            Receipt::from_accounts(
+                self.receipt_mint,
+                self.receipt_token_account,
+                self.token_2022_program,
+                global_pool.to_account_info(),
+                self.global_bump
+            )
+        )?;
+
+        // Deposit leaves its web2 user index
+        self.web2_user_index.remove(&deposit_state.mint)?;
+
+        user_pool.deposit_count = user_pool.deposit_count
+            .checked_sub(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+        global_pool.total_deposit_count = global_pool.total_deposit_count
+            .checked_sub(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+        collection_pool.deposit_count = collection_pool.deposit_count
+            .checked_sub(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+
+        Ok(())
+    }
+}
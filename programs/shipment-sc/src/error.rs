@@ -22,4 +22,74 @@ pub enum ShipmentError {
     InvalidNFTAddress,
     #[msg("Withdrawal is disabled")]
     DisabledWithdrawal,
+    #[msg("Math operation overflow")]
+    MathOverflow,
+    #[msg("Withdrawal is already requested")]
+    WithdrawalAlreadyRequested,
+    #[msg("Withdrawal is not requested")]
+    WithdrawalNotRequested,
+    #[msg("Grace period is invalid")]
+    InvalidGracePeriod,
+    #[msg("Lock expiry is invalid")]
+    InvalidLockExpiry,
+    #[msg("Deposit is under dispute")]
+    DepositDisputed,
+    #[msg("Deposit is not disputed")]
+    NotDisputed,
+    #[msg("Destination token account is invalid")]
+    InvalidDestination,
+    #[msg("Delivery proof signature is required")]
+    DeliveryProofRequired,
+    #[msg("Delivery proof attestation is invalid")]
+    InvalidDeliveryProof,
+    #[msg("Receipt accounts are required")]
+    ReceiptRequired,
+    #[msg("Receipt token account is invalid")]
+    InvalidReceipt,
+    #[msg("New owner is invalid")]
+    InvalidNewOwner,
+    #[msg("Redemption accounts are required")]
+    RedemptionRequired,
+    #[msg("Redemption account is invalid")]
+    InvalidRedemption,
+    #[msg("Rent recipient is invalid")]
+    InvalidRentRecipient,
+    #[msg("Collection name or uri is too long")]
+    CollectionMetadataTooLong,
+    #[msg("Collection still has active deposits")]
+    CollectionHasDeposits,
+    #[msg("Collection is not allowed")]
+    CollectionNotAllowed,
+    #[msg("Merkle proof is invalid")]
+    InvalidMerkleProof,
+    #[msg("Depositor is not approved")]
+    DepositorNotAllowed,
+    #[msg("Deposit quota exceeded")]
+    QuotaExceeded,
+    #[msg("Web2 user index page is full")]
+    Web2UserIndexFull,
+    #[msg("Web2 identity attestation is required")]
+    IdentityAttestationRequired,
+    #[msg("Web2 identity attestation is invalid")]
+    InvalidIdentityAttestation,
+    #[msg("Web2 user is not linked to the wallet")]
+    Web2UserNotLinked,
+    #[msg("User still has deposits in custody or in the current quota window")]
+    UserHasDeposits,
+    #[msg("User still holds a role")]
+    UserHasRoles,
+    #[msg("Beneficiary must sign or register the depositor as its delegate")]
+    DepositConsentRequired,
+    #[msg("Web2 user uuid is too long")]
+    Web2UserTooLong,
+    #[msg("Web2 user index page can only be opened once the previous page is full")]
+    Web2UserIndexPageSkipped,
+    #[msg("Daily deposit quota exceeds the deposits tracked per user")]
+    InvalidDepositQuota,
+    #[msg("Deposit status can not be cleared while the nft is in custody")]
+    InvalidDepositStatus,
+    #[msg("Account is in neither the legacy nor the current layout")]
+    InvalidLegacyAccount,
+    #[msg("Web2 user does not match the deposit")]
+    InvalidWeb2User,
 }
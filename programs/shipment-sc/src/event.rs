@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::DisputeOutcome;
+
+#[event]
+pub struct DepositFinalized {
+    pub mint: Pubkey, // finalized nft mint, burned with its deposit account
+    pub owner: Pubkey, // web3 account wallet address
+    pub collection: Pubkey, // collection the nft was deposited under
+    pub user: String, // uuid of the web2 account
+    pub delivery_proof: [u8; 32], // hash of the delivery proof accepted on finalize
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub mint: Pubkey, // disputed nft mint
+    pub owner: Pubkey, // owner who opened the dispute, refunded the dispute rent
+    pub opened: i64, // timestamp when dispute opened
+    pub resolved: i64, // timestamp when dispute resolved
+    pub evidence_hash: [u8; 32], // hash of evidence submitted by owner
+    pub resolution_hash: [u8; 32], // hash of evidence backing the admin decision
+    pub outcome: DisputeOutcome,
+}
@@ -0,0 +1,146 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+    token_2022::Token2022,
+};
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    #[account(mut)]
+    pub updater: Signer<'info>,
+
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), updater.key().as_ref()], bump)]
+    pub updater_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_pool.address == user.key() @ ShipmentError::InvalidOwner,
+    )]
+    pub user_pool: Account<'info, UserPool>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = deposit_state.owner == user.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+
+    // Web2 user index page listing the deposit
+    #[account(
+        mut,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&deposit_state.user).as_ref(),
+            deposit_state.index_page.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = global_pool,
+    )]
+    pub dest_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        payer = updater
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: receipt mint PDA, created by the program on first deposit of the nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ApproveWithdrawal<'_> {
+    pub fn process_instruction(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let updater_pool = &mut ctx.accounts.updater_pool;
+        let user_pool = &mut ctx.accounts.user_pool;
+        let deposit_state = &mut ctx.accounts.deposit_state;
+        let collection_pool = &mut ctx.accounts.collection_pool;
+
+        validate_updater(global_pool, updater_pool, &ctx.accounts.updater.key())?;
+
+        // Validate if deposit exist
+        require!(deposit_state.status != 0, ShipmentError::NotDeposited);
+
+        // Disputed deposit is frozen until admin resolves it
+        require!(!deposit_state.disputed, ShipmentError::DepositDisputed);
+
+        // Approval only answers a pending owner request, lock is overridden
+        require!(
+            deposit_state.withdrawal_requested_at != 0,
+            ShipmentError::WithdrawalNotRequested
+        );
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+        let signer = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.dest_token_account.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: global_pool.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+            1
+        )?;
+
+        CustodyRelease {
+            global_pool,
+            global_bump: ctx.bumps.global_pool,
+            user_pool,
+            collection_pool,
+            deposit_state,
+            web2_user_index: &mut ctx.accounts.web2_user_index,
+            receipt_mint: &ctx.accounts.receipt_mint,
+            receipt_token_account: &ctx.accounts.receipt_token_account,
+            token_2022_program: &ctx.accounts.token_2022_program,
+        }.process()?;
+        deposit_state.reset();
+
+        Ok(())
+    }
+}
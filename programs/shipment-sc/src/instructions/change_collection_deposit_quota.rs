@@ -0,0 +1,42 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct ChangeCollectionDepositQuota<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Account<'info, CollectionPool>,
+}
+
+impl ChangeCollectionDepositQuota<'_> {
+    pub fn process_instruction(
+        ctx: &mut Context<Self>,
+        _collection: Pubkey,
+        deposit_quota: DepositQuota
+    ) -> Result<()> {
+        let collection_pool = &mut ctx.accounts.collection_pool;
+
+        validate_admin(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.admin_pool,
+            ctx.accounts.admin.key
+        )?;
+        validate_deposit_quota(&deposit_quota)?;
+
+        collection_pool.deposit_quota = deposit_quota;
+
+        Ok(())
+    }
+}
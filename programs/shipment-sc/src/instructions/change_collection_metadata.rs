@@ -0,0 +1,48 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct ChangeCollectionMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Account<'info, CollectionPool>,
+}
+
+impl ChangeCollectionMetadata<'_> {
+    pub fn process_instruction(
+        ctx: &mut Context<Self>,
+        _collection: Pubkey,
+        name: String,
+        uri: String
+    ) -> Result<()> {
+        let collection_pool = &mut ctx.accounts.collection_pool;
+
+        validate_admin(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.admin_pool,
+            ctx.accounts.admin.key
+        )?;
+
+        require!(
+            name.len() <= MAX_COLLECTION_NAME_LEN && uri.len() <= MAX_COLLECTION_URI_LEN,
+            ShipmentError::CollectionMetadataTooLong
+        );
+
+        collection_pool.name = name;
+        collection_pool.uri = uri;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ChangeDepositQuota<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+}
+
+impl ChangeDepositQuota<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, deposit_quota: DepositQuota) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        validate_admin(global_pool, &ctx.accounts.admin_pool, ctx.accounts.admin.key)?;
+        validate_deposit_quota(&deposit_quota)?;
+
+        global_pool.deposit_quota = deposit_quota;
+
+        Ok(())
+    }
+}
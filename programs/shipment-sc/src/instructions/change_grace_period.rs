@@ -0,0 +1,31 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ChangeGracePeriod<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+}
+
+impl ChangeGracePeriod<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, grace_period: i64) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        validate_admin(global_pool, &ctx.accounts.admin_pool, ctx.accounts.admin.key)?;
+
+        require!(grace_period >= 0, ShipmentError::InvalidGracePeriod);
+
+        global_pool.withdrawal_grace_period = grace_period;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ChangeIdentityAuthority<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+}
+
+impl ChangeIdentityAuthority<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, identity_authority: Pubkey) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        validate_admin(global_pool, &ctx.accounts.admin_pool, ctx.accounts.admin.key)?;
+
+        global_pool.identity_authority = identity_authority;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+use anchor_spl::token::Mint;
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct ChangeRedemptionMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Account<'info, CollectionPool>,
+
+    // Redeemed token mint, none disables redemption for the collection
+    pub redemption_mint: Option<Account<'info, Mint>>,
+}
+
+impl ChangeRedemptionMint<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, _collection: Pubkey) -> Result<()> {
+        let collection_pool = &mut ctx.accounts.collection_pool;
+
+        validate_admin(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.admin_pool,
+            ctx.accounts.admin.key
+        )?;
+
+        collection_pool.redemption_mint = ctx.accounts.redemption_mint
+            .as_ref()
+            .map_or(Pubkey::default(), |mint| mint.key());
+
+        Ok(())
+    }
+}
@@ -28,7 +28,7 @@ impl ChangeRole<'_> {
         validate_admin(
             &ctx.accounts.global_pool,
             &ctx.accounts.admin_pool,
-            &ctx.accounts.admin.key
+            ctx.accounts.admin.key
         )?;
 
         user_pool.admin = admin.unwrap_or(user_pool.admin);
@@ -21,7 +21,7 @@ impl ChangeTreasury<'_> {
         let global_pool = &mut ctx.accounts.global_pool;
 
         // Validate super admin
-        validate_admin(&global_pool, &ctx.accounts.admin_pool, &ctx.accounts.admin.key)?;
+        validate_admin(global_pool, &ctx.accounts.admin_pool, ctx.accounts.admin.key)?;
 
         global_pool.treasury = new_treasury;
 
@@ -0,0 +1,58 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct CloseCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
+        bump,
+        constraint = collection_pool.deposit_count == 0 @ ShipmentError::CollectionHasDeposits,
+        close = admin,
+    )]
+    pub collection_pool: Account<'info, CollectionPool>,
+
+    // Registry page holding the collection
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_REGISTRY_SEED.as_ref(),
+            CollectionRegistry::page_of(collection_pool.registry_index).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub collection_registry: Box<Account<'info, CollectionRegistry>>,
+}
+
+impl CloseCollection<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, collection: Pubkey) -> Result<()> {
+        let collection_pool = &ctx.accounts.collection_pool;
+        let collection_registry = &mut ctx.accounts.collection_registry;
+
+        validate_admin(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.admin_pool,
+            ctx.accounts.admin.key
+        )?;
+
+        // Empty the registry slot, registering the collection again appends a new one
+        let slot = (collection_pool.registry_index as usize) % COLLECTION_REGISTRY_PAGE_SIZE;
+        if collection_registry.collections[slot].eq(&collection) {
+            collection_registry.collections[slot] = Pubkey::default();
+            collection_registry.count = collection_registry.count
+                .checked_sub(1)
+                .ok_or(ShipmentError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_spl::token::Mint;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CloseLegacyDeposit<'info> {
+    pub admin: Signer<'info>,
+
+    // Receives the rent it paid for the legacy deposit
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: deposit in the legacy layout, decoded by the program
+    #[account(mut, seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()], bump)]
+    pub deposit_state: UncheckedAccount<'info>,
+}
+
+impl CloseLegacyDeposit<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let deposit_state = ctx.accounts.deposit_state.to_account_info();
+
+        // Validate super admin
+        require!(
+            ctx.accounts.global_pool.super_admin.eq(ctx.accounts.admin.key),
+            ShipmentError::InvalidAdmin
+        );
+
+        let legacy = match
+            legacy_account::<LegacyNftDeposit, NftDeposit>(
+                &deposit_state,
+                LegacyNftDeposit::DATA_SIZE,
+                NftDeposit::DATA_SIZE
+            )?
+        {
+            Some(legacy) => legacy,
+            None => {
+                msg!("deposit already migrated");
+                return Ok(());
+            }
+        };
+
+        // Live deposits are migrated in place with migrate_nft_deposit
+        require!(legacy.status == 0, ShipmentError::AlreadyDeposited);
+        require!(legacy.owner.eq(ctx.accounts.owner.key), ShipmentError::InvalidOwner);
+
+        // Withdrawn legacy deposit holds nothing, the next deposit of the nft starts afresh
+        close_legacy(&deposit_state, &ctx.accounts.owner.to_account_info())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CloseUser<'info> {
+    // The user itself or an admin
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub user: SystemAccount<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    // Required when an admin closes another user's pool
+    #[account(seeds = [USER_POOL_SEED.as_ref(), authority.key().as_ref()], bump)]
+    pub admin_pool: Option<Account<'info, UserPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+        constraint = !user_pool.admin && !user_pool.updater @ ShipmentError::UserHasRoles,
+        close = recipient,
+    )]
+    pub user_pool: Account<'info, UserPool>,
+
+    // Receives the user pool rent
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+}
+
+impl CloseUser<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let user_pool = &mut ctx.accounts.user_pool;
+
+        if !ctx.accounts.authority.key().eq(&ctx.accounts.user.key()) {
+            match &ctx.accounts.admin_pool {
+                Some(admin_pool) => {
+                    validate_admin(&ctx.accounts.global_pool, admin_pool, ctx.accounts.authority.key)?;
+                }
+                None => {
+                    return Err(error!(ShipmentError::InvalidAdmin));
+                }
+            }
+        }
+
+        // Closing must not reset the daily quota of a user who deposited in the current window
+        require!(
+            user_pool.deposit_count == 0 &&
+                user_pool.deposits_in_window(Clock::get()?.unix_timestamp) == 0,
+            ShipmentError::UserHasDeposits
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_spl::token::Mint;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct DenyWithdrawal<'info> {
+    #[account(mut)]
+    pub updater: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), updater.key().as_ref()], bump)]
+    pub updater_pool: Account<'info, UserPool>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+}
+
+impl DenyWithdrawal<'_> {
+    pub fn process_instruction(ctx: Context<DenyWithdrawal>) -> Result<()> {
+        let deposit_state = &mut ctx.accounts.deposit_state;
+
+        validate_updater(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.updater_pool,
+            &ctx.accounts.updater.key()
+        )?;
+
+        require!(deposit_state.status != 0, ShipmentError::NotDeposited);
+        require!(
+            deposit_state.withdrawal_requested_at != 0,
+            ShipmentError::WithdrawalNotRequested
+        );
+
+        deposit_state.withdrawal_requested_at = 0;
+
+        Ok(())
+    }
+}
@@ -2,12 +2,19 @@ use crate::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{ self, Mint, Token, TokenAccount, Transfer },
+    token_2022::Token2022,
 };
 use mpl_token_metadata::accounts::Metadata;
 use solana_program::pubkey::Pubkey;
 
 #[derive(Accounts)]
-#[instruction(collection: Pubkey)]
+#[instruction(
+    collection: Pubkey,
+    uuid: String,
+    mint_proof: Option<Vec<[u8; 32]>>,
+    depositor_proof: Option<Vec<[u8; 32]>>,
+    index_page: u64
+)]
 pub struct DepositNft<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -15,19 +22,35 @@ pub struct DepositNft<'info> {
     #[account(mut, seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
     pub global_pool: Account<'info, GlobalPool>,
 
+    // Created with no roles on the first deposit of the wallet
     #[account(
-        mut,
+        init_if_needed,
         seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
-        bump
+        bump,
+        payer = user,
+        space = UserPool::DATA_SIZE
     )]
     pub user_pool: Account<'info, UserPool>,
 
-    #[account(seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()], bump)]
-    pub collection_pool: Account<'info, CollectionPool>,
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
 
     pub token_mint: Box<Account<'info, Mint>>,
-    /// CHECK: instruction will fail if wrong metadata is supplied
-    #[account(mut)]
+
+    // Supplied when the mint is allowlisted on its own
+    #[account(seeds = [MINT_ALLOWLIST_SEED.as_ref(), token_mint.key().as_ref()], bump)]
+    pub mint_allowlist: Option<Box<Account<'info, MintAllowlist>>>,
+
+    /// CHECK: metadata PDA of the mint, owned by the token metadata program
+    #[account(
+        mut,
+        address = Metadata::find_pda(&token_mint.key()).0,
+        owner = mpl_token_metadata::ID
+    )]
     pub mint_metadata: UncheckedAccount<'info>,
 
     #[account(
@@ -39,6 +62,31 @@ pub struct DepositNft<'info> {
     )]
     pub deposit_state: Account<'info, NftDeposit>,
 
+    // Web2 user index page listing the deposit, chosen by the client among pages with a free slot
+    #[account(
+        init_if_needed,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&uuid).as_ref(),
+            index_page.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = user,
+        space = Web2UserIndex::DATA_SIZE
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    // Previous index page of the web2 user, needed to open a new page
+    #[account(
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&uuid).as_ref(),
+            index_page.saturating_sub(1).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub previous_web2_user_index: Option<Box<Account<'info, Web2UserIndex>>>,
+
     #[account(
         mut, 
         token::mint = token_mint, 
@@ -54,9 +102,23 @@ pub struct DepositNft<'info> {
     )]
     pub dest_token_account: Box<Account<'info, TokenAccount>>,
 
+    /// CHECK: receipt mint PDA, created by the program on first deposit of the nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    /// CHECK: intstruction will fail if wrong program is supplied
+    /// CHECK: token metadata program
+    #[account(address = mpl_token_metadata::ID)]
     pub token_metadata_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
@@ -65,67 +127,206 @@ impl DepositNft<'_> {
     pub fn process_instruction(
         ctx: Context<DepositNft>,
         collection: Pubkey,
-        user: String
+        user: String,
+        mint_proof: Option<Vec<[u8; 32]>>,
+        depositor_proof: Option<Vec<[u8; 32]>>,
+        index_page: u64
     ) -> Result<()> {
-        let global_pool = &mut ctx.accounts.global_pool;
-        let user_pool = &mut ctx.accounts.user_pool;
-        let deposit_state = &mut ctx.accounts.deposit_state;
+        let accounts = ctx.accounts;
+        Deposit {
+            depositor: accounts.user.to_account_info(),
+            owner: accounts.user.to_account_info(),
+            global_pool: &mut accounts.global_pool,
+            global_bump: ctx.bumps.global_pool,
+            user_pool: &mut accounts.user_pool,
+            collection_pool: &mut accounts.collection_pool,
+            token_mint: &accounts.token_mint,
+            mint_allowlist: &accounts.mint_allowlist,
+            mint_metadata: &accounts.mint_metadata,
+            deposit_state: &mut accounts.deposit_state,
+            web2_user_index: &mut accounts.web2_user_index,
+            previous_web2_user_index: &accounts.previous_web2_user_index,
+            token_account: &accounts.token_account,
+            dest_token_account: &accounts.dest_token_account,
+            receipt_mint: &accounts.receipt_mint,
+            receipt_token_account: &accounts.receipt_token_account,
+            token_program: &accounts.token_program,
+            token_2022_program: &accounts.token_2022_program,
+            associated_token_program: &accounts.associated_token_program,
+            system_program: &accounts.system_program,
+        }.process(collection, user, mint_proof, depositor_proof, index_page)
+    }
+}
 
-        // Check user pool owner matched with signed user
-        require!(user_pool.address.eq(&ctx.accounts.user.key()), ShipmentError::InvalidOwner);
+// Deposit accounts shared by direct and delegated deposits
+pub struct Deposit<'a, 'info> {
+    pub depositor: AccountInfo<'info>, // token authority, pays the rent
+    pub owner: AccountInfo<'info>, // wallet the deposit is attributed to
+    pub global_pool: &'a mut Account<'info, GlobalPool>,
+    pub global_bump: u8,
+    pub user_pool: &'a mut Account<'info, UserPool>,
+    pub collection_pool: &'a mut Account<'info, CollectionPool>,
+    pub token_mint: &'a Account<'info, Mint>,
+    pub mint_allowlist: &'a Option<Box<Account<'info, MintAllowlist>>>,
+    pub mint_metadata: &'a UncheckedAccount<'info>,
+    pub deposit_state: &'a mut Account<'info, NftDeposit>,
+    pub web2_user_index: &'a mut Account<'info, Web2UserIndex>,
+    pub previous_web2_user_index: &'a Option<Box<Account<'info, Web2UserIndex>>>,
+    pub token_account: &'a Account<'info, TokenAccount>,
+    pub dest_token_account: &'a Account<'info, TokenAccount>,
+    pub receipt_mint: &'a Option<UncheckedAccount<'info>>,
+    pub receipt_token_account: &'a Option<UncheckedAccount<'info>>,
+    pub token_program: &'a Program<'info, Token>,
+    pub token_2022_program: &'a Option<Program<'info, Token2022>>,
+    pub associated_token_program: &'a Program<'info, AssociatedToken>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl Deposit<'_, '_> {
+    pub fn process(
+        self,
+        collection: Pubkey,
+        user: String,
+        mint_proof: Option<Vec<[u8; 32]>>,
+        depositor_proof: Option<Vec<[u8; 32]>>,
+        index_page: u64
+    ) -> Result<()> {
+        let global_pool = self.global_pool;
+        let user_pool = self.user_pool;
+        let collection_pool = self.collection_pool;
+        let deposit_state = self.deposit_state;
+        let web2_user_index = self.web2_user_index;
+
+        // Fresh user pool, roles are only granted through change_role
+        if user_pool.address == Pubkey::default() {
+            user_pool.address = self.owner.key();
+            user_pool.admin = false;
+            user_pool.updater = false;
+        }
+        require!(user_pool.address == self.owner.key(), ShipmentError::InvalidOwner);
 
         // Check if deposit possible
         require!(deposit_state.status == 0, ShipmentError::AlreadyDeposited);
 
-        // Verify metadata is legit
-        let nft_metadata = Metadata::safe_deserialize(
-            &mut ctx.accounts.mint_metadata.to_account_info().data.borrow_mut()
-        ).unwrap();
-
-        // Check if this NFT is the wanted collection and verified
-        let mut valid: u8 = 0;
-        if let Some(meta_collection) = nft_metadata.collection {
-            msg!("collection: {}", meta_collection.key.to_string());
-            if meta_collection.key.eq(&collection) {
-                valid = 1;
-            }
+        // Revoked collection accepts no new deposits
+        require!(collection_pool.allowed, ShipmentError::CollectionNotAllowed);
+
+        // Deposit is attributed to the web2 user linked to the wallet
+        validate_web2_user(global_pool, user_pool, &user)?;
+
+        // Per-user quotas, collection limits override the global ones
+        let timestamp = Clock::get()?.unix_timestamp;
+        let quota = collection_pool.deposit_quota.or(global_pool.deposit_quota);
+        validate_concurrent_quota(&quota, user_pool)?;
+        require!(
+            quota.max_daily_deposits == 0 ||
+                user_pool.deposits_in_window(timestamp) < quota.max_daily_deposits,
+            ShipmentError::QuotaExceeded
+        );
+
+        // Closed beta, collection root takes precedence over the global one
+        let depositor_merkle_root = if collection_pool.depositor_merkle_root != [0; 32] {
+            collection_pool.depositor_merkle_root
         } else {
-            return Err(error!(ShipmentError::MetadataCreatorParseError));
+            global_pool.depositor_merkle_root
+        };
+        if depositor_merkle_root != [0; 32] {
+            let proof = depositor_proof.ok_or(ShipmentError::DepositorNotAllowed)?;
+            require!(
+                verify_merkle_proof(
+                    &proof,
+                    &depositor_merkle_root,
+                    merkle_leaf(&self.depositor.key())
+                ),
+                ShipmentError::DepositorNotAllowed
+            );
         }
-        if let Some(creators) = nft_metadata.creators {
-            for creator in creators {
-                if creator.address.eq(&collection) {
-                    valid = 1;
-                    break;
-                }
+
+        // Allowlisted mints are deposited without collection metadata
+        let mint_allowed = match (&self.mint_allowlist, &mint_proof) {
+            (Some(mint_allowlist), _) => {
+                require!(
+                    mint_allowlist.collection.eq(&collection),
+                    ShipmentError::InvalidCollection
+                );
+                true
             }
-        } else {
-            return Err(error!(ShipmentError::MetadataCreatorParseError));
-        }
+            (None, Some(proof)) => {
+                require!(
+                    collection_pool.mint_merkle_root != [0; 32] &&
+                        verify_merkle_proof(
+                            proof,
+                            &collection_pool.mint_merkle_root,
+                            merkle_leaf(&self.token_mint.key())
+                        ),
+                    ShipmentError::InvalidMerkleProof
+                );
+                true
+            }
+            (None, None) => false,
+        };
 
-        require!(valid == 1, ShipmentError::InvalidCollection);
+        if !mint_allowed {
+            validate_collection_metadata(&self.mint_metadata.to_account_info(), &collection)?;
+        }
 
         // Transfer NFT to global pool
-        let token_program = ctx.accounts.token_program.to_account_info();
+        let token_program = self.token_program.to_account_info();
         let cpi_accounts = Transfer {
-            from: ctx.accounts.token_account.to_account_info(),
-            to: ctx.accounts.dest_token_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
+            from: self.token_account.to_account_info(),
+            to: self.dest_token_account.to_account_info(),
+            authority: self.depositor.clone(),
         };
         token::transfer(CpiContext::new(token_program.clone(), cpi_accounts), 1)?;
 
-        // Update nft deposit info
-        let timestamp = Clock::get()?.unix_timestamp;
+        // Mint custody receipt to the owner when receipt accounts are supplied
+        let receipt = Receipt::from_accounts(
+            self.receipt_mint,
+            self.receipt_token_account,
+            self.token_2022_program,
+            global_pool.to_account_info(),
+            self.global_bump
+        );
+        if let Some(receipt) = &receipt {
+            receipt.issue(
+                &self.token_mint.key(),
+                &self.owner,
+                &self.depositor,
+                &self.associated_token_program.to_account_info(),
+                &self.system_program.to_account_info()
+            )?;
+        }
 
+        // Update nft deposit info
         deposit_state.reset();
-        deposit_state.owner = ctx.accounts.user.key();
+        // Deposit state and pool ATA are paid by the depositor who created them
+        if deposit_state.payer == Pubkey::default() {
+            deposit_state.payer = self.depositor.key();
+        }
+        deposit_state.owner = self.owner.key();
         deposit_state.user = user;
-        deposit_state.mint = ctx.accounts.token_mint.key();
+        deposit_state.mint = self.token_mint.key();
+        deposit_state.collection = collection;
         deposit_state.created = timestamp;
         deposit_state.status = 1; // deposited
+        deposit_state.receipt = receipt.is_some();
+        deposit_state.index_page = index_page;
+
+        // List the deposit under its web2 user
+        validate_index_page(web2_user_index, self.previous_web2_user_index, index_page)?;
+        web2_user_index.page = index_page;
+        web2_user_index.insert(self.token_mint.key())?;
 
-        user_pool.deposit_count += 1;
-        global_pool.total_deposit_count += 1;
+        user_pool.deposit_count = user_pool.deposit_count
+            .checked_add(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+        user_pool.record_deposit(timestamp);
+        global_pool.total_deposit_count = global_pool.total_deposit_count
+            .checked_add(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+        collection_pool.deposit_count = collection_pool.deposit_count
+            .checked_add(1)
+            .ok_or(ShipmentError::MathOverflow)?;
 
         Ok(())
     }
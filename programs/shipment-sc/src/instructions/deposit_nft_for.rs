@@ -0,0 +1,180 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ Mint, Token, TokenAccount },
+    token_2022::Token2022,
+};
+use mpl_token_metadata::accounts::Metadata;
+use solana_program::pubkey::Pubkey;
+
+#[derive(Accounts)]
+#[instruction(
+    collection: Pubkey,
+    uuid: String,
+    mint_proof: Option<Vec<[u8; 32]>>,
+    depositor_proof: Option<Vec<[u8; 32]>>,
+    index_page: u64
+)]
+pub struct DepositNftFor<'info> {
+    // Token authority of the nft, pays the rent
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // Wallet the deposit is attributed to, consents by signing or through a deposit delegate
+    pub beneficiary: SystemAccount<'info>,
+
+    // Registered by the beneficiary to accept deposits of the depositor without its signature
+    #[account(
+        seeds = [
+            DEPOSIT_DELEGATE_SEED.as_ref(),
+            beneficiary.key().as_ref(),
+            depositor.key().as_ref(),
+        ],
+        bump
+    )]
+    pub deposit_delegate: Option<Account<'info, DepositDelegate>>,
+
+    #[account(mut, seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    // Created with no roles on the first deposit attributed to the beneficiary
+    #[account(
+        init_if_needed,
+        seeds = [USER_POOL_SEED.as_ref(), beneficiary.key().as_ref()],
+        bump,
+        payer = depositor,
+        space = UserPool::DATA_SIZE
+    )]
+    pub user_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Supplied when the mint is allowlisted on its own
+    #[account(seeds = [MINT_ALLOWLIST_SEED.as_ref(), token_mint.key().as_ref()], bump)]
+    pub mint_allowlist: Option<Box<Account<'info, MintAllowlist>>>,
+
+    /// CHECK: metadata PDA of the mint, owned by the token metadata program
+    #[account(
+        mut,
+        address = Metadata::find_pda(&token_mint.key()).0,
+        owner = mpl_token_metadata::ID
+    )]
+    pub mint_metadata: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        payer = depositor,
+        space = NftDeposit::DATA_SIZE
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+
+    // Web2 user index page listing the deposit, chosen by the client among pages with a free slot
+    #[account(
+        init_if_needed,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&uuid).as_ref(),
+            index_page.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = depositor,
+        space = Web2UserIndex::DATA_SIZE
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    // Previous index page of the web2 user, needed to open a new page
+    #[account(
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&uuid).as_ref(),
+            index_page.saturating_sub(1).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub previous_web2_user_index: Option<Box<Account<'info, Web2UserIndex>>>,
+
+    #[account(
+        mut, 
+        token::mint = token_mint, 
+        token::authority = depositor,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = token_mint,
+        associated_token::authority = global_pool,
+        payer = depositor
+    )]
+    pub dest_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: receipt mint PDA, created by the program on first deposit of the nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl DepositNftFor<'_> {
+    pub fn process_instruction(
+        ctx: Context<DepositNftFor>,
+        collection: Pubkey,
+        user: String,
+        mint_proof: Option<Vec<[u8; 32]>>,
+        depositor_proof: Option<Vec<[u8; 32]>>,
+        index_page: u64
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+
+        // Beneficiary consents to the deposit by signature or pre-registered delegate
+        if !accounts.beneficiary.is_signer && accounts.deposit_delegate.is_none() {
+            return Err(error!(ShipmentError::DepositConsentRequired));
+        }
+
+        Deposit {
+            depositor: accounts.depositor.to_account_info(),
+            owner: accounts.beneficiary.to_account_info(),
+            global_pool: &mut accounts.global_pool,
+            global_bump: ctx.bumps.global_pool,
+            user_pool: &mut accounts.user_pool,
+            collection_pool: &mut accounts.collection_pool,
+            token_mint: &accounts.token_mint,
+            mint_allowlist: &accounts.mint_allowlist,
+            mint_metadata: &accounts.mint_metadata,
+            deposit_state: &mut accounts.deposit_state,
+            web2_user_index: &mut accounts.web2_user_index,
+            previous_web2_user_index: &accounts.previous_web2_user_index,
+            token_account: &accounts.token_account,
+            dest_token_account: &accounts.dest_token_account,
+            receipt_mint: &accounts.receipt_mint,
+            receipt_token_account: &accounts.receipt_token_account,
+            token_program: &accounts.token_program,
+            token_2022_program: &accounts.token_2022_program,
+            associated_token_program: &accounts.associated_token_program,
+            system_program: &accounts.system_program,
+        }.process(collection, user, mint_proof, depositor_proof, index_page)
+    }
+}
@@ -1,6 +1,10 @@
 use crate::*;
-use anchor_spl::token::{ self, Burn, CloseAccount, Mint, Token, TokenAccount };
-use solana_program::pubkey::Pubkey;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Burn, CloseAccount, Mint, Token, TokenAccount },
+    token_2022::Token2022,
+};
+use solana_program::sysvar;
 
 #[derive(Accounts)]
 pub struct FinalizeDeposit<'info> {
@@ -23,7 +27,8 @@ pub struct FinalizeDeposit<'info> {
     #[account(
         mut,
         seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
-        bump
+        bump,
+        constraint = user_pool.address == user.key() @ ShipmentError::InvalidOwner,
     )]
     pub user_pool: Account<'info, UserPool>,
 
@@ -34,39 +39,131 @@ pub struct FinalizeDeposit<'info> {
         mut,
         seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
         bump,
-        close = treasury,
+        close = rent_recipient,
+        constraint = deposit_state.owner == user.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
     )]
     pub deposit_state: Account<'info, NftDeposit>,
 
+    // Web2 user index page listing the deposit
+    #[account(
+        mut,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&deposit_state.user).as_ref(),
+            deposit_state.index_page.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
     #[account(mut)]
     pub treasury: SystemAccount<'info>,
 
+    // Receives deposit and pool ATA rent, chosen by the collection's rent destination
+    #[account(mut)]
+    pub rent_recipient: SystemAccount<'info>,
+
     #[account(
-        mut, 
-        token::mint = token_mint, 
-        token::authority = global_pool,
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = global_pool,
     )]
     pub dest_token_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    pub redemption_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// CHECK: per-collection redemption mint authority
+    #[account(
+        seeds = [REDEMPTION_AUTHORITY_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub redemption_authority: Option<UncheckedAccount<'info>>,
+
+    // Stock of redeemed tokens, used when the authority can not mint
+    #[account(
+        mut,
+        token::mint = redemption_mint,
+        token::authority = redemption_authority,
+    )]
+    pub redemption_source: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: owner's redeemed token associated account, created if missing
+    #[account(mut)]
+    pub redemption_token_account: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// CHECK: instructions sysvar, read for the courier delivery attestation
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: receipt mint PDA, created by the program on first deposit of the nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
     pub system_program: Program<'info, System>,
 }
 
 impl FinalizeDeposit<'_> {
-    pub fn process_instruction(ctx: Context<FinalizeDeposit>) -> Result<()> {
+    pub fn process_instruction(ctx: Context<FinalizeDeposit>, proof_hash: [u8; 32]) -> Result<()> {
         let global_pool = &mut ctx.accounts.global_pool;
         let user_pool = &mut ctx.accounts.user_pool;
         let updater_pool = &mut ctx.accounts.updater_pool;
         let deposit_state = &mut ctx.accounts.deposit_state;
+        let collection_pool = &mut ctx.accounts.collection_pool;
 
-        validate_updater(&global_pool, &updater_pool, &ctx.accounts.updater.key())?;
-
-        // Check user pool owner matched with signed user
-        require!(user_pool.address.eq(&ctx.accounts.user.key()), ShipmentError::InvalidOwner);
+        validate_updater(global_pool, updater_pool, &ctx.accounts.updater.key())?;
 
         // Check if deposit possible
         require!(deposit_state.status != 0, ShipmentError::NotDeposited);
 
+        // Disputed deposit is frozen until admin resolves it
+        require!(!deposit_state.disputed, ShipmentError::DepositDisputed);
+
+        // Delivery must be confirmed by the owner or the courier oracle when required
+        validate_delivery_proof(
+            global_pool,
+            ctx.accounts.user.is_signer,
+            &ctx.accounts.instructions,
+            &ctx.accounts.token_mint.key(),
+            &proof_hash
+        )?;
+        // Deposit account is closed below, the proof outlives it in the event
+        emit!(DepositFinalized {
+            mint: deposit_state.mint,
+            owner: deposit_state.owner,
+            collection: deposit_state.collection,
+            user: deposit_state.user.clone(),
+            delivery_proof: proof_hash,
+        });
+
+        validate_rent_recipient(
+            collection_pool,
+            deposit_state,
+            ctx.accounts.treasury.key,
+            ctx.accounts.updater.key,
+            ctx.accounts.rent_recipient.key
+        )?;
+
         // Burn deposit NFT
         let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
         let signer = &[&seeds[..]];
@@ -81,15 +178,41 @@ impl FinalizeDeposit<'_> {
         // Close ATA
         let cpi_accounts = CloseAccount {
             account: ctx.accounts.dest_token_account.to_account_info(),
-            destination: ctx.accounts.treasury.to_account_info(),
+            destination: ctx.accounts.rent_recipient.to_account_info(),
             authority: global_pool.to_account_info(),
         };
         token::close_account(
             CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer)
         )?;
 
-        user_pool.deposit_count -= 1;
-        global_pool.total_deposit_count -= 1;
+        CustodyRelease {
+            global_pool,
+            global_bump: ctx.bumps.global_pool,
+            user_pool,
+            collection_pool,
+            deposit_state,
+            web2_user_index: &mut ctx.accounts.web2_user_index,
+            receipt_mint: &ctx.accounts.receipt_mint,
+            receipt_token_account: &ctx.accounts.receipt_token_account,
+            token_2022_program: &ctx.accounts.token_2022_program,
+        }.process()?;
+
+        // Give the owner the collection's redeemed token
+        let accounts = &ctx.accounts;
+        RedemptionIssue {
+            collection_pool: &accounts.collection_pool,
+            collection: accounts.deposit_state.collection,
+            payer: accounts.updater.to_account_info(),
+            owner: accounts.user.to_account_info(),
+            redemption_mint: &accounts.redemption_mint,
+            redemption_authority: &accounts.redemption_authority,
+            redemption_authority_bump: ctx.bumps.redemption_authority,
+            redemption_source: &accounts.redemption_source,
+            redemption_token_account: &accounts.redemption_token_account,
+            associated_token_program: &accounts.associated_token_program,
+            token_program: &accounts.token_program,
+            system_program: &accounts.system_program,
+        }.process()?;
 
         Ok(())
     }
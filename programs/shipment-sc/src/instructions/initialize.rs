@@ -24,6 +24,7 @@ impl Initialize<'_> {
 
         global_pool.super_admin = ctx.accounts.admin.key();
         global_pool.treasury = treasury;
+        global_pool.withdrawal_grace_period = DEFAULT_WITHDRAWAL_GRACE_PERIOD;
 
         Ok(())
     }
@@ -0,0 +1,58 @@
+use crate::*;
+use solana_program::sysvar;
+
+#[derive(Accounts)]
+pub struct LinkWeb2Identity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    // Created with no roles when linking before the first deposit
+    #[account(
+        init_if_needed,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = UserPool::DATA_SIZE
+    )]
+    pub user_pool: Account<'info, UserPool>,
+
+    /// CHECK: instructions sysvar, read for the identity authority attestation
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl LinkWeb2Identity<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, uuid: String) -> Result<()> {
+        let global_pool = &ctx.accounts.global_pool;
+        let user = ctx.accounts.user.key();
+        validate_uuid(&uuid)?;
+
+        // Fresh user pool, roles are only granted through change_role
+        let user_pool = &mut ctx.accounts.user_pool;
+        if user_pool.address == Pubkey::default() {
+            user_pool.address = user;
+            user_pool.admin = false;
+            user_pool.updater = false;
+        }
+        require!(user_pool.address == user, ShipmentError::InvalidOwner);
+
+        // Binding must be verified by the instruction right before the link
+        validate_ed25519_attestation(
+            &ctx.accounts.instructions,
+            &global_pool.identity_authority,
+            &web2_identity_message(&user, &uuid),
+            ShipmentError::IdentityAttestationRequired,
+            ShipmentError::InvalidIdentityAttestation
+        )?;
+
+        user_pool.web2_user = Web2UserIndex::user_seed(&uuid);
+        msg!("linked web2 user: {}", uuid);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct MigrateCollectionPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut, seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    /// CHECK: collection pool in the legacy layout, decoded by the program
+    #[account(mut, seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()], bump)]
+    pub collection_pool: UncheckedAccount<'info>,
+
+    // Registry page receiving the next collection
+    #[account(
+        init_if_needed,
+        seeds = [
+            COLLECTION_REGISTRY_SEED.as_ref(),
+            CollectionRegistry::page_of(global_pool.collection_count).to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        space = CollectionRegistry::DATA_SIZE
+    )]
+    pub collection_registry: Box<Account<'info, CollectionRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateCollectionPool<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, collection: Pubkey) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let collection_pool = ctx.accounts.collection_pool.to_account_info();
+
+        // Validate super admin
+        require!(global_pool.super_admin.eq(ctx.accounts.admin.key), ShipmentError::InvalidAdmin);
+
+        let legacy = match
+            legacy_account::<LegacyCollectionPool, CollectionPool>(
+                &collection_pool,
+                LegacyCollectionPool::DATA_SIZE,
+                CollectionPool::DATA_SIZE
+            )?
+        {
+            Some(legacy) => legacy,
+            None => {
+                msg!("collection pool already migrated");
+                return Ok(());
+            }
+        };
+
+        // Legacy collections were never listed, they join the registry like a new registration
+        let registry_index = ctx.accounts.collection_registry.append(global_pool, collection)?;
+
+        // Live deposits are counted again as they are migrated
+        let migrated = CollectionPool {
+            address: legacy.address,
+            allowed: legacy.allowed,
+            redemption_mint: Pubkey::default(),
+            rent_destination: RentDestination::default(),
+            deposit_count: 0,
+            registry_index,
+            mint_merkle_root: [0; 32],
+            depositor_merkle_root: [0; 32],
+            deposit_quota: DepositQuota::default(),
+            name: String::new(),
+            uri: String::new(),
+        };
+        write_migrated(
+            &collection_pool,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrated,
+            CollectionPool::DATA_SIZE
+        )
+    }
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MigrateGlobalPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: global pool in the legacy layout, decoded by the program
+    #[account(mut, seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateGlobalPool<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = ctx.accounts.global_pool.to_account_info();
+
+        let legacy = match
+            legacy_account::<LegacyGlobalPool, GlobalPool>(
+                &global_pool,
+                LegacyGlobalPool::DATA_SIZE,
+                GlobalPool::DATA_SIZE
+            )?
+        {
+            Some(legacy) => legacy,
+            None => {
+                msg!("global pool already migrated");
+                return Ok(());
+            }
+        };
+
+        // Validate super admin
+        require!(legacy.super_admin.eq(ctx.accounts.admin.key), ShipmentError::InvalidAdmin);
+
+        // Live deposits stay counted, settings added since start disabled
+        let migrated = GlobalPool {
+            super_admin: legacy.super_admin,
+            treasury: legacy.treasury,
+            total_deposit_count: legacy.total_deposit_count,
+            withdrawal_grace_period: DEFAULT_WITHDRAWAL_GRACE_PERIOD,
+            delivery_proof_required: false,
+            courier_oracle: Pubkey::default(),
+            transfer_cosign_required: false,
+            collection_count: 0,
+            depositor_merkle_root: [0; 32],
+            deposit_quota: DepositQuota::default(),
+            identity_authority: Pubkey::default(),
+        };
+        write_migrated(
+            &global_pool,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrated,
+            GlobalPool::DATA_SIZE
+        )
+    }
+}
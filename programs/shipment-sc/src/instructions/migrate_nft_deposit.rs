@@ -0,0 +1,131 @@
+use anchor_spl::token::Mint;
+use mpl_token_metadata::accounts::Metadata;
+
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey, user: String, index_page: u64)]
+pub struct MigrateNftDeposit<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    // Collection pool migrated beforehand
+    #[account(mut, seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()], bump)]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: metadata PDA of the mint, owned by the token metadata program
+    #[account(address = Metadata::find_pda(&token_mint.key()).0, owner = mpl_token_metadata::ID)]
+    pub mint_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: deposit in the legacy layout, decoded by the program
+    #[account(mut, seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()], bump)]
+    pub deposit_state: UncheckedAccount<'info>,
+
+    // Web2 user index page listing the deposit, chosen by the client among pages with a free slot
+    #[account(
+        init_if_needed,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&user).as_ref(),
+            index_page.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        space = Web2UserIndex::DATA_SIZE
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    // Previous index page of the web2 user, needed to open a new page
+    #[account(
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&user).as_ref(),
+            index_page.saturating_sub(1).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub previous_web2_user_index: Option<Box<Account<'info, Web2UserIndex>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateNftDeposit<'_> {
+    pub fn process_instruction(
+        ctx: &mut Context<Self>,
+        collection: Pubkey,
+        user: String,
+        index_page: u64
+    ) -> Result<()> {
+        let deposit_state = ctx.accounts.deposit_state.to_account_info();
+        let collection_pool = &mut ctx.accounts.collection_pool;
+        let web2_user_index = &mut ctx.accounts.web2_user_index;
+
+        // Validate super admin
+        require!(
+            ctx.accounts.global_pool.super_admin.eq(ctx.accounts.admin.key),
+            ShipmentError::InvalidAdmin
+        );
+
+        let legacy = match
+            legacy_account::<LegacyNftDeposit, NftDeposit>(
+                &deposit_state,
+                LegacyNftDeposit::DATA_SIZE,
+                NftDeposit::DATA_SIZE
+            )?
+        {
+            Some(legacy) => legacy,
+            None => {
+                msg!("deposit already migrated");
+                return Ok(());
+            }
+        };
+
+        // Withdrawn legacy deposits are closed with close_legacy_deposit
+        require!(legacy.status != 0, ShipmentError::NotDeposited);
+        require!(legacy.user == user, ShipmentError::InvalidWeb2User);
+        validate_uuid(&user)?;
+
+        // Legacy deposits were only accepted through collection metadata
+        validate_collection_metadata(&ctx.accounts.mint_metadata.to_account_info(), &collection)?;
+
+        // List the deposit under its web2 user
+        validate_index_page(web2_user_index, &ctx.accounts.previous_web2_user_index, index_page)?;
+        web2_user_index.page = index_page;
+        web2_user_index.insert(legacy.mint)?;
+
+        // Global and user pools kept counting the deposit, the collection counts it again
+        collection_pool.deposit_count = collection_pool.deposit_count
+            .checked_add(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+
+        // The owner deposited it and paid its rent, custody state added since starts cleared
+        let migrated = NftDeposit {
+            owner: legacy.owner,
+            mint: legacy.mint,
+            created: legacy.created,
+            status: legacy.status,
+            locked: legacy.locked,
+            lock_reason: 0,
+            locked_until: 0,
+            withdrawal_requested_at: 0,
+            disputed: false,
+            receipt: false,
+            collection,
+            payer: legacy.owner,
+            index_page,
+            user,
+        };
+        write_migrated(
+            &deposit_state,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrated,
+            NftDeposit::DATA_SIZE
+        )
+    }
+}
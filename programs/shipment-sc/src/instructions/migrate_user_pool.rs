@@ -0,0 +1,61 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MigrateUserPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub user: SystemAccount<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    /// CHECK: user pool in the legacy layout, decoded by the program
+    #[account(mut, seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()], bump)]
+    pub user_pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateUserPool<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let user_pool = ctx.accounts.user_pool.to_account_info();
+
+        // Validate super admin
+        require!(
+            ctx.accounts.global_pool.super_admin.eq(ctx.accounts.admin.key),
+            ShipmentError::InvalidAdmin
+        );
+
+        let legacy = match
+            legacy_account::<LegacyUserPool, UserPool>(
+                &user_pool,
+                LegacyUserPool::DATA_SIZE,
+                UserPool::DATA_SIZE
+            )?
+        {
+            Some(legacy) => legacy,
+            None => {
+                msg!("user pool already migrated");
+                return Ok(());
+            }
+        };
+
+        // Roles and live deposits are kept, quota window and web2 identity start empty
+        let migrated = UserPool {
+            address: legacy.address,
+            deposit_count: legacy.deposit_count,
+            admin: legacy.admin,
+            updater: legacy.updater,
+            recent_deposits: [0; MAX_DAILY_DEPOSITS],
+            web2_user: [0; 32],
+        };
+        write_migrated(
+            &user_pool,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrated,
+            UserPool::DATA_SIZE
+        )
+    }
+}
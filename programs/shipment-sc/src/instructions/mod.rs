@@ -22,3 +22,66 @@ pub mod withdraw_treasury;
 pub use withdraw_treasury::*;
 pub mod finalize_deposit;
 pub use finalize_deposit::*;
+
+pub mod change_grace_period;
+pub use change_grace_period::*;
+pub mod request_withdrawal;
+pub use request_withdrawal::*;
+pub mod approve_withdrawal;
+pub use approve_withdrawal::*;
+pub mod deny_withdrawal;
+pub use deny_withdrawal::*;
+pub mod open_dispute;
+pub use open_dispute::*;
+pub mod resolve_dispute;
+pub use resolve_dispute::*;
+pub mod change_delivery_proof;
+pub use change_delivery_proof::*;
+pub mod change_transfer_policy;
+pub use change_transfer_policy::*;
+pub mod transfer_deposit_ownership;
+pub use transfer_deposit_ownership::*;
+pub mod change_redemption_mint;
+pub use change_redemption_mint::*;
+pub mod change_rent_destination;
+pub use change_rent_destination::*;
+pub mod change_collection_metadata;
+pub use change_collection_metadata::*;
+pub mod close_collection;
+pub use close_collection::*;
+pub mod register_mint;
+pub use register_mint::*;
+pub mod revoke_mint;
+pub use revoke_mint::*;
+pub mod change_mint_merkle_root;
+pub use change_mint_merkle_root::*;
+pub mod change_depositor_merkle_root;
+pub use change_depositor_merkle_root::*;
+pub mod change_collection_depositor_merkle_root;
+pub use change_collection_depositor_merkle_root::*;
+pub mod change_deposit_quota;
+pub use change_deposit_quota::*;
+pub mod change_collection_deposit_quota;
+pub use change_collection_deposit_quota::*;
+pub mod change_identity_authority;
+pub use change_identity_authority::*;
+pub mod link_web2_identity;
+pub use link_web2_identity::*;
+pub mod close_user;
+pub use close_user::*;
+pub mod register_deposit_delegate;
+pub use register_deposit_delegate::*;
+pub mod revoke_deposit_delegate;
+pub use revoke_deposit_delegate::*;
+pub mod deposit_nft_for;
+pub use deposit_nft_for::*;
+pub mod migrate_global_pool;
+pub use migrate_global_pool::*;
+pub mod migrate_collection_pool;
+pub use migrate_collection_pool::*;
+pub mod migrate_user_pool;
+pub use migrate_user_pool::*;
+pub mod migrate_nft_deposit;
+pub use migrate_nft_deposit::*;
+pub mod close_legacy_deposit;
+pub use close_legacy_deposit::*;
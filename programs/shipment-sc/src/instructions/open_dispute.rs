@@ -0,0 +1,52 @@
+use anchor_spl::token::Mint;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = deposit_state.owner == owner.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+
+    #[account(
+        init_if_needed,
+        seeds = [DISPUTE_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        payer = owner,
+        space = Dispute::DATA_SIZE
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenDispute<'_> {
+    pub fn process_instruction(ctx: Context<OpenDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+        let deposit_state = &mut ctx.accounts.deposit_state;
+        let dispute = &mut ctx.accounts.dispute;
+
+        // Validate if deposit exist
+        require!(deposit_state.status != 0, ShipmentError::NotDeposited);
+
+        require!(!deposit_state.disputed, ShipmentError::DepositDisputed);
+
+        dispute.mint = ctx.accounts.token_mint.key();
+        dispute.owner = ctx.accounts.owner.key();
+        dispute.opened = Clock::get()?.unix_timestamp;
+        dispute.evidence_hash = evidence_hash;
+
+        deposit_state.disputed = true;
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ pub struct RegisterCollection<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    #[account(mut, seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
     pub global_pool: Account<'info, GlobalPool>,
 
     #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
@@ -20,19 +20,35 @@ pub struct RegisterCollection<'info> {
         space = CollectionPool::DATA_SIZE
     )]
     pub collection_pool: Account<'info, CollectionPool>,
+
+    // Registry page receiving the next collection
+    #[account(
+        init_if_needed,
+        seeds = [
+            COLLECTION_REGISTRY_SEED.as_ref(),
+            CollectionRegistry::page_of(global_pool.collection_count).to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        space = CollectionRegistry::DATA_SIZE
+    )]
+    pub collection_registry: Box<Account<'info, CollectionRegistry>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl RegisterCollection<'_> {
     pub fn process_instruction(ctx: &mut Context<Self>, collection: Pubkey) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
         let collection_pool = &mut ctx.accounts.collection_pool;
+        let collection_registry = &mut ctx.accounts.collection_registry;
+
+        validate_admin(global_pool, &ctx.accounts.admin_pool, ctx.accounts.admin.key)?;
 
-        validate_admin(
-            &ctx.accounts.global_pool,
-            &ctx.accounts.admin_pool,
-            ctx.accounts.admin.key
-        )?;
+        // First registration appends the collection to the registry
+        if collection_pool.address.eq(&Pubkey::default()) {
+            collection_pool.registry_index = collection_registry.append(global_pool, collection)?;
+        }
 
         collection_pool.address = collection;
         collection_pool.allowed = true;
@@ -0,0 +1,29 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct RegisterDepositDelegate<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [DEPOSIT_DELEGATE_SEED.as_ref(), beneficiary.key().as_ref(), delegate.as_ref()],
+        bump,
+        payer = beneficiary,
+        space = DepositDelegate::DATA_SIZE
+    )]
+    pub deposit_delegate: Account<'info, DepositDelegate>,
+    pub system_program: Program<'info, System>,
+}
+
+impl RegisterDepositDelegate<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, delegate: Pubkey) -> Result<()> {
+        let deposit_delegate = &mut ctx.accounts.deposit_delegate;
+
+        deposit_delegate.beneficiary = ctx.accounts.beneficiary.key();
+        deposit_delegate.delegate = delegate;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, collection: Pubkey)]
+pub struct RegisterMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    // Collection pool the mint is deposited under, must be registered
+    #[account(seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()], bump)]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [MINT_ALLOWLIST_SEED.as_ref(), mint.as_ref()],
+        bump,
+        payer = admin,
+        space = MintAllowlist::DATA_SIZE
+    )]
+    pub mint_allowlist: Account<'info, MintAllowlist>,
+    pub system_program: Program<'info, System>,
+}
+
+impl RegisterMint<'_> {
+    pub fn process_instruction(
+        ctx: &mut Context<Self>,
+        mint: Pubkey,
+        collection: Pubkey
+    ) -> Result<()> {
+        let mint_allowlist = &mut ctx.accounts.mint_allowlist;
+
+        validate_admin(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.admin_pool,
+            ctx.accounts.admin.key
+        )?;
+
+        mint_allowlist.mint = mint;
+        mint_allowlist.collection = collection;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_spl::token::Mint;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = deposit_state.owner == owner.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+}
+
+impl RequestWithdrawal<'_> {
+    pub fn process_instruction(ctx: Context<RequestWithdrawal>) -> Result<()> {
+        let deposit_state = &mut ctx.accounts.deposit_state;
+
+        // Validate if deposit exist
+        require!(deposit_state.status != 0, ShipmentError::NotDeposited);
+
+        require!(
+            deposit_state.withdrawal_requested_at == 0,
+            ShipmentError::WithdrawalAlreadyRequested
+        );
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        deposit_state.withdrawal_requested_at = timestamp;
+
+        msg!("withdrawal requested: {}", timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer },
+    token_2022::Token2022,
+};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = treasury
+    )]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_pool.address == user.key() @ ShipmentError::InvalidOwner,
+    )]
+    pub user_pool: Account<'info, UserPool>,
+
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = deposit_state.owner == user.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+
+    // Web2 user index page listing the deposit
+    #[account(
+        mut,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&deposit_state.user).as_ref(),
+            deposit_state.index_page.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = dispute.owner == user.key() @ ShipmentError::InvalidOwner,
+        close = user
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    // Receives deposit and pool ATA rent when finalized, chosen by the collection's rent destination
+    #[account(mut)]
+    pub rent_recipient: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = global_pool,
+    )]
+    pub dest_token_account: Box<Account<'info, TokenAccount>>,
+
+    //  Owner or treasury token account, not needed to finalize
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub destination_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Redemption accounts, only needed to finalize a deposit of a redeeming collection
+    #[account(mut)]
+    pub redemption_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// CHECK: per-collection redemption mint authority
+    #[account(
+        seeds = [REDEMPTION_AUTHORITY_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub redemption_authority: Option<UncheckedAccount<'info>>,
+
+    // Stock of redeemed tokens, used when the authority can not mint
+    #[account(
+        mut,
+        token::mint = redemption_mint,
+        token::authority = redemption_authority,
+    )]
+    pub redemption_source: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: owner's redeemed token associated account, created if missing
+    #[account(mut)]
+    pub redemption_token_account: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// CHECK: receipt mint PDA, created by the program on first deposit of the nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ResolveDispute<'_> {
+    pub fn process_instruction(
+        ctx: Context<ResolveDispute>,
+        outcome: DisputeOutcome,
+        resolution_hash: [u8; 32]
+    ) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let user_pool = &mut ctx.accounts.user_pool;
+        let deposit_state = &mut ctx.accounts.deposit_state;
+        let collection_pool = &mut ctx.accounts.collection_pool;
+        let dispute = &mut ctx.accounts.dispute;
+
+        validate_admin(global_pool, &ctx.accounts.admin_pool, ctx.accounts.admin.key)?;
+
+        // Validate if deposit exist
+        require!(deposit_state.status != 0, ShipmentError::NotDeposited);
+
+        require!(deposit_state.disputed, ShipmentError::NotDisputed);
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+        let signer = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+
+        match outcome {
+            DisputeOutcome::ReturnToOwner | DisputeOutcome::SendToTreasury => {
+                let recipient = if outcome == DisputeOutcome::ReturnToOwner {
+                    ctx.accounts.user.key()
+                } else {
+                    ctx.accounts.treasury.key()
+                };
+                let destination = ctx.accounts.destination_token_account
+                    .as_ref()
+                    .ok_or(ShipmentError::InvalidDestination)?;
+                require!(destination.owner.eq(&recipient), ShipmentError::InvalidDestination);
+
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.dest_token_account.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: global_pool.to_account_info(),
+                };
+                token::transfer(
+                    CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+                    1
+                )?;
+            }
+            DisputeOutcome::Finalize => {
+                let rent_recipient = ctx.accounts.rent_recipient
+                    .as_ref()
+                    .ok_or(ShipmentError::InvalidRentRecipient)?;
+                validate_rent_recipient(
+                    collection_pool,
+                    deposit_state,
+                    ctx.accounts.treasury.key,
+                    ctx.accounts.admin.key,
+                    rent_recipient.key
+                )?;
+
+                // Burn deposit NFT
+                let cpi_accounts = Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.dest_token_account.to_account_info(),
+                    authority: global_pool.to_account_info(),
+                };
+                token::burn(
+                    CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+                    1
+                )?;
+
+                // Close ATA
+                let cpi_accounts = CloseAccount {
+                    account: ctx.accounts.dest_token_account.to_account_info(),
+                    destination: rent_recipient.to_account_info(),
+                    authority: global_pool.to_account_info(),
+                };
+                token::close_account(
+                    CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer)
+                )?;
+            }
+        }
+
+        CustodyRelease {
+            global_pool,
+            global_bump: ctx.bumps.global_pool,
+            user_pool,
+            collection_pool,
+            deposit_state,
+            web2_user_index: &mut ctx.accounts.web2_user_index,
+            receipt_mint: &ctx.accounts.receipt_mint,
+            receipt_token_account: &ctx.accounts.receipt_token_account,
+            token_2022_program: &ctx.accounts.token_2022_program,
+        }.process()?;
+        if outcome != DisputeOutcome::Finalize {
+            deposit_state.reset();
+        }
+
+        emit!(DisputeResolved {
+            mint: dispute.mint,
+            owner: dispute.owner,
+            opened: dispute.opened,
+            resolved: Clock::get()?.unix_timestamp,
+            evidence_hash: dispute.evidence_hash,
+            resolution_hash,
+            outcome,
+        });
+
+        // Finalized deposit is redeemed and closed like in finalize_deposit
+        if outcome == DisputeOutcome::Finalize {
+            let accounts = &ctx.accounts;
+            RedemptionIssue {
+                collection_pool: &accounts.collection_pool,
+                collection: accounts.deposit_state.collection,
+                payer: accounts.admin.to_account_info(),
+                owner: accounts.user.to_account_info(),
+                redemption_mint: &accounts.redemption_mint,
+                redemption_authority: &accounts.redemption_authority,
+                redemption_authority_bump: ctx.bumps.redemption_authority,
+                redemption_source: &accounts.redemption_source,
+                redemption_token_account: &accounts.redemption_token_account,
+                associated_token_program: &accounts.associated_token_program,
+                token_program: &accounts.token_program,
+                system_program: &accounts.system_program,
+            }.process()?;
+
+            let rent_recipient = accounts.rent_recipient
+                .as_ref()
+                .ok_or(ShipmentError::InvalidRentRecipient)?;
+            accounts.deposit_state.close(rent_recipient.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
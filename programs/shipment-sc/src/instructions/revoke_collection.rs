@@ -13,28 +13,23 @@ pub struct RevokeCollection<'info> {
     pub admin_pool: Account<'info, UserPool>,
 
     #[account(
-        init_if_needed,
+        mut,
         seeds = [COLLECTION_POOL_SEED.as_ref(), collection.as_ref()],
-        bump,
-        payer = admin,
-        space = CollectionPool::DATA_SIZE
+        bump
     )]
     pub collection_pool: Account<'info, CollectionPool>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 impl RevokeCollection<'_> {
-    pub fn process_instruction(ctx: &mut Context<Self>, collection: Pubkey) -> Result<()> {
+    pub fn process_instruction(ctx: &mut Context<Self>, _collection: Pubkey) -> Result<()> {
         let collection_pool = &mut ctx.accounts.collection_pool;
 
         validate_admin(
             &ctx.accounts.global_pool,
             &ctx.accounts.admin_pool,
-            &ctx.accounts.admin.key
+            ctx.accounts.admin.key
         )?;
 
-        collection_pool.address = collection;
         collection_pool.allowed = false;
 
         Ok(())
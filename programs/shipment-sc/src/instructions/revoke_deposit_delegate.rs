@@ -0,0 +1,22 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct RevokeDepositDelegate<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_DELEGATE_SEED.as_ref(), beneficiary.key().as_ref(), delegate.as_ref()],
+        bump,
+        close = beneficiary,
+    )]
+    pub deposit_delegate: Account<'info, DepositDelegate>,
+}
+
+impl RevokeDepositDelegate<'_> {
+    pub fn process_instruction(_ctx: &mut Context<Self>, _delegate: Pubkey) -> Result<()> {
+        Ok(())
+    }
+}
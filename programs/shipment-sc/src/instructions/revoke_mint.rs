@@ -0,0 +1,34 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct RevokeMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    #[account(seeds = [USER_POOL_SEED.as_ref(), admin.key().as_ref()], bump)]
+    pub admin_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [MINT_ALLOWLIST_SEED.as_ref(), mint.as_ref()],
+        bump,
+        close = admin,
+    )]
+    pub mint_allowlist: Account<'info, MintAllowlist>,
+}
+
+impl RevokeMint<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, _mint: Pubkey) -> Result<()> {
+        validate_admin(
+            &ctx.accounts.global_pool,
+            &ctx.accounts.admin_pool,
+            ctx.accounts.admin.key
+        )?;
+
+        Ok(())
+    }
+}
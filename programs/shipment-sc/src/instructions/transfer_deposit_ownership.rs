@@ -0,0 +1,223 @@
+use crate::*;
+use anchor_spl::{ associated_token::AssociatedToken, token::Mint, token_2022::Token2022 };
+
+#[derive(Accounts)]
+#[instruction(new_user: String, new_index_page: u64)]
+pub struct TransferDepositOwnership<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub new_owner: SystemAccount<'info>,
+
+    // Admin co-signer, required when the transfer policy asks for it
+    pub admin: Option<Signer<'info>>,
+
+    #[account(seeds = [GLOBAL_AUTHORITY_SEED.as_ref()], bump)]
+    pub global_pool: Account<'info, GlobalPool>,
+
+    // Checked against the admin key by validate_admin
+    pub admin_pool: Option<Account<'info, UserPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = owner_pool.address == owner.key() @ ShipmentError::InvalidOwner,
+    )]
+    pub owner_pool: Account<'info, UserPool>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_pool: Account<'info, UserPool>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = deposit_state.owner == owner.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
+    )]
+    pub deposit_state: Account<'info, NftDeposit>,
+
+    // Web2 user index page listing the deposit
+    #[account(
+        mut,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&deposit_state.user).as_ref(),
+            deposit_state.index_page.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    // Index page of the new web2 user receiving the deposit
+    #[account(
+        init_if_needed,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&new_user).as_ref(),
+            new_index_page.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = Web2UserIndex::DATA_SIZE
+    )]
+    pub new_web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    // Previous index page of the new web2 user, needed to open a new page
+    #[account(
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&new_user).as_ref(),
+            new_index_page.saturating_sub(1).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub previous_web2_user_index: Option<Box<Account<'info, Web2UserIndex>>>,
+
+    // Collection quota overrides the global one for the new owner
+    #[account(
+        seeds = [COLLECTION_POOL_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    /// CHECK: receipt mint PDA of the deposited nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: current owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: new owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub new_receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl TransferDepositOwnership<'_> {
+    pub fn process_instruction(
+        ctx: Context<TransferDepositOwnership>,
+        new_user: String,
+        new_index_page: u64
+    ) -> Result<()> {
+        let global_pool = &ctx.accounts.global_pool;
+        let owner_pool = &mut ctx.accounts.owner_pool;
+        let new_owner_pool = &mut ctx.accounts.new_owner_pool;
+        let deposit_state = &mut ctx.accounts.deposit_state;
+
+        // Validate if deposit exist
+        require!(deposit_state.status != 0, ShipmentError::NotDeposited);
+
+        // Claim must move to another wallet with an initialized user pool
+        require!(
+            !ctx.accounts.new_owner.key().eq(&ctx.accounts.owner.key()) &&
+                new_owner_pool.address.eq(&ctx.accounts.new_owner.key()),
+            ShipmentError::InvalidNewOwner
+        );
+
+        // New web2 user must be linked to the new owner wallet
+        validate_web2_user(global_pool, new_owner_pool, &new_user)?;
+
+        // Claim counts toward the new owner's active deposits like a deposit would
+        let quota = ctx.accounts.collection_pool.deposit_quota.or(global_pool.deposit_quota);
+        validate_concurrent_quota(&quota, new_owner_pool)?;
+
+        // Disputed deposit is frozen until admin resolves it
+        require!(!deposit_state.disputed, ShipmentError::DepositDisputed);
+
+        if global_pool.transfer_cosign_required {
+            match (&ctx.accounts.admin, &ctx.accounts.admin_pool) {
+                (Some(admin), Some(admin_pool)) => {
+                    validate_admin(global_pool, admin_pool, admin.key)?;
+                }
+                _ => {
+                    return Err(error!(ShipmentError::InvalidAdmin));
+                }
+            }
+        }
+
+        // Receipt follows the claim, burn it from the current owner and mint to the new one
+        if deposit_state.receipt {
+            let global_info = global_pool.to_account_info();
+            let receipt = Receipt::from_accounts(
+                &ctx.accounts.receipt_mint,
+                &ctx.accounts.receipt_token_account,
+                &ctx.accounts.token_2022_program,
+                global_info.clone(),
+                ctx.bumps.global_pool
+            );
+            let new_receipt = Receipt::from_accounts(
+                &ctx.accounts.receipt_mint,
+                &ctx.accounts.new_receipt_token_account,
+                &ctx.accounts.token_2022_program,
+                global_info,
+                ctx.bumps.global_pool
+            );
+
+            match (receipt, new_receipt) {
+                (Some(receipt), Some(new_receipt)) => {
+                    receipt.burn(&deposit_state.owner)?;
+                    new_receipt.issue(
+                        &ctx.accounts.token_mint.key(),
+                        &ctx.accounts.new_owner.to_account_info(),
+                        &ctx.accounts.owner.to_account_info(),
+                        &ctx.accounts.associated_token_program.to_account_info(),
+                        &ctx.accounts.system_program.to_account_info()
+                    )?;
+                }
+                _ => {
+                    // Holder already burned the receipt, the claim moves without one
+                    require!(
+                        outstanding_receipts(&ctx.accounts.receipt_mint)? == Some(0),
+                        ShipmentError::ReceiptRequired
+                    );
+                    deposit_state.receipt = false;
+                }
+            }
+        }
+
+        msg!("deposit owner: {} -> {}", deposit_state.owner, ctx.accounts.new_owner.key());
+
+        deposit_state.owner = ctx.accounts.new_owner.key();
+        deposit_state.user = new_user;
+        // Move the deposit to the new web2 user index, unless it stays in the same page
+        if !ctx.accounts.web2_user_index.key().eq(&ctx.accounts.new_web2_user_index.key()) {
+            let mint = ctx.accounts.token_mint.key();
+            ctx.accounts.web2_user_index.remove(&mint)?;
+            validate_index_page(
+                &ctx.accounts.new_web2_user_index,
+                &ctx.accounts.previous_web2_user_index,
+                new_index_page
+            )?;
+            ctx.accounts.new_web2_user_index.page = new_index_page;
+            ctx.accounts.new_web2_user_index.insert(mint)?;
+            deposit_state.index_page = new_index_page;
+        }
+        // Pending withdrawal request belonged to the previous owner
+        deposit_state.withdrawal_requested_at = 0;
+
+        owner_pool.deposit_count = owner_pool.deposit_count
+            .checked_sub(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+        new_owner_pool.deposit_count = new_owner_pool.deposit_count
+            .checked_add(1)
+            .ok_or(ShipmentError::MathOverflow)?;
+
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ impl TransferSuperAdmin<'_> {
         let global_pool = &mut ctx.accounts.global_pool;
 
         // Validate super admin
-        require!(global_pool.super_admin.eq(&ctx.accounts.admin.key), ShipmentError::InvalidAdmin);
+        require!(global_pool.super_admin.eq(ctx.accounts.admin.key), ShipmentError::InvalidAdmin);
 
         global_pool.super_admin = new_admin;
 
@@ -21,6 +21,8 @@ pub struct UpdateDeposit<'info> {
         mut,
         seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
         bump,
+        constraint = deposit_state.owner == user.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
     )]
     pub deposit_state: Account<'info, NftDeposit>,
 }
@@ -29,13 +31,15 @@ impl UpdateDeposit<'_> {
     pub fn process_instruction(
         ctx: Context<UpdateDeposit>,
         status: Option<u8>,
-        locked: Option<bool>
+        locked: Option<bool>,
+        lock_reason: Option<u8>,
+        locked_until: Option<i64>
     ) -> Result<()> {
         let global_pool = &mut ctx.accounts.global_pool;
         let updater_pool = &mut ctx.accounts.updater_pool;
         let deposit_state = &mut ctx.accounts.deposit_state;
 
-        validate_updater(&global_pool, &updater_pool, &ctx.accounts.updater.key())?;
+        validate_updater(global_pool, updater_pool, &ctx.accounts.updater.key())?;
 
         // Check user pool owner matched with signed user
         require!(
@@ -45,8 +49,28 @@ impl UpdateDeposit<'_> {
 
         require!(deposit_state.status != 0, ShipmentError::NotDeposited);
 
+        // Zero status marks the nft as out of custody, only releases may set it
+        require!(status != Some(0), ShipmentError::InvalidDepositStatus);
+
         deposit_state.status = status.unwrap_or(deposit_state.status);
         deposit_state.locked = locked.unwrap_or(deposit_state.locked);
+        deposit_state.lock_reason = lock_reason.unwrap_or(deposit_state.lock_reason);
+
+        if let Some(locked_until) = locked_until {
+            // Expiry must be in the future, 0 keeps the lock until released
+            let timestamp = Clock::get()?.unix_timestamp;
+            require!(
+                locked_until == 0 || locked_until > timestamp,
+                ShipmentError::InvalidLockExpiry
+            );
+            deposit_state.locked_until = locked_until;
+        }
+
+        // Lock context is meaningless once released
+        if !deposit_state.locked {
+            deposit_state.lock_reason = 0;
+            deposit_state.locked_until = 0;
+        }
 
         Ok(())
     }
@@ -2,6 +2,7 @@ use crate::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{ self, Mint, Token, TokenAccount, Transfer },
+    token_2022::Token2022,
 };
 
 #[derive(Accounts)]
@@ -24,7 +25,8 @@ pub struct WithdrawOwner<'info> {
     #[account(
         mut,
         seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
-        bump
+        bump,
+        constraint = user_pool.address == user.key() @ ShipmentError::InvalidOwner,
     )]
     pub user_pool: Account<'info, UserPool>,
 
@@ -34,9 +36,30 @@ pub struct WithdrawOwner<'info> {
         mut,
         seeds = [NFT_DEPOSIT_SEED.as_ref(), token_mint.key().as_ref()],
         bump,
+        constraint = deposit_state.owner == user.key() @ ShipmentError::InvalidOwner,
+        constraint = deposit_state.mint == token_mint.key() @ ShipmentError::InvalidNFTAddress,
     )]
     pub deposit_state: Account<'info, NftDeposit>,
 
+    // Web2 user index page listing the deposit
+    #[account(
+        mut,
+        seeds = [
+            WEB2_USER_INDEX_SEED.as_ref(),
+            Web2UserIndex::user_seed(&deposit_state.user).as_ref(),
+            deposit_state.index_page.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub web2_user_index: Box<Account<'info, Web2UserIndex>>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_POOL_SEED.as_ref(), deposit_state.collection.as_ref()],
+        bump
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
     #[account(
         mut,
         associated_token::mint = token_mint,
@@ -52,7 +75,20 @@ pub struct WithdrawOwner<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    /// CHECK: receipt mint PDA, created by the program on first deposit of the nft
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: owner's receipt associated token account, validated by the program
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -63,20 +99,20 @@ impl WithdrawOwner<'_> {
         let payer_pool = &mut ctx.accounts.payer_pool;
         let user_pool = &mut ctx.accounts.user_pool;
         let deposit_state = &mut ctx.accounts.deposit_state;
+        let collection_pool = &mut ctx.accounts.collection_pool;
 
         if !ctx.accounts.payer.key().eq(&ctx.accounts.user.key()) {
-            validate_admin(&global_pool, &payer_pool, &ctx.accounts.payer.key())?;
+            validate_admin(global_pool, payer_pool, &ctx.accounts.payer.key())?;
         }
 
-        require!(user_pool.address.eq(&ctx.accounts.user.key()), ShipmentError::InvalidOwner);
-        require!(deposit_state.owner.eq(&ctx.accounts.user.key()), ShipmentError::InvalidOwner);
-        require!(
-            deposit_state.mint.eq(&ctx.accounts.token_mint.key()),
-            ShipmentError::InvalidNFTAddress
-        );
+        // Disputed deposit is frozen until admin resolves it
+        require!(!deposit_state.disputed, ShipmentError::DepositDisputed);
 
-        // Validate if withdraw enabled
-        require!(deposit_state.locked == false, ShipmentError::DisabledWithdrawal);
+        // Validate if withdraw enabled, unanswered withdrawal request lifts the lock after grace period
+        let timestamp = Clock::get()?.unix_timestamp;
+        if !deposit_state.withdrawal_overdue(global_pool.withdrawal_grace_period, timestamp) {
+            validate_unlocked(deposit_state, timestamp)?;
+        }
 
         // Validate if deposit exist
         require!(deposit_state.status != 0, ShipmentError::NotDeposited);
@@ -94,9 +130,18 @@ impl WithdrawOwner<'_> {
             1
         )?;
 
+        CustodyRelease {
+            global_pool,
+            global_bump: ctx.bumps.global_pool,
+            user_pool,
+            collection_pool,
+            deposit_state,
+            web2_user_index: &mut ctx.accounts.web2_user_index,
+            receipt_mint: &ctx.accounts.receipt_mint,
+            receipt_token_account: &ctx.accounts.receipt_token_account,
+            token_2022_program: &ctx.accounts.token_2022_program,
+        }.process()?;
         deposit_state.reset();
-        user_pool.deposit_count -= 1;
-        global_pool.total_deposit_count -= 1;
 
         Ok(())
     }
@@ -0,0 +1,104 @@
+use crate::*;
+use anchor_lang::{ system_program::{ self, Transfer }, Discriminator };
+
+// Layouts of the accounts created by the deployed program, kept to migrate live
+// accounts in place after an upgrade. A legacy account has the discriminator of
+// its current type and is told apart by its size.
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyGlobalPool {
+    pub super_admin: Pubkey,
+    pub treasury: Pubkey,
+    pub total_deposit_count: u64,
+}
+
+impl LegacyGlobalPool {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<LegacyGlobalPool>();
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyCollectionPool {
+    pub address: Pubkey,
+    pub allowed: bool,
+}
+
+impl LegacyCollectionPool {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<LegacyCollectionPool>();
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserPool {
+    pub address: Pubkey,
+    pub deposit_count: u64,
+    pub admin: bool,
+    pub updater: bool,
+}
+
+impl LegacyUserPool {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<LegacyUserPool>();
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyNftDeposit {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub created: i64,
+    pub status: u8,
+    pub locked: bool,
+    pub user: String,
+}
+
+impl LegacyNftDeposit {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<LegacyNftDeposit>();
+}
+
+// Decodes a program account of type C still in its legacy layout L, None once it is migrated
+pub fn legacy_account<L: AnchorDeserialize, C: Discriminator>(
+    account: &AccountInfo,
+    legacy_size: usize,
+    current_size: usize
+) -> Result<Option<L>> {
+    let data = account.try_borrow_data()?;
+    require!(
+        account.owner.eq(&crate::ID) && data.len() >= 8 && data[..8] == C::DISCRIMINATOR,
+        ShipmentError::InvalidLegacyAccount
+    );
+    if data.len() == current_size {
+        return Ok(None);
+    }
+    require!(data.len() == legacy_size, ShipmentError::InvalidLegacyAccount);
+    Ok(Some(L::deserialize(&mut &data[8..])?))
+}
+
+// Rewrites an account in its current layout, the payer tops up the rent of the grown account
+pub fn write_migrated<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    migrated: &T,
+    space: usize
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if rent > 0 {
+        let cpi_accounts = Transfer {
+            from: payer.clone(),
+            to: account.clone(),
+        };
+        system_program::transfer(CpiContext::new(system_program.clone(), cpi_accounts), rent)?;
+    }
+    account.realloc(space, true)?;
+    migrated.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+// Closes a legacy account that has nothing left to migrate
+pub fn close_legacy<'info>(account: &AccountInfo<'info>, recipient: &AccountInfo<'info>) -> Result<()> {
+    let lamports = recipient.lamports()
+        .checked_add(account.lamports())
+        .ok_or(ShipmentError::MathOverflow)?;
+    **recipient.lamports.borrow_mut() = lamports;
+    **account.lamports.borrow_mut() = 0;
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
@@ -1,13 +1,23 @@
 use anchor_lang::{prelude::*, AnchorDeserialize};
 
 pub mod constant;
+pub mod custody;
 pub mod error;
+pub mod event;
 pub mod instructions;
+pub mod legacy;
+pub mod receipt;
+pub mod redemption;
 pub mod state;
 pub mod validations;
 use constant::*;
+use custody::*;
 use error::*;
+use event::*;
 use instructions::*;
+use legacy::*;
+use receipt::*;
+use redemption::*;
 use state::*;
 use validations::*;
 
@@ -38,11 +48,65 @@ pub mod shipment_sc {
         ChangeTreasury::process_instruction(&mut ctx, new_treasury)
     }
 
+    //  Admin can set how long a withdrawal request waits before owner can force withdraw
+    pub fn change_grace_period(mut ctx: Context<ChangeGracePeriod>, grace_period: i64) -> Result<()> {
+        ChangeGracePeriod::process_instruction(&mut ctx, grace_period)
+    }
+
+    //  Admin can require delivery proof on finalize and set the courier oracle key
+    pub fn change_delivery_proof(
+        mut ctx: Context<ChangeDeliveryProof>,
+        required: bool,
+        courier_oracle: Pubkey,
+    ) -> Result<()> {
+        ChangeDeliveryProof::process_instruction(&mut ctx, required, courier_oracle)
+    }
+
+    //  Admin can require admin co-sign on deposit ownership transfers
+    pub fn change_transfer_policy(
+        mut ctx: Context<ChangeTransferPolicy>,
+        cosign_required: bool,
+    ) -> Result<()> {
+        ChangeTransferPolicy::process_instruction(&mut ctx, cosign_required)
+    }
+
+    //  Admin can set the token minted or transferred to owners when a collection deposit is finalized
+    pub fn change_redemption_mint(
+        mut ctx: Context<ChangeRedemptionMint>,
+        collection: Pubkey,
+    ) -> Result<()> {
+        ChangeRedemptionMint::process_instruction(&mut ctx, collection)
+    }
+
+    //  Admin can choose who receives rent when a collection deposit is finalized
+    pub fn change_rent_destination(
+        mut ctx: Context<ChangeRentDestination>,
+        collection: Pubkey,
+        rent_destination: RentDestination,
+    ) -> Result<()> {
+        ChangeRentDestination::process_instruction(&mut ctx, collection, rent_destination)
+    }
+
+    //  Admin can set the display name and uri of a collection
+    pub fn change_collection_metadata(
+        mut ctx: Context<ChangeCollectionMetadata>,
+        collection: Pubkey,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        ChangeCollectionMetadata::process_instruction(&mut ctx, collection, name, uri)
+    }
+
     //  Anyone can initialize user pool
     pub fn init_user(mut ctx: Context<InitUser>) -> Result<()> {
         InitUser::process_instruction(&mut ctx)
     }
 
+    //  User or admin can close a user pool without deposits or roles, rent goes to the recipient
+    pub fn close_user(mut ctx: Context<CloseUser>) -> Result<()> {
+        CloseUser::process_instruction(&mut ctx)
+    }
+
     //  Admin can set user role
     pub fn change_role(
         mut ctx: Context<ChangeRole>,
@@ -65,11 +129,154 @@ pub mod shipment_sc {
         RevokeCollection::process_instruction(&mut ctx, collection)
     }
 
+    //  Admin can close collection without active deposits, rent is returned to admin
+    pub fn close_collection(mut ctx: Context<CloseCollection>, collection: Pubkey) -> Result<()> {
+        CloseCollection::process_instruction(&mut ctx, collection)
+    }
+
+    //  Admin can allow a single mint to be deposited under a collection
+    pub fn register_mint(
+        mut ctx: Context<RegisterMint>,
+        mint: Pubkey,
+        collection: Pubkey,
+    ) -> Result<()> {
+        RegisterMint::process_instruction(&mut ctx, mint, collection)
+    }
+
+    //  Admin can revoke an allowed mint, rent is returned to admin
+    pub fn revoke_mint(mut ctx: Context<RevokeMint>, mint: Pubkey) -> Result<()> {
+        RevokeMint::process_instruction(&mut ctx, mint)
+    }
+
+    //  Admin can set merkle root of mints allowed to be deposited under a collection
+    pub fn change_mint_merkle_root(
+        mut ctx: Context<ChangeMintMerkleRoot>,
+        collection: Pubkey,
+        mint_merkle_root: [u8; 32],
+    ) -> Result<()> {
+        ChangeMintMerkleRoot::process_instruction(&mut ctx, collection, mint_merkle_root)
+    }
+
+    //  Admin can set merkle root of wallets approved to deposit, zero opens deposits to everyone
+    pub fn change_depositor_merkle_root(
+        mut ctx: Context<ChangeDepositorMerkleRoot>,
+        depositor_merkle_root: [u8; 32],
+    ) -> Result<()> {
+        ChangeDepositorMerkleRoot::process_instruction(&mut ctx, depositor_merkle_root)
+    }
+
+    //  Admin can set merkle root of wallets approved to deposit under a collection
+    pub fn change_collection_depositor_merkle_root(
+        mut ctx: Context<ChangeCollectionDepositorMerkleRoot>,
+        collection: Pubkey,
+        depositor_merkle_root: [u8; 32],
+    ) -> Result<()> {
+        ChangeCollectionDepositorMerkleRoot::process_instruction(&mut ctx, collection, depositor_merkle_root)
+    }
+
+    //  Admin can set per-user deposit limits, zero means unlimited
+    pub fn change_deposit_quota(
+        mut ctx: Context<ChangeDepositQuota>,
+        deposit_quota: DepositQuota,
+    ) -> Result<()> {
+        ChangeDepositQuota::process_instruction(&mut ctx, deposit_quota)
+    }
+
+    //  Admin can override per-user deposit limits for a collection
+    pub fn change_collection_deposit_quota(
+        mut ctx: Context<ChangeCollectionDepositQuota>,
+        collection: Pubkey,
+        deposit_quota: DepositQuota,
+    ) -> Result<()> {
+        ChangeCollectionDepositQuota::process_instruction(&mut ctx, collection, deposit_quota)
+    }
+
+    //  Admin can set the key attesting web2 identity links, default stops checking deposit uuids
+    pub fn change_identity_authority(
+        mut ctx: Context<ChangeIdentityAuthority>,
+        identity_authority: Pubkey,
+    ) -> Result<()> {
+        ChangeIdentityAuthority::process_instruction(&mut ctx, identity_authority)
+    }
+
     /**
-     * User can deposit NFT
+     * User can link its wallet to a web2 user attested by the identity authority
+     * the ed25519 verification of the attestation must precede this instruction
+     * the user pool is created with no roles if the wallet has none yet
      */
-    pub fn deposit_nft(ctx: Context<DepositNft>, collection: Pubkey, user: String) -> Result<()> {
-        DepositNft::process_instruction(ctx, collection, user)
+    pub fn link_web2_identity(mut ctx: Context<LinkWeb2Identity>, uuid: String) -> Result<()> {
+        LinkWeb2Identity::process_instruction(&mut ctx, uuid)
+    }
+
+    /**
+     * User can allow a delegate to deposit NFTs on its behalf
+     */
+    pub fn register_deposit_delegate(
+        mut ctx: Context<RegisterDepositDelegate>,
+        delegate: Pubkey,
+    ) -> Result<()> {
+        RegisterDepositDelegate::process_instruction(&mut ctx, delegate)
+    }
+
+    /**
+     * User can revoke a deposit delegate, rent is returned to the user
+     */
+    pub fn revoke_deposit_delegate(
+        mut ctx: Context<RevokeDepositDelegate>,
+        delegate: Pubkey,
+    ) -> Result<()> {
+        RevokeDepositDelegate::process_instruction(&mut ctx, delegate)
+    }
+
+    /**
+     * User can deposit NFT of an allowed collection
+     * or an allowlisted mint, by allowlist account or merkle proof
+     * the user pool is created with no roles on the first deposit
+     * when a depositor root is set, the user must prove it is an approved wallet
+     * when an identity authority is set, `user` must be the web2 user linked to the wallet
+     * the deposit is listed in page `index_page` of its web2 user index
+     */
+    pub fn deposit_nft(
+        ctx: Context<DepositNft>,
+        collection: Pubkey,
+        user: String,
+        mint_proof: Option<Vec<[u8; 32]>>,
+        depositor_proof: Option<Vec<[u8; 32]>>,
+        index_page: u64,
+    ) -> Result<()> {
+        DepositNft::process_instruction(
+            ctx,
+            collection,
+            user,
+            mint_proof,
+            depositor_proof,
+            index_page
+        )
+    }
+
+    /**
+     * Depositor can deposit NFT it holds on behalf of a beneficiary wallet
+     * the beneficiary must co-sign or have registered the depositor as its delegate
+     * the deposit is owned by the beneficiary, rent is paid by the depositor
+     * when a depositor root is set, the depositor must prove it is an approved wallet
+     * when an identity authority is set, `user` must be the web2 user linked to the beneficiary
+     */
+    pub fn deposit_nft_for(
+        ctx: Context<DepositNftFor>,
+        collection: Pubkey,
+        user: String,
+        mint_proof: Option<Vec<[u8; 32]>>,
+        depositor_proof: Option<Vec<[u8; 32]>>,
+        index_page: u64,
+    ) -> Result<()> {
+        DepositNftFor::process_instruction(
+            ctx,
+            collection,
+            user,
+            mint_proof,
+            depositor_proof,
+            index_page
+        )
     }
 
     /**
@@ -79,8 +286,10 @@ pub mod shipment_sc {
         ctx: Context<UpdateDeposit>,
         status: Option<u8>,
         locked: Option<bool>,
+        lock_reason: Option<u8>,
+        locked_until: Option<i64>,
     ) -> Result<()> {
-        UpdateDeposit::process_instruction(ctx, status, locked)
+        UpdateDeposit::process_instruction(ctx, status, locked, lock_reason, locked_until)
     }
 
     /**
@@ -90,6 +299,40 @@ pub mod shipment_sc {
         WithdrawOwner::process_instruction(ctx)
     }
 
+    /**
+     * Owner can transfer deposit claim to a new wallet and web2 user
+     * admin must co-sign when transfer policy requires it
+     * the deposit moves to page `new_index_page` of the new web2 user index
+     */
+    pub fn transfer_deposit_ownership(
+        ctx: Context<TransferDepositOwnership>,
+        new_user: String,
+        new_index_page: u64,
+    ) -> Result<()> {
+        TransferDepositOwnership::process_instruction(ctx, new_user, new_index_page)
+    }
+
+    /**
+     * Owner can request withdrawal of a locked nft
+     */
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>) -> Result<()> {
+        RequestWithdrawal::process_instruction(ctx)
+    }
+
+    /**
+     * Admin & updater can approve withdrawal request, nft is sent to owner
+     */
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        ApproveWithdrawal::process_instruction(ctx)
+    }
+
+    /**
+     * Admin & updater can deny withdrawal request
+     */
+    pub fn deny_withdrawal(ctx: Context<DenyWithdrawal>) -> Result<()> {
+        DenyWithdrawal::process_instruction(ctx)
+    }
+
     /**
      * Admin can withdraw nft to treasury
      */
@@ -97,10 +340,76 @@ pub mod shipment_sc {
         WithdrawTreasury::process_instruction(ctx)
     }
 
+    /**
+     * Owner can dispute a deposit, freezing withdrawal & finalize until resolved
+     */
+    pub fn open_dispute(ctx: Context<OpenDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+        OpenDispute::process_instruction(ctx, evidence_hash)
+    }
+
+    /**
+     * Admin can resolve a dispute by returning, sending to treasury or finalizing the nft
+     * the dispute account is closed to its owner, the resolution is emitted as an event
+     */
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        outcome: DisputeOutcome,
+        resolution_hash: [u8; 32],
+    ) -> Result<()> {
+        ResolveDispute::process_instruction(ctx, outcome, resolution_hash)
+    }
+
     /**
      * Admin & updater can finalize deposit
+     * with delivery proof mode, owner must co-sign or courier oracle attestation must precede
+     */
+    pub fn finalize_deposit(ctx: Context<FinalizeDeposit>, proof_hash: [u8; 32]) -> Result<()> {
+        FinalizeDeposit::process_instruction(ctx, proof_hash)
+    }
+
+    /**
+     * Super admin can migrate the global pool left in the legacy layout by an upgrade
+     * it runs before the other migrations, live deposits stay counted
+     */
+    pub fn migrate_global_pool(mut ctx: Context<MigrateGlobalPool>) -> Result<()> {
+        MigrateGlobalPool::process_instruction(&mut ctx)
+    }
+
+    /**
+     * Super admin can migrate a collection pool left in the legacy layout
+     * the collection is appended to the registry
+     */
+    pub fn migrate_collection_pool(
+        mut ctx: Context<MigrateCollectionPool>,
+        collection: Pubkey,
+    ) -> Result<()> {
+        MigrateCollectionPool::process_instruction(&mut ctx, collection)
+    }
+
+    /**
+     * Super admin can migrate a user pool left in the legacy layout, roles are kept
+     */
+    pub fn migrate_user_pool(mut ctx: Context<MigrateUserPool>) -> Result<()> {
+        MigrateUserPool::process_instruction(&mut ctx)
+    }
+
+    /**
+     * Super admin can migrate a live legacy deposit in place, its collection is checked
+     * against the nft metadata and it is listed in the web2 user index
+     */
+    pub fn migrate_nft_deposit(
+        mut ctx: Context<MigrateNftDeposit>,
+        collection: Pubkey,
+        user: String,
+        index_page: u64,
+    ) -> Result<()> {
+        MigrateNftDeposit::process_instruction(&mut ctx, collection, user, index_page)
+    }
+
+    /**
+     * Super admin can close a withdrawn legacy deposit, rent is returned to its owner
      */
-    pub fn finalize_deposit(ctx: Context<FinalizeDeposit>) -> Result<()> {
-        FinalizeDeposit::process_instruction(ctx)
+    pub fn close_legacy_deposit(mut ctx: Context<CloseLegacyDeposit>) -> Result<()> {
+        CloseLegacyDeposit::process_instruction(&mut ctx)
     }
 }
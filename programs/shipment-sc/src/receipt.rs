@@ -0,0 +1,219 @@
+use crate::*;
+use anchor_lang::system_program::{ self, CreateAccount };
+use anchor_spl::{
+    associated_token::{ self, get_associated_token_address_with_program_id, Create },
+    token_2022::{
+        self,
+        spl_token_2022::{
+            self,
+            extension::{ ExtensionType, StateWithExtensions },
+            state::{ Account as Account2022, Mint as Mint2022 },
+        },
+        Burn,
+        InitializeMint2,
+        MintTo,
+        Token2022,
+    },
+};
+use solana_program::program::invoke;
+
+// Non-transferable Token-2022 receipt proving custody of a deposited nft.
+// Global pool is mint authority and permanent delegate, so it can burn the
+// receipt from the owner's wallet when custody ends.
+pub struct Receipt<'info> {
+    pub mint: AccountInfo<'info>,
+    pub token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub global_pool: AccountInfo<'info>,
+    pub global_bump: u8,
+}
+
+impl<'info> Receipt<'info> {
+    // Receipt accounts are optional, all of them must be supplied to use the receipt
+    pub fn from_accounts(
+        mint: &Option<UncheckedAccount<'info>>,
+        token_account: &Option<UncheckedAccount<'info>>,
+        token_program: &Option<Program<'info, Token2022>>,
+        global_pool: AccountInfo<'info>,
+        global_bump: u8
+    ) -> Option<Self> {
+        match (mint, token_account, token_program) {
+            (Some(mint), Some(token_account), Some(token_program)) =>
+                Some(Self {
+                    mint: mint.to_account_info(),
+                    token_account: token_account.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                    global_pool,
+                    global_bump,
+                }),
+            _ => None,
+        }
+    }
+
+    fn validate_token_account(&self, owner: &Pubkey) -> Result<()> {
+        let expected = get_associated_token_address_with_program_id(
+            owner,
+            self.mint.key,
+            &token_2022::ID
+        );
+        require!(self.token_account.key.eq(&expected), ShipmentError::InvalidReceipt);
+
+        Ok(())
+    }
+
+    // Mint one receipt to the owner, creating the receipt mint on first deposit of the nft
+    pub fn issue(
+        &self,
+        nft_mint: &Pubkey,
+        owner: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        associated_token_program: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>
+    ) -> Result<()> {
+        self.validate_token_account(owner.key)?;
+
+        let global_seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[self.global_bump]];
+        let global_signer = &[&global_seeds[..]];
+
+        if self.mint.data_is_empty() {
+            let (_, bump) = Pubkey::find_program_address(
+                &[RECEIPT_SEED.as_bytes(), nft_mint.as_ref()],
+                &crate::ID
+            );
+            let receipt_seeds = &[RECEIPT_SEED.as_bytes(), nft_mint.as_ref(), &[bump]];
+            let space = ExtensionType::try_calculate_account_len::<Mint2022>(
+                &[ExtensionType::NonTransferable, ExtensionType::PermanentDelegate]
+            )?;
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    CreateAccount { from: payer.clone(), to: self.mint.clone() },
+                    &[&receipt_seeds[..]]
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                &token_2022::ID
+            )?;
+
+            invoke(
+                &spl_token_2022::instruction::initialize_non_transferable_mint(
+                    &token_2022::ID,
+                    self.mint.key
+                )?,
+                std::slice::from_ref(&self.mint)
+            )?;
+            invoke(
+                &spl_token_2022::instruction::initialize_permanent_delegate(
+                    &token_2022::ID,
+                    self.mint.key,
+                    self.global_pool.key
+                )?,
+                std::slice::from_ref(&self.mint)
+            )?;
+            token_2022::initialize_mint2(
+                CpiContext::new(
+                    self.token_program.clone(),
+                    InitializeMint2 { mint: self.mint.clone() }
+                ),
+                0,
+                self.global_pool.key,
+                None
+            )?;
+        }
+
+        associated_token::create_idempotent(
+            CpiContext::new(associated_token_program.clone(), Create {
+                payer: payer.clone(),
+                associated_token: self.token_account.clone(),
+                authority: owner.clone(),
+                mint: self.mint.clone(),
+                system_program: system_program.clone(),
+                token_program: self.token_program.clone(),
+            })
+        )?;
+
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                MintTo {
+                    mint: self.mint.clone(),
+                    to: self.token_account.clone(),
+                    authority: self.global_pool.clone(),
+                },
+                global_signer
+            ),
+            1
+        )
+    }
+
+    // Burn the owner's receipt with the global pool as permanent delegate,
+    // skipped when the holder already burned it or closed its account
+    pub fn burn(&self, owner: &Pubkey) -> Result<()> {
+        self.validate_token_account(owner)?;
+
+        if
+            self.token_account.data_is_empty() ||
+            !self.token_account.owner.eq(&token_2022::ID)
+        {
+            return Ok(());
+        }
+        let amount = StateWithExtensions::<Account2022>
+            ::unpack(&self.token_account.try_borrow_data()?)?.base.amount;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let global_seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[self.global_bump]];
+        let global_signer = &[&global_seeds[..]];
+
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                Burn {
+                    mint: self.mint.clone(),
+                    from: self.token_account.clone(),
+                    authority: self.global_pool.clone(),
+                },
+                global_signer
+            ),
+            1
+        )
+    }
+}
+
+// Receipts of the nft left unburned, zero once the receipt mint was never created
+pub fn outstanding_receipts(receipt_mint: &Option<UncheckedAccount>) -> Result<Option<u64>> {
+    let Some(receipt_mint) = receipt_mint else {
+        return Ok(None);
+    };
+    if receipt_mint.data_is_empty() {
+        return Ok(Some(0));
+    }
+    let supply = StateWithExtensions::<Mint2022>
+        ::unpack(&receipt_mint.try_borrow_data()?)?.base.supply;
+
+    Ok(Some(supply))
+}
+
+// Burn the receipt of a deposit leaving custody, receipt accounts are required if one was issued
+// unless the receipt mint shows the holder already burned it
+pub fn burn_deposit_receipt<'info>(
+    deposit_state: &NftDeposit,
+    receipt_mint: &Option<UncheckedAccount<'info>>,
+    receipt: Option<Receipt<'info>>
+) -> Result<()> {
+    if !deposit_state.receipt {
+        return Ok(());
+    }
+
+    match receipt {
+        Some(receipt) => receipt.burn(&deposit_state.owner),
+        None => {
+            require!(
+                outstanding_receipts(receipt_mint)? == Some(0),
+                ShipmentError::ReceiptRequired
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::{ self, AssociatedToken, Create },
+    token::{ self, Mint, MintTo, Token, TokenAccount, Transfer },
+};
+
+// Hands the owner its collection's redeemed token when a deposit is finalized,
+// shared by finalize and dispute resolution. Does nothing when the collection
+// has no redemption mint.
+pub struct RedemptionIssue<'a, 'info> {
+    pub collection_pool: &'a CollectionPool,
+    pub collection: Pubkey,
+    pub payer: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    pub redemption_mint: &'a Option<Box<Account<'info, Mint>>>,
+    pub redemption_authority: &'a Option<UncheckedAccount<'info>>,
+    pub redemption_authority_bump: u8,
+    pub redemption_source: &'a Option<Box<Account<'info, TokenAccount>>>,
+    pub redemption_token_account: &'a Option<UncheckedAccount<'info>>,
+    pub associated_token_program: &'a Option<Program<'info, AssociatedToken>>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl RedemptionIssue<'_, '_> {
+    pub fn process(self) -> Result<()> {
+        if self.collection_pool.redemption_mint == Pubkey::default() {
+            return Ok(());
+        }
+
+        let (
+            Some(redemption_mint),
+            Some(redemption_authority),
+            Some(redemption_token_account),
+            Some(associated_token_program),
+        ) = (
+            self.redemption_mint,
+            self.redemption_authority,
+            self.redemption_token_account,
+            self.associated_token_program,
+        ) else {
+            return Err(error!(ShipmentError::RedemptionRequired));
+        };
+        require!(
+            redemption_mint.key().eq(&self.collection_pool.redemption_mint),
+            ShipmentError::InvalidRedemption
+        );
+
+        let token_program = self.token_program.to_account_info();
+        associated_token::create_idempotent(
+            CpiContext::new(associated_token_program.to_account_info(), Create {
+                payer: self.payer,
+                associated_token: redemption_token_account.to_account_info(),
+                authority: self.owner,
+                mint: redemption_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: token_program.clone(),
+            })
+        )?;
+
+        let seeds = &[
+            REDEMPTION_AUTHORITY_SEED.as_bytes(),
+            self.collection.as_ref(),
+            &[self.redemption_authority_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Mint when the authority controls the mint, otherwise hand out from the stock
+        if redemption_mint.mint_authority == Some(redemption_authority.key()).into() {
+            let cpi_accounts = MintTo {
+                mint: redemption_mint.to_account_info(),
+                to: redemption_token_account.to_account_info(),
+                authority: redemption_authority.to_account_info(),
+            };
+            token::mint_to(CpiContext::new_with_signer(token_program, cpi_accounts, signer), 1)
+        } else {
+            let redemption_source = self.redemption_source
+                .as_ref()
+                .ok_or(ShipmentError::RedemptionRequired)?;
+            let cpi_accounts = Transfer {
+                from: redemption_source.to_account_info(),
+                to: redemption_token_account.to_account_info(),
+                authority: redemption_authority.to_account_info(),
+            };
+            token::transfer(CpiContext::new_with_signer(token_program, cpi_accounts, signer), 1)
+        }
+    }
+}
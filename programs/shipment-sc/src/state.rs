@@ -1,10 +1,21 @@
 use anchor_lang::prelude::*;
 
+use crate::constant::*;
+use crate::error::*;
+
 #[account]
 pub struct GlobalPool {
     pub super_admin: Pubkey,
     pub treasury: Pubkey,
     pub total_deposit_count: u64,
+    pub withdrawal_grace_period: i64,
+    pub delivery_proof_required: bool, // finalize needs owner signature or courier attestation
+    pub courier_oracle: Pubkey, // ed25519 key attesting deliveries
+    pub transfer_cosign_required: bool, // deposit ownership transfer needs admin co-sign
+    pub collection_count: u64, // collections appended to the registry
+    pub depositor_merkle_root: [u8; 32], // root of approved depositor tree, zero if deposits are open
+    pub deposit_quota: DepositQuota, // per-user deposit limits
+    pub identity_authority: Pubkey, // ed25519 key attesting web2 identity links, default if uuids are not checked
 }
 
 impl GlobalPool {
@@ -15,10 +26,140 @@ impl GlobalPool {
 pub struct CollectionPool {
     pub address: Pubkey,
     pub allowed: bool,
+    pub redemption_mint: Pubkey, // token given to owners on finalize, default if disabled
+    pub rent_destination: RentDestination, // who receives rent when a deposit is finalized
+    pub deposit_count: u64, // active deposits of the collection
+    pub registry_index: u64, // slot of the collection in the registry
+    pub mint_merkle_root: [u8; 32], // root of allowlisted mint tree, zero if disabled
+    pub depositor_merkle_root: [u8; 32], // overrides the global depositor root, zero if not set
+    pub deposit_quota: DepositQuota, // overrides the global limits it sets
+    pub name: String, // display name
+    pub uri: String, // display metadata uri
 }
 
 impl CollectionPool {
-    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<CollectionPool>();
+    pub const DATA_SIZE: usize =
+        8 + std::mem::size_of::<CollectionPool>() + MAX_COLLECTION_NAME_LEN + MAX_COLLECTION_URI_LEN;
+}
+
+// Page of registered collections, collection at registry index i is in page i / COLLECTION_REGISTRY_PAGE_SIZE
+#[account]
+pub struct CollectionRegistry {
+    pub page: u64,
+    pub count: u64, // registered collections in this page, closed collections leave an empty slot
+    pub collections: [Pubkey; 32],
+}
+
+impl CollectionRegistry {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<CollectionRegistry>();
+
+    pub fn page_of(index: u64) -> u64 {
+        index / COLLECTION_REGISTRY_PAGE_SIZE as u64
+    }
+
+    // Lists the collection at the next registry index, returns that index
+    pub fn append(&mut self, global_pool: &mut GlobalPool, collection: Pubkey) -> Result<u64> {
+        let index = global_pool.collection_count;
+        self.page = Self::page_of(index);
+        self.collections[(index as usize) % COLLECTION_REGISTRY_PAGE_SIZE] = collection;
+        self.count = self.count.checked_add(1).ok_or(ShipmentError::MathOverflow)?;
+        global_pool.collection_count = index.checked_add(1).ok_or(ShipmentError::MathOverflow)?;
+        Ok(index)
+    }
+}
+
+// Page of active deposit mints of a web2 user, seeded by the hash of its uuid
+#[account]
+pub struct Web2UserIndex {
+    pub page: u64,
+    pub count: u64, // active deposits in this page, removed deposits leave an empty slot
+    pub mints: [Pubkey; 16],
+}
+
+impl Web2UserIndex {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<Web2UserIndex>();
+
+    // uuids may exceed the 32 byte seed limit, pages are seeded by their hash
+    pub fn user_seed(user: &str) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hash(user.as_bytes()).to_bytes()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.mints.iter().all(|slot| *slot != Pubkey::default())
+    }
+
+    // Lists the mint in the first empty slot
+    pub fn insert(&mut self, mint: Pubkey) -> Result<()> {
+        let slot = self.mints
+            .iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+            .ok_or(ShipmentError::Web2UserIndexFull)?;
+        *slot = mint;
+        self.count = self.count.checked_add(1).ok_or(ShipmentError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, mint: &Pubkey) -> Result<()> {
+        if let Some(slot) = self.mints.iter_mut().find(|slot| *slot == mint) {
+            *slot = Pubkey::default();
+            self.count = self.count.checked_sub(1).ok_or(ShipmentError::MathOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RentDestination {
+    #[default]
+    Treasury,
+    Depositor,
+    Updater,
+}
+
+// Per-user deposit limits, zero means unlimited
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DepositQuota {
+    pub max_concurrent_deposits: u64, // active deposits a user may hold
+    pub max_daily_deposits: u64, // deposits a user may make per quota window
+}
+
+impl DepositQuota {
+    // Limits left unset fall back to the given quota
+    pub fn or(self, fallback: DepositQuota) -> DepositQuota {
+        DepositQuota {
+            max_concurrent_deposits: if self.max_concurrent_deposits != 0 {
+                self.max_concurrent_deposits
+            } else {
+                fallback.max_concurrent_deposits
+            },
+            max_daily_deposits: if self.max_daily_deposits != 0 {
+                self.max_daily_deposits
+            } else {
+                fallback.max_daily_deposits
+            },
+        }
+    }
+}
+
+// Mint allowed to be deposited under a collection without collection metadata
+#[account]
+pub struct MintAllowlist {
+    pub mint: Pubkey,
+    pub collection: Pubkey, // collection pool the mint is deposited under
+}
+
+impl MintAllowlist {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<MintAllowlist>();
+}
+
+#[account]
+pub struct DepositDelegate {
+    pub beneficiary: Pubkey, // wallet the delegated deposits are attributed to
+    pub delegate: Pubkey, // wallet allowed to deposit on behalf of the beneficiary
+}
+
+impl DepositDelegate {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<DepositDelegate>();
 }
 
 #[account]
@@ -27,10 +168,27 @@ pub struct UserPool {
     pub deposit_count: u64,
     pub admin: bool,
     pub updater: bool,
+    pub recent_deposits: [i64; MAX_DAILY_DEPOSITS], // timestamps of the latest deposits, zero if unused
+    pub web2_user: [u8; 32], // hash of the linked web2 uuid, zero if not linked
 }
 
 impl UserPool {
     pub const DATA_SIZE: usize = 8 + std::mem::size_of::<UserPool>();
+
+    // Deposits made within the rolling quota window ending now
+    pub fn deposits_in_window(&self, now: i64) -> u64 {
+        self.recent_deposits
+            .iter()
+            .filter(|timestamp| now < timestamp.saturating_add(DEPOSIT_QUOTA_WINDOW))
+            .count() as u64
+    }
+
+    // Records a deposit over the oldest one kept
+    pub fn record_deposit(&mut self, now: i64) {
+        if let Some(oldest) = self.recent_deposits.iter_mut().min() {
+            *oldest = now;
+        }
+    }
 }
 
 #[account]
@@ -40,14 +198,58 @@ pub struct NftDeposit {
     pub created: i64, // timestamp when deposit created
     pub status: u8, // deposit status
     pub locked: bool, // if locked
+    pub lock_reason: u8, // why the deposit is locked, 0 if unspecified
+    pub locked_until: i64, // timestamp when lock expires, 0 if no expiry
+    pub withdrawal_requested_at: i64, // timestamp when owner requested withdrawal, 0 if none
+    pub disputed: bool, // owner opened a dispute, withdrawal and finalize are frozen
+    pub receipt: bool, // custody receipt token was minted to the owner
+    pub collection: Pubkey, // collection the nft was deposited under
+    pub payer: Pubkey, // depositor who paid rent for the deposit accounts
+    pub index_page: u64, // page of the web2 user index listing the deposit
     pub user: String, // uuid of the web2 account
 }
 
 impl NftDeposit {
-    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<NftDeposit>();
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<NftDeposit>() + MAX_UUID_LEN;
 
     pub fn reset(&mut self) {
         self.status = 0; // created state
         self.locked = false; // release freeze
+        self.lock_reason = 0;
+        self.locked_until = 0;
+        self.withdrawal_requested_at = 0; // clear pending request
+        self.disputed = false;
+        self.receipt = false;
+    }
+
+    // Lock is released automatically once locked_until passes
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.locked && (self.locked_until == 0 || now < self.locked_until)
     }
+
+    // Pending withdrawal request left unanswered for the whole grace period
+    pub fn withdrawal_overdue(&self, grace_period: i64, now: i64) -> bool {
+        self.withdrawal_requested_at != 0 &&
+            now >= self.withdrawal_requested_at.saturating_add(grace_period)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisputeOutcome {
+    ReturnToOwner,
+    SendToTreasury,
+    Finalize,
+}
+
+// Open dispute, closed to its owner on resolution
+#[account]
+pub struct Dispute {
+    pub mint: Pubkey, // disputed nft mint
+    pub owner: Pubkey, // owner who opened the dispute
+    pub opened: i64, // timestamp when dispute opened
+    pub evidence_hash: [u8; 32], // hash of evidence submitted by owner
+}
+
+impl Dispute {
+    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<Dispute>();
 }
@@ -0,0 +1,34 @@
+use crate::*;
+use mpl_token_metadata::accounts::Metadata;
+
+// Nft belongs to the collection through its verified collection or a verified creator
+pub fn validate_collection_metadata(mint_metadata: &AccountInfo, collection: &Pubkey) -> Result<()> {
+    // Verify metadata is legit
+    let nft_metadata = Metadata::safe_deserialize(
+        &mint_metadata.data.borrow()
+    ).map_err(|_| error!(ShipmentError::InvalidMetadata))?;
+
+    // Check if this NFT is the wanted collection and verified
+    let mut valid: u8 = 0;
+    if let Some(meta_collection) = nft_metadata.collection {
+        msg!("collection: {}", meta_collection.key.to_string());
+        if meta_collection.verified && meta_collection.key.eq(collection) {
+            valid = 1;
+        }
+    } else {
+        return Err(error!(ShipmentError::MetadataCreatorParseError));
+    }
+    if let Some(creators) = nft_metadata.creators {
+        for creator in creators {
+            if creator.verified && creator.address.eq(collection) {
+                valid = 1;
+                break;
+            }
+        }
+    } else {
+        return Err(error!(ShipmentError::MetadataCreatorParseError));
+    }
+
+    require!(valid == 1, ShipmentError::InvalidCollection);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::*;
+
+// Message the courier oracle signs to attest a delivery: mint followed by proof hash
+pub fn delivery_proof_message(mint: &Pubkey, proof_hash: &[u8; 32]) -> [u8; 64] {
+    let mut message = [0u8; 64];
+    message[..32].copy_from_slice(mint.as_ref());
+    message[32..].copy_from_slice(proof_hash);
+    message
+}
+
+pub fn validate_delivery_proof(
+    global_pool: &GlobalPool,
+    owner_signed: bool,
+    instructions: &AccountInfo,
+    mint: &Pubkey,
+    proof_hash: &[u8; 32]
+) -> Result<()> {
+    if !global_pool.delivery_proof_required || owner_signed {
+        return Ok(());
+    }
+
+    // Courier attestation must be verified by the instruction right before finalize
+    validate_ed25519_attestation(
+        instructions,
+        &global_pool.courier_oracle,
+        &delivery_proof_message(mint, proof_hash),
+        ShipmentError::DeliveryProofRequired,
+        ShipmentError::InvalidDeliveryProof
+    )
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn validate_unlocked(deposit_state: &NftDeposit, now: i64) -> Result<()> {
+    if deposit_state.is_locked(now) {
+        msg!(
+            "deposit locked: reason {}, until {}",
+            deposit_state.lock_reason,
+            deposit_state.locked_until
+        );
+        return Err(error!(ShipmentError::DisabledWithdrawal));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::*;
+
+// Daily quota is counted over the deposit timestamps kept in the user pool
+pub fn validate_deposit_quota(deposit_quota: &DepositQuota) -> Result<()> {
+    require!(
+        deposit_quota.max_daily_deposits <= MAX_DAILY_DEPOSITS as u64,
+        ShipmentError::InvalidDepositQuota
+    );
+    Ok(())
+}
+
+// Active deposits a wallet may hold, applied to deposits and incoming transfers alike
+pub fn validate_concurrent_quota(quota: &DepositQuota, user_pool: &UserPool) -> Result<()> {
+    require!(
+        quota.max_concurrent_deposits == 0 ||
+            user_pool.deposit_count < quota.max_concurrent_deposits,
+        ShipmentError::QuotaExceeded
+    );
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::*;
+use solana_program::{ ed25519_program, sysvar::instructions as ix_sysvar };
+
+// Ed25519 precompile layout: count (u8), padding (u8), then 7 u16 offsets per signature
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+const ED25519_SIGNATURE_SIZE: usize = 64;
+const ED25519_PUBKEY_SIZE: usize = 32;
+// Offsets pointing at the precompile instruction's own data
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// Checks the instruction right before the current one verified `signer`'s signature of `message`
+pub fn validate_ed25519_attestation(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    missing: ShipmentError,
+    invalid: ShipmentError
+) -> Result<()> {
+    let current = ix_sysvar::load_current_index_checked(instructions)?;
+    if current == 0 {
+        return Err(missing.into());
+    }
+    let ix = ix_sysvar::load_instruction_at_checked((current - 1) as usize, instructions)?;
+    if ix.program_id != ed25519_program::ID {
+        return Err(missing.into());
+    }
+
+    let data = &ix.data;
+    if data.len() < ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE || data[0] != 1 {
+        return Err(invalid.into());
+    }
+    let offset = |i: usize| {
+        let at = ED25519_OFFSETS_START + i * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let (signature_ix, pubkey_offset, pubkey_ix) = (offset(1), offset(2) as usize, offset(3));
+    let (message_offset, message_size, message_ix) = (offset(4) as usize, offset(5) as usize, offset(6));
+    if
+        signature_ix != CURRENT_INSTRUCTION ||
+        pubkey_ix != CURRENT_INSTRUCTION ||
+        message_ix != CURRENT_INSTRUCTION ||
+        offset(0) as usize + ED25519_SIGNATURE_SIZE > data.len()
+    {
+        return Err(invalid.into());
+    }
+
+    let pubkey = data.get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_SIZE);
+    let signed = data.get(message_offset..message_offset + message_size);
+    if pubkey != Some(signer.as_ref()) || signed != Some(message) {
+        return Err(invalid.into());
+    }
+
+    Ok(())
+}
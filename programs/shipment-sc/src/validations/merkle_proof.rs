@@ -0,0 +1,20 @@
+use crate::*;
+use solana_program::keccak;
+
+// Leaf of an allowlist tree: keccak of the allowed pubkey
+pub fn merkle_leaf(key: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[key.as_ref()]).to_bytes()
+}
+
+// Sorted pair hashing, so proofs do not carry left / right flags
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+
+    computed.eq(root)
+}
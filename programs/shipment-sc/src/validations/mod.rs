@@ -2,3 +2,21 @@ pub mod admin_authority;
 pub use admin_authority::*;
 pub mod updater_authority;
 pub use updater_authority::*;
+pub mod deposit_lock;
+pub use deposit_lock::*;
+pub mod ed25519_attestation;
+pub use ed25519_attestation::*;
+pub mod delivery_proof;
+pub use delivery_proof::*;
+pub mod merkle_proof;
+pub use merkle_proof::*;
+pub mod web2_identity;
+pub use web2_identity::*;
+pub mod rent_recipient;
+pub use rent_recipient::*;
+pub mod web2_user_index;
+pub use web2_user_index::*;
+pub mod deposit_quota;
+pub use deposit_quota::*;
+pub mod collection_metadata;
+pub use collection_metadata::*;
@@ -0,0 +1,19 @@
+use crate::*;
+
+// Rent of a finalized deposit goes where its collection is configured to send it
+pub fn validate_rent_recipient(
+    collection_pool: &CollectionPool,
+    deposit_state: &NftDeposit,
+    treasury: &Pubkey,
+    finalizer: &Pubkey,
+    rent_recipient: &Pubkey
+) -> Result<()> {
+    let expected = match collection_pool.rent_destination {
+        RentDestination::Treasury => *treasury,
+        RentDestination::Depositor => deposit_state.payer,
+        RentDestination::Updater => *finalizer,
+    };
+    require!(rent_recipient.eq(&expected), ShipmentError::InvalidRentRecipient);
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::*;
+
+// Message the identity authority signs to bind a wallet to a web2 user: wallet followed by uuid
+pub fn web2_identity_message(wallet: &Pubkey, uuid: &str) -> Vec<u8> {
+    [wallet.as_ref(), uuid.as_bytes()].concat()
+}
+
+// Deposit accounts only reserve room for a standard uuid
+pub fn validate_uuid(uuid: &str) -> Result<()> {
+    require!(uuid.len() <= MAX_UUID_LEN, ShipmentError::Web2UserTooLong);
+    Ok(())
+}
+
+// Once an identity authority is set, deposits are attributed only to the uuid linked to the wallet
+pub fn validate_web2_user(global_pool: &GlobalPool, user_pool: &UserPool, uuid: &str) -> Result<()> {
+    validate_uuid(uuid)?;
+    if global_pool.identity_authority == Pubkey::default() {
+        return Ok(());
+    }
+    require!(
+        user_pool.web2_user == Web2UserIndex::user_seed(uuid),
+        ShipmentError::Web2UserNotLinked
+    );
+    Ok(())
+}
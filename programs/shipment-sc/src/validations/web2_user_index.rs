@@ -0,0 +1,18 @@
+use crate::*;
+
+// Pages of a web2 user are opened in order, a new page only once the previous one is full
+pub fn validate_index_page(
+    web2_user_index: &Web2UserIndex,
+    previous_web2_user_index: &Option<Box<Account<Web2UserIndex>>>,
+    index_page: u64
+) -> Result<()> {
+    // First page, or a page already opened by an earlier deposit
+    if index_page == 0 || web2_user_index.page == index_page {
+        return Ok(());
+    }
+
+    match previous_web2_user_index {
+        Some(previous) if previous.is_full() => Ok(()),
+        _ => Err(error!(ShipmentError::Web2UserIndexPageSkipped)),
+    }
+}
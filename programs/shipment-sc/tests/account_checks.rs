@@ -0,0 +1,260 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{
+    account::Account, program_error::ProgramError, pubkey::Pubkey, signature::Signer,
+};
+
+fn deposit_count(env: &TestEnv, user: &Pubkey) -> u64 {
+    let pool: UserPool = env.svm.get(&pda::user_pool(user).0);
+    pool.deposit_count
+}
+
+#[test]
+fn finalize_rejects_other_users_pool() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let victim = env.new_user();
+    let nft = env.deposit(&owner);
+    env.deposit(&victim);
+
+    let updater = env.updater.insecure_clone();
+    let (collection, treasury) = (env.collection, env.treasury);
+    let result = env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &victim.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            [0; 32],
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+    assert_eq!(deposit_count(&env, &victim.pubkey()), 1);
+    assert_eq!(deposit_count(&env, &owner.pubkey()), 1);
+}
+
+#[test]
+fn finalize_rejects_non_associated_custody_account() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    // Same mint and authority as the custody account, but not its associated address
+    let custody = pda::custody_token_account(&nft.mint);
+    let decoy = Pubkey::new_unique();
+    let account = env.svm.get_account(&custody).unwrap();
+    env.svm.set_account(decoy, account);
+
+    let updater = env.updater.insecure_clone();
+    let (collection, treasury) = (env.collection, env.treasury);
+    let mut ix = finalize_deposit_ix(
+        &updater.pubkey(),
+        &user.pubkey(),
+        &nft.mint,
+        &collection,
+        &treasury,
+        [0; 32],
+    );
+    for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == custody) {
+        meta.pubkey = decoy;
+    }
+    let result = env.process(&[ix], &[&updater]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintAssociated.into()),
+    );
+    assert_eq!(deposit_count(&env, &user.pubkey()), 1);
+}
+
+#[test]
+fn withdraw_treasury_rejects_other_users_pool() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let owner = env.new_user();
+    let victim = env.new_user();
+    let nft = env.deposit(&owner);
+    env.deposit(&victim);
+
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+    let result = env.process(
+        &[withdraw_treasury_ix(
+            &admin,
+            &victim.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+    assert_eq!(deposit_count(&env, &victim.pubkey()), 1);
+}
+
+#[test]
+fn approve_withdrawal_rejects_other_users_pool() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let owner = env.new_user();
+    let victim = env.new_user();
+    let nft = env.deposit(&owner);
+    env.deposit(&victim);
+    env.process(
+        &[instructions::request_withdrawal(&owner.pubkey(), &nft.mint)],
+        &[&owner],
+    )
+    .unwrap();
+
+    let updater = env.updater.insecure_clone();
+    let result = env.process(
+        &[approve_withdrawal_ix(
+            &updater.pubkey(),
+            &victim.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+    assert_eq!(deposit_count(&env, &victim.pubkey()), 1);
+}
+
+#[test]
+fn update_rejects_mismatched_owner() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let updater = env.updater.insecure_clone();
+    let result = env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &other.pubkey(),
+            &nft.mint,
+            Some(2),
+            None,
+            None,
+            None,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+}
+
+#[test]
+fn request_withdrawal_rejects_non_owner() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let result = env.process(
+        &[instructions::request_withdrawal(&other.pubkey(), &nft.mint)],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.withdrawal_requested_at, 0);
+}
+
+#[test]
+fn transfer_rejects_non_owner() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &other.pubkey(),
+            &other.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "other-user",
+        )],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+    assert_eq!(deposit_count(&env, &other.pubkey()), 0);
+    assert_eq!(deposit_count(&env, &owner.pubkey()), 1);
+}
+
+#[test]
+fn deposit_rejects_spoofed_metadata() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+    let collection = env.collection;
+    let metadata = env.svm.get_account(&nft.metadata).unwrap();
+
+    // Metadata naming the collection, at an address other than the mint's metadata PDA
+    let spoofed = Pubkey::new_unique();
+    env.svm.set_account(spoofed, metadata.clone());
+    let mut ix = deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID);
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == nft.metadata {
+            meta.pubkey = spoofed;
+        }
+    }
+    let result = env.process(&[ix], &[&user]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintAddress.into()),
+    );
+
+    // Token metadata program swapped for another one
+    let mut ix = deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID);
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == mpl_token_metadata::ID {
+            meta.pubkey = Pubkey::new_unique();
+        }
+    }
+    let result = env.process(&[ix], &[&user]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintAddress.into()),
+    );
+
+    // Metadata PDA not written by the token metadata program
+    env.svm.set_account(
+        nft.metadata,
+        Account {
+            owner: Pubkey::new_unique(),
+            ..metadata
+        },
+    );
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintOwner.into()),
+    );
+    assert_eq!(deposit_count(&env, &user.pubkey()), 0);
+}
+
+#[test]
+fn deposit_requires_verified_collection() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let collection = env.collection;
+    let nft = env.mint_nft_with(&user.pubkey(), Some(collection), false);
+
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidCollection);
+    assert_eq!(deposit_count(&env, &user.pubkey()), 0);
+}
@@ -0,0 +1,287 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::solana_program::{program_error::ProgramError, system_instruction::SystemError};
+use common::*;
+use shipment_sc::{constant::*, error::ShipmentError, state::*};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+#[test]
+fn initialize_sets_defaults() {
+    let env = TestEnv::new();
+
+    let global_pool: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global_pool.super_admin, env.super_admin.pubkey());
+    assert_eq!(global_pool.treasury, env.treasury);
+    assert_eq!(global_pool.total_deposit_count, 0);
+    assert_eq!(
+        global_pool.withdrawal_grace_period,
+        DEFAULT_WITHDRAWAL_GRACE_PERIOD
+    );
+    assert!(!global_pool.delivery_proof_required);
+    assert!(!global_pool.transfer_cosign_required);
+    assert_eq!(global_pool.collection_count, 1);
+    assert_eq!(global_pool.depositor_merkle_root, [0; 32]);
+}
+
+#[test]
+fn initialize_only_once() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::initialize(&user.pubkey(), &user.pubkey())],
+        &[&user],
+    );
+    assert_program_error(
+        result,
+        ProgramError::Custom(SystemError::AccountAlreadyInUse as u32),
+    );
+
+    let global_pool: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global_pool.super_admin, env.super_admin.pubkey());
+}
+
+#[test]
+fn init_user_starts_without_roles() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+
+    let user_pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(user_pool.address, user.pubkey());
+    assert_eq!(user_pool.deposit_count, 0);
+    assert!(!user_pool.admin);
+    assert!(!user_pool.updater);
+}
+
+#[test]
+fn init_user_only_once() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::init_user(&user.pubkey(), &user.pubkey())],
+        &[&user],
+    );
+    assert_program_error(
+        result,
+        ProgramError::Custom(SystemError::AccountAlreadyInUse as u32),
+    );
+}
+
+#[test]
+fn change_role_grants_and_revokes_admin() {
+    let mut env = TestEnv::new();
+    let super_admin = env.super_admin.pubkey();
+    let admin = env.new_user();
+    let collection = Pubkey::new_unique();
+
+    env.process(
+        &[instructions::change_role(
+            &super_admin,
+            &admin.pubkey(),
+            Some(true),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+    let user_pool: UserPool = env.svm.get(&pda::user_pool(&admin.pubkey()).0);
+    assert!(user_pool.admin);
+    assert!(!user_pool.updater);
+
+    env.process(
+        &[instructions::register_collection(
+            &admin.pubkey(),
+            &collection,
+            0,
+        )],
+        &[&admin],
+    )
+    .unwrap();
+
+    env.process(
+        &[instructions::change_role(
+            &super_admin,
+            &admin.pubkey(),
+            Some(false),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+    let result = env.process(
+        &[instructions::revoke_collection(
+            &admin.pubkey(),
+            &collection,
+        )],
+        &[&admin],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn change_role_keeps_unspecified_roles() {
+    let mut env = TestEnv::new();
+    let super_admin = env.super_admin.pubkey();
+    let updater = env.updater.pubkey();
+
+    env.process(
+        &[instructions::change_role(
+            &super_admin,
+            &updater,
+            Some(true),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    let user_pool: UserPool = env.svm.get(&pda::user_pool(&updater).0);
+    assert!(user_pool.admin);
+    assert!(user_pool.updater);
+}
+
+#[test]
+fn admin_role_can_change_roles() {
+    let mut env = TestEnv::new();
+    let super_admin = env.super_admin.pubkey();
+    let admin = env.new_user();
+    let user = env.new_user();
+    env.process(
+        &[instructions::change_role(
+            &super_admin,
+            &admin.pubkey(),
+            Some(true),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    env.process(
+        &[instructions::change_role(
+            &admin.pubkey(),
+            &user.pubkey(),
+            None,
+            Some(true),
+        )],
+        &[&admin],
+    )
+    .unwrap();
+
+    let user_pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert!(user_pool.updater);
+}
+
+#[test]
+fn change_role_requires_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::change_role(
+            &user.pubkey(),
+            &user.pubkey(),
+            Some(true),
+            None,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn transfer_super_admin_hands_over_control() {
+    let mut env = TestEnv::new();
+    let old_admin = env.super_admin.pubkey();
+    let new_admin = env.new_user();
+    let treasury = Pubkey::new_unique();
+
+    env.process(
+        &[instructions::transfer_super_admin(
+            &old_admin,
+            &new_admin.pubkey(),
+        )],
+        &[],
+    )
+    .unwrap();
+    let global_pool: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global_pool.super_admin, new_admin.pubkey());
+
+    env.process(
+        &[instructions::change_treasury(
+            &new_admin.pubkey(),
+            &treasury,
+        )],
+        &[&new_admin],
+    )
+    .unwrap();
+    let result = env.process(
+        &[instructions::transfer_super_admin(&old_admin, &old_admin)],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn transfer_super_admin_requires_super_admin() {
+    let mut env = TestEnv::new();
+    let super_admin = env.super_admin.pubkey();
+    let admin = env.new_user();
+    env.process(
+        &[instructions::change_role(
+            &super_admin,
+            &admin.pubkey(),
+            Some(true),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[instructions::transfer_super_admin(
+            &admin.pubkey(),
+            &admin.pubkey(),
+        )],
+        &[&admin],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn change_treasury_updates_global_pool() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let treasury = Pubkey::new_unique();
+
+    env.process(&[instructions::change_treasury(&admin, &treasury)], &[])
+        .unwrap();
+
+    let global_pool: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global_pool.treasury, treasury);
+}
+
+#[test]
+fn register_and_revoke_collection() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = Pubkey::new_unique();
+
+    env.process(
+        &[instructions::register_collection(&admin, &collection, 0)],
+        &[],
+    )
+    .unwrap();
+    let collection_pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(collection_pool.address, collection);
+    assert!(collection_pool.allowed);
+    assert_eq!(collection_pool.deposit_count, 0);
+
+    env.process(&[instructions::revoke_collection(&admin, &collection)], &[])
+        .unwrap();
+    let collection_pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert!(!collection_pool.allowed);
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{constant::*, error::ShipmentError};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn withdraw(env: &mut TestEnv, user: &Keypair, nft: &Nft) {
+    let collection = env.collection;
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[user],
+    )
+    .unwrap();
+}
+
+#[test]
+fn user_closes_own_pool() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let recipient = Pubkey::new_unique();
+    let rent = env.svm.lamports(&pda::user_pool(&user.pubkey()).0);
+
+    env.process(
+        &[instructions::close_user(
+            &user.pubkey(),
+            &user.pubkey(),
+            &recipient,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(env.svm.lamports(&pda::user_pool(&user.pubkey()).0), 0);
+    assert_eq!(env.svm.lamports(&recipient), rent);
+
+    // The user can register again
+    let admin = env.super_admin.pubkey();
+    env.process(&[instructions::init_user(&admin, &user.pubkey())], &[])
+        .unwrap();
+}
+
+#[test]
+fn admin_closes_user_pool() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let user = env.new_user();
+    let recipient = Pubkey::new_unique();
+
+    env.process(
+        &[instructions::close_user(&admin, &user.pubkey(), &recipient)],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(env.svm.lamports(&pda::user_pool(&user.pubkey()).0), 0);
+    assert!(env.svm.lamports(&recipient) > 0);
+}
+
+#[test]
+fn close_other_user_requires_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let other = env.new_user();
+
+    let result = env.process(
+        &[instructions::close_user(
+            &other.pubkey(),
+            &user.pubkey(),
+            &other.pubkey(),
+        )],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn close_requires_no_roles() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let updater = env.updater.insecure_clone();
+
+    let result = env.process(
+        &[instructions::close_user(
+            &updater.pubkey(),
+            &updater.pubkey(),
+            &updater.pubkey(),
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::UserHasRoles);
+
+    let user = env.new_user();
+    env.process(
+        &[instructions::change_role(
+            &admin,
+            &user.pubkey(),
+            Some(true),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+    let result = env.process(
+        &[instructions::close_user(&admin, &user.pubkey(), &admin)],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::UserHasRoles);
+
+    // Revoked roles no longer block closing
+    env.process(
+        &[instructions::change_role(
+            &admin,
+            &user.pubkey(),
+            Some(false),
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+    env.process(
+        &[instructions::close_user(&admin, &user.pubkey(), &admin)],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn close_requires_no_deposits() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let close = || instructions::close_user(&user.pubkey(), &user.pubkey(), &user.pubkey());
+    let result = env.process(&[close()], &[&user]);
+    assert_shipment_error(result, ShipmentError::UserHasDeposits);
+
+    // Deposits of the current quota window still count
+    withdraw(&mut env, &user, &nft);
+    let result = env.process(&[close()], &[&user]);
+    assert_shipment_error(result, ShipmentError::UserHasDeposits);
+
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW);
+    env.process(&[close()], &[&user]).unwrap();
+    let pool = env.svm.lamports(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool, 0);
+}
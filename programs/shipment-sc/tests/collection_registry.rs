@@ -0,0 +1,317 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use shipment_sc::{constant::*, error::ShipmentError, state::*};
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey, signature::Signer};
+
+fn collection_deposits(env: &TestEnv, collection: &Pubkey) -> u64 {
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(collection).0);
+    pool.deposit_count
+}
+
+#[test]
+fn register_appends_to_registry() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let first = env.collection;
+    let second = Pubkey::new_unique();
+
+    env.process(
+        &[instructions::register_collection(&admin, &second, 0)],
+        &[],
+    )
+    .unwrap();
+
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.collection_count, 2);
+    let registry: CollectionRegistry = env.svm.get(&pda::collection_registry(0).0);
+    assert_eq!(registry.page, 0);
+    assert_eq!(registry.count, 2);
+    assert_eq!(registry.collections[..2], [first, second]);
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&second).0);
+    assert_eq!(pool.registry_index, 1);
+    assert!(pool.allowed);
+}
+
+#[test]
+fn reregister_keeps_registry_slot() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+
+    env.process(
+        &[
+            instructions::revoke_collection(&admin, &collection),
+            instructions::register_collection(&admin, &collection, 0),
+        ],
+        &[],
+    )
+    .unwrap();
+
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.collection_count, 1);
+    let registry: CollectionRegistry = env.svm.get(&pda::collection_registry(0).0);
+    assert_eq!(registry.count, 1);
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.registry_index, 0);
+    assert!(pool.allowed);
+}
+
+#[test]
+fn registry_rolls_over_to_next_page() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+
+    let mut last = Pubkey::default();
+    for index in 1..=COLLECTION_REGISTRY_PAGE_SIZE as u64 {
+        last = Pubkey::new_unique();
+        let page = index / COLLECTION_REGISTRY_PAGE_SIZE as u64;
+        env.process(
+            &[instructions::register_collection(&admin, &last, page)],
+            &[],
+        )
+        .unwrap();
+    }
+
+    let first_page: CollectionRegistry = env.svm.get(&pda::collection_registry(0).0);
+    assert_eq!(first_page.count, COLLECTION_REGISTRY_PAGE_SIZE as u64);
+    let second_page: CollectionRegistry = env.svm.get(&pda::collection_registry(1).0);
+    assert_eq!(second_page.page, 1);
+    assert_eq!(second_page.count, 1);
+    assert_eq!(second_page.collections[0], last);
+
+    // Registration must target the page of the next registry index
+    let result = env.process(
+        &[instructions::register_collection(
+            &admin,
+            &Pubkey::new_unique(),
+            0,
+        )],
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn deposits_are_counted_per_collection() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let first = env.deposit(&user);
+    let second = env.deposit(&user);
+    assert_eq!(collection_deposits(&env, &collection), 2);
+
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &first.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(collection_deposits(&env, &collection), 1);
+
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+    env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &second.mint,
+            &collection,
+            &treasury,
+            [0; 32],
+        )],
+        &[&updater],
+    )
+    .unwrap();
+    assert_eq!(collection_deposits(&env, &collection), 0);
+}
+
+#[test]
+fn change_collection_metadata() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+
+    env.process(
+        &[instructions::change_collection_metadata(
+            &admin,
+            &collection,
+            "Shipment Collection",
+            "https://example.com/collection.json",
+        )],
+        &[],
+    )
+    .unwrap();
+
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.name, "Shipment Collection");
+    assert_eq!(pool.uri, "https://example.com/collection.json");
+}
+
+#[test]
+fn change_collection_metadata_errors() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::change_collection_metadata(
+            &user.pubkey(),
+            &collection,
+            "name",
+            "uri",
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let name = "n".repeat(MAX_COLLECTION_NAME_LEN + 1);
+    let result = env.process(
+        &[instructions::change_collection_metadata(
+            &admin,
+            &collection,
+            &name,
+            "uri",
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::CollectionMetadataTooLong);
+
+    let uri = "u".repeat(MAX_COLLECTION_URI_LEN + 1);
+    let result = env.process(
+        &[instructions::change_collection_metadata(
+            &admin,
+            &collection,
+            "name",
+            &uri,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::CollectionMetadataTooLong);
+
+    // Longest allowed metadata fits the collection pool
+    let name = "n".repeat(MAX_COLLECTION_NAME_LEN);
+    let uri = "u".repeat(MAX_COLLECTION_URI_LEN);
+    env.process(
+        &[instructions::change_collection_metadata(
+            &admin,
+            &collection,
+            &name,
+            &uri,
+        )],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn revoke_requires_registered_collection() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let unknown = Pubkey::new_unique();
+
+    let result = env.process(&[instructions::revoke_collection(&admin, &unknown)], &[]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::AccountNotInitialized.into()),
+    );
+    assert!(env
+        .svm
+        .get_account(&pda::collection_pool(&unknown).0)
+        .is_none());
+}
+
+#[test]
+fn close_collection_returns_rent_to_admin() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+
+    let pool_rent = env.svm.lamports(&pda::collection_pool(&collection).0);
+    let before = env.svm.lamports(&admin);
+    env.process(
+        &[
+            instructions::revoke_collection(&admin, &collection),
+            instructions::close_collection(&admin, &collection, 0),
+        ],
+        &[],
+    )
+    .unwrap();
+
+    assert!(env
+        .svm
+        .get_account(&pda::collection_pool(&collection).0)
+        .is_none());
+    assert_eq!(env.svm.lamports(&admin), before + pool_rent);
+    let registry: CollectionRegistry = env.svm.get(&pda::collection_registry(0).0);
+    assert_eq!(registry.count, 0);
+    assert_eq!(registry.collections[0], Pubkey::default());
+
+    // Registering again appends a fresh slot
+    env.process(
+        &[instructions::register_collection(&admin, &collection, 0)],
+        &[],
+    )
+    .unwrap();
+    let registry: CollectionRegistry = env.svm.get(&pda::collection_registry(0).0);
+    assert_eq!(registry.count, 1);
+    assert_eq!(registry.collections[1], collection);
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.registry_index, 1);
+}
+
+#[test]
+fn close_collection_requires_no_active_deposits() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let result = env.process(
+        &[instructions::close_collection(&admin, &collection, 0)],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::CollectionHasDeposits);
+
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    env.process(
+        &[instructions::close_collection(&admin, &collection, 0)],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn close_collection_requires_admin() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::close_collection(
+            &user.pubkey(),
+            &collection,
+            0,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
@@ -0,0 +1,699 @@
+#![allow(dead_code)]
+
+pub mod svm;
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        program_pack::Pack,
+        system_instruction,
+    },
+    Event,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address, token::spl_token, token_2022::spl_token_2022,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{CreateV1Builder, MintV1Builder, VerifyCollectionV1Builder},
+    types::{Collection, Creator, PrintSupply, TokenStandard},
+};
+use shipment_sc::{error::ShipmentError, state::DisputeOutcome};
+use shipment_sc_client::instructions::{
+    DepositOptions, DepositUpdate, DisputedDeposit, FinalizedDeposit, Redemption,
+};
+use solana_sdk::{
+    keccak,
+    rent::Rent,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+pub use shipment_sc_client::{instructions, pda};
+pub use svm::{Svm, TransactionError};
+
+pub const LAMPORTS: u64 = 10_000_000_000;
+
+/// Web2 user uuid of test deposits.
+pub const UUID: &str = "web2-user";
+
+/// Page 0 of the [`UUID`] index, which lists the test deposits.
+pub fn uuid_index() -> Pubkey {
+    pda::web2_user_index(UUID, 0).0
+}
+
+/// Points a withdrawal or finalize instruction, built for a deposit of
+/// [`UUID`] listed in page 0, at page `page` of `uuid`'s index.
+pub fn with_web2_user_index(mut ix: Instruction, uuid: &str, page: u64) -> Instruction {
+    let default = uuid_index();
+    let meta = ix
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == default)
+        .unwrap();
+    meta.pubkey = pda::web2_user_index(uuid, page).0;
+    ix
+}
+
+/// Fills the optional receipt accounts, which sit around `token_program`,
+/// of a deposit or withdrawal instruction.
+pub fn with_receipt(mut ix: Instruction, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    let token_program = ix
+        .accounts
+        .iter()
+        .position(|meta| meta.pubkey == spl_token::ID)
+        .unwrap();
+    ix.accounts[token_program - 2] = AccountMeta::new(pda::receipt_mint(mint).0, false);
+    ix.accounts[token_program - 1] =
+        AccountMeta::new(pda::receipt_token_account(owner, mint), false);
+    ix.accounts[token_program + 1] = AccountMeta::new_readonly(spl_token_2022::ID, false);
+    ix
+}
+
+pub fn merkle_leaf(key: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[key.as_ref()]).to_bytes()
+}
+
+pub fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak::hashv(&[&a, &b]).to_bytes()
+    } else {
+        keccak::hashv(&[&b, &a]).to_bytes()
+    }
+}
+
+/// Root of a four leaf tree and the proof of its first leaf.
+pub fn merkle_tree(keys: [Pubkey; 4]) -> ([u8; 32], Vec<[u8; 32]>) {
+    let leaves = keys.map(|key| merkle_leaf(&key));
+    let left = hash_pair(leaves[0], leaves[1]);
+    let right = hash_pair(leaves[2], leaves[3]);
+    (hash_pair(left, right), vec![leaves[1], right])
+}
+
+pub fn deposit_nft_ix(user: &Pubkey, nft: &Nft, collection: &Pubkey, uuid: &str) -> Instruction {
+    instructions::deposit_nft(user, &nft.mint, collection, uuid, DepositOptions::default())
+}
+
+/// Deposit of a mint allowlisted by its `MintAllowlist` account or by a
+/// merkle proof against the collection's mint root.
+pub fn allowlisted_deposit_nft_ix(
+    user: &Pubkey,
+    nft: &Nft,
+    collection: &Pubkey,
+    uuid: &str,
+    mint_allowlist: bool,
+    mint_proof: Option<Vec<[u8; 32]>>,
+) -> Instruction {
+    let options = DepositOptions {
+        mint_allowlist,
+        mint_proof,
+        ..DepositOptions::default()
+    };
+    instructions::deposit_nft(user, &nft.mint, collection, uuid, options)
+}
+
+/// Deposit listed in page `index_page` of the web2 user index of `uuid`.
+pub fn indexed_deposit_nft_ix(
+    user: &Pubkey,
+    nft: &Nft,
+    collection: &Pubkey,
+    uuid: &str,
+    index_page: u64,
+) -> Instruction {
+    let options = DepositOptions {
+        index_page,
+        ..DepositOptions::default()
+    };
+    instructions::deposit_nft(user, &nft.mint, collection, uuid, options)
+}
+
+/// Deposit by a wallet proving it is in the depositor merkle tree.
+pub fn approved_deposit_nft_ix(
+    user: &Pubkey,
+    nft: &Nft,
+    collection: &Pubkey,
+    uuid: &str,
+    depositor_proof: Option<Vec<[u8; 32]>>,
+) -> Instruction {
+    let options = DepositOptions {
+        depositor_proof,
+        ..DepositOptions::default()
+    };
+    instructions::deposit_nft(user, &nft.mint, collection, uuid, options)
+}
+
+/// Deposit of an nft held by `depositor` on behalf of `beneficiary`, with
+/// the depositor's delegate account when `delegated` and the beneficiary as
+/// a signer otherwise.
+pub fn deposit_nft_for_ix(
+    depositor: &Pubkey,
+    beneficiary: &Pubkey,
+    nft: &Nft,
+    collection: &Pubkey,
+    uuid: &str,
+    delegated: bool,
+) -> Instruction {
+    instructions::deposit_nft_for(
+        depositor,
+        beneficiary,
+        &nft.mint,
+        collection,
+        uuid,
+        delegated,
+        DepositOptions::default(),
+    )
+}
+
+pub fn update_deposit_ix(
+    updater: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    status: Option<u8>,
+    locked: Option<bool>,
+    lock_reason: Option<u8>,
+    locked_until: Option<i64>,
+) -> Instruction {
+    let update = DepositUpdate {
+        status,
+        locked,
+        lock_reason,
+        locked_until,
+    };
+    instructions::update_deposit(updater, user, mint, update)
+}
+
+pub fn withdraw_owner_ix(
+    payer: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+) -> Instruction {
+    instructions::withdraw_owner(payer, user, mint, &uuid_index(), collection, false)
+}
+
+pub fn withdraw_treasury_ix(
+    admin: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    instructions::withdraw_treasury(
+        admin,
+        user,
+        mint,
+        &uuid_index(),
+        collection,
+        treasury,
+        false,
+    )
+}
+
+pub fn approve_withdrawal_ix(
+    updater: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+) -> Instruction {
+    instructions::approve_withdrawal(updater, user, mint, &uuid_index(), collection, false)
+}
+
+pub fn transfer_deposit_ownership_ix(
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    admin: Option<&Pubkey>,
+    receipt: bool,
+    new_user: &str,
+) -> Instruction {
+    instructions::transfer_deposit_ownership(
+        owner,
+        new_owner,
+        mint,
+        collection,
+        &uuid_index(),
+        admin,
+        receipt,
+        new_user,
+        0,
+    )
+}
+
+/// Deposit of `user` finalized with its rent to the treasury.
+fn finalized_deposit(
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+) -> FinalizedDeposit {
+    FinalizedDeposit {
+        owner: *user,
+        mint: *mint,
+        web2_user_index: uuid_index(),
+        collection: *collection,
+        treasury: *treasury,
+        rent_recipient: *treasury,
+        redemption: None,
+        receipt: false,
+    }
+}
+
+pub fn finalize_deposit_ix(
+    updater: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    proof_hash: [u8; 32],
+) -> Instruction {
+    let deposit = finalized_deposit(user, mint, collection, treasury);
+    instructions::finalize_deposit(updater, &deposit, proof_hash)
+}
+
+/// Finalize of a deposit whose collection sends rent to `rent_recipient`.
+pub fn finalize_deposit_to_ix(
+    updater: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    rent_recipient: &Pubkey,
+) -> Instruction {
+    let deposit = FinalizedDeposit {
+        rent_recipient: *rent_recipient,
+        ..finalized_deposit(user, mint, collection, treasury)
+    };
+    instructions::finalize_deposit(updater, &deposit, [0; 32])
+}
+
+/// Finalize of a deposit whose collection hands out `redemption_mint`.
+pub fn redeem_finalize_deposit_ix(
+    updater: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    redemption_mint: &Pubkey,
+    redemption_source: Option<Pubkey>,
+) -> Instruction {
+    let deposit = FinalizedDeposit {
+        redemption: Some(Redemption {
+            mint: *redemption_mint,
+            source: redemption_source,
+        }),
+        ..finalized_deposit(user, mint, collection, treasury)
+    };
+    instructions::finalize_deposit(updater, &deposit, [0; 32])
+}
+
+/// Disputed deposit of `user`, a finalize outcome sends its rent to the
+/// treasury.
+fn disputed_deposit(
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    outcome: DisputeOutcome,
+) -> DisputedDeposit {
+    DisputedDeposit {
+        owner: *user,
+        mint: *mint,
+        web2_user_index: uuid_index(),
+        collection: *collection,
+        treasury: *treasury,
+        rent_recipient: (outcome == DisputeOutcome::Finalize).then_some(*treasury),
+        redemption: None,
+        receipt: false,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_dispute_ix(
+    admin: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    destination_token_account: Option<Pubkey>,
+    outcome: DisputeOutcome,
+    resolution_hash: [u8; 32],
+) -> Instruction {
+    let deposit = disputed_deposit(user, mint, collection, treasury, outcome);
+    instructions::resolve_dispute(
+        admin,
+        &deposit,
+        destination_token_account,
+        outcome,
+        resolution_hash,
+    )
+}
+
+/// Finalizes a disputed deposit of a redeeming collection.
+pub fn redeem_resolve_dispute_ix(
+    admin: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    treasury: &Pubkey,
+    redemption_mint: &Pubkey,
+    redemption_source: Option<Pubkey>,
+) -> Instruction {
+    let outcome = DisputeOutcome::Finalize;
+    let deposit = DisputedDeposit {
+        redemption: Some(Redemption {
+            mint: *redemption_mint,
+            source: redemption_source,
+        }),
+        ..disputed_deposit(user, mint, collection, treasury, outcome)
+    };
+    instructions::resolve_dispute(admin, &deposit, None, outcome, [0; 32])
+}
+
+/// Ed25519 precompile instruction with signature, key and message inline.
+pub fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    const HEADER: u16 = 2 + 14;
+    let signature = signer.sign_message(message);
+    let pubkey_offset = HEADER;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1u8, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        pubkey_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: solana_sdk::ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// A 1/1 NFT held in its owner's associated token account.
+#[derive(Debug, Clone, Copy)]
+pub struct Nft {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub metadata: Pubkey,
+}
+
+/// Program state shared by most tests: an initialized global pool, a super
+/// admin with a user pool, an updater and one registered collection, whose
+/// collection NFT the super admin holds and updates.
+pub struct TestEnv {
+    pub svm: Svm,
+    pub super_admin: Keypair,
+    pub updater: Keypair,
+    pub treasury: Pubkey,
+    pub collection: Pubkey,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let mut svm = Svm::new();
+        let super_admin = Keypair::new();
+        let treasury = Pubkey::new_unique();
+        svm.airdrop(&super_admin.pubkey(), LAMPORTS);
+        svm.airdrop(&treasury, LAMPORTS);
+
+        let mut env = TestEnv {
+            svm,
+            super_admin,
+            updater: Keypair::new(),
+            treasury,
+            collection: Pubkey::default(),
+        };
+        env.collection = env.create_collection();
+        let admin = env.super_admin.pubkey();
+        let collection = env.collection;
+        env.process(
+            &[
+                instructions::initialize(&admin, &treasury),
+                instructions::init_user(&admin, &admin),
+                instructions::register_collection(&admin, &collection, 0),
+            ],
+            &[],
+        )
+        .unwrap();
+
+        let updater = env.updater.pubkey();
+        env.new_user_with(&updater);
+        env.process(
+            &[instructions::change_role(
+                &admin,
+                &updater,
+                None,
+                Some(true),
+            )],
+            &[],
+        )
+        .unwrap();
+        env
+    }
+
+    /// Processes `instructions` with the super admin as an extra signer.
+    pub fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        let mut all: Vec<&Keypair> = vec![&self.super_admin];
+        all.extend_from_slice(signers);
+        self.svm.process(instructions, &all)
+    }
+
+    /// Funds a fresh wallet and initializes its user pool.
+    pub fn new_user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        self.new_user_with(&user.pubkey());
+        user
+    }
+
+    fn new_user_with(&mut self, user: &Pubkey) {
+        self.svm.airdrop(user, LAMPORTS);
+        let admin = self.super_admin.pubkey();
+        self.process(&[instructions::init_user(&admin, user)], &[])
+            .unwrap();
+    }
+
+    /// Creates a collection NFT held and updated by the super admin.
+    pub fn create_collection(&mut self) -> Pubkey {
+        let admin = self.super_admin.pubkey();
+        self.create_nft(&admin, None).mint
+    }
+
+    /// Mints an NFT of the registered collection to `owner`.
+    pub fn mint_nft(&mut self, owner: &Pubkey) -> Nft {
+        let collection = self.collection;
+        self.mint_nft_with(owner, Some(collection), true)
+    }
+
+    /// Mints an NFT to `owner` naming `collection` in its metadata, verified
+    /// by the super admin as the collection update authority when `verified`.
+    pub fn mint_nft_with(
+        &mut self,
+        owner: &Pubkey,
+        collection: Option<Pubkey>,
+        verified: bool,
+    ) -> Nft {
+        let nft = self.create_nft(owner, collection);
+        if let (Some(collection), true) = (collection, verified) {
+            let verify = VerifyCollectionV1Builder::new()
+                .authority(self.super_admin.pubkey())
+                .metadata(nft.metadata)
+                .collection_mint(collection)
+                .collection_metadata(Some(Metadata::find_pda(&collection).0))
+                .collection_master_edition(Some(MasterEdition::find_pda(&collection).0))
+                .instruction();
+            self.process(&[verify], &[]).unwrap();
+        }
+        nft
+    }
+
+    /// Creates a master edition NFT through the token metadata program, with
+    /// the super admin as its update authority and verified creator, and
+    /// mints it to `owner`. The collection it names starts unverified.
+    fn create_nft(&mut self, owner: &Pubkey, collection: Option<Pubkey>) -> Nft {
+        let mint = Keypair::new();
+        let admin = self.super_admin.pubkey();
+        let nft = Nft {
+            mint: mint.pubkey(),
+            token_account: get_associated_token_address(owner, &mint.pubkey()),
+            metadata: Metadata::find_pda(&mint.pubkey()).0,
+        };
+        let master_edition = MasterEdition::find_pda(&nft.mint).0;
+
+        let mut create = CreateV1Builder::new();
+        create
+            .metadata(nft.metadata)
+            .master_edition(Some(master_edition))
+            .mint(nft.mint, true)
+            .authority(admin)
+            .payer(admin)
+            .update_authority(admin, true)
+            .spl_token_program(Some(spl_token::ID))
+            .name("Shipment".to_string())
+            .symbol("SHIP".to_string())
+            .uri(String::new())
+            .seller_fee_basis_points(0)
+            .creators(vec![Creator {
+                address: admin,
+                verified: true,
+                share: 100,
+            }])
+            .token_standard(TokenStandard::NonFungible)
+            .print_supply(PrintSupply::Zero);
+        if let Some(key) = collection {
+            create.collection(Collection {
+                verified: false,
+                key,
+            });
+        }
+        let mint_to = MintV1Builder::new()
+            .token(nft.token_account)
+            .token_owner(Some(*owner))
+            .metadata(nft.metadata)
+            .master_edition(Some(master_edition))
+            .mint(nft.mint)
+            .authority(admin)
+            .payer(admin)
+            .amount(1)
+            .instruction();
+        self.process(&[create.instruction(), mint_to], &[&mint])
+            .unwrap();
+        nft
+    }
+
+    /// Creates a 0-decimals mint controlled by `authority`.
+    pub fn create_mint(&mut self, authority: &Pubkey) -> Pubkey {
+        let mint = Keypair::new();
+        let admin = self.super_admin.pubkey();
+        let space = spl_token::state::Mint::LEN;
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &admin,
+                    &mint.pubkey(),
+                    Rent::default().minimum_balance(space),
+                    space as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    authority,
+                    None,
+                    0,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .unwrap();
+        mint.pubkey()
+    }
+
+    /// Creates the associated token account of `owner` holding `amount` of
+    /// `mint`, minted by the super admin.
+    pub fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let admin = self.super_admin.pubkey();
+        let token_account = get_associated_token_address(owner, mint);
+        let mut ixs = vec![create_associated_token_account_idempotent(
+            &admin,
+            owner,
+            mint,
+            &spl_token::ID,
+        )];
+        if amount > 0 {
+            ixs.push(
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    mint,
+                    &token_account,
+                    &admin,
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            );
+        }
+        self.process(&ixs, &[]).unwrap();
+        token_account
+    }
+
+    /// Mints an NFT of the registered collection to `user` and deposits it.
+    pub fn deposit(&mut self, user: &Keypair) -> Nft {
+        let nft = self.mint_nft(&user.pubkey());
+        let collection = self.collection;
+        self.process(
+            &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+            &[user],
+        )
+        .unwrap();
+        nft
+    }
+
+    /// Locks a deposit as the updater.
+    pub fn lock(&mut self, user: &Pubkey, mint: &Pubkey) {
+        let updater = self.updater.insecure_clone();
+        self.process(
+            &[update_deposit_ix(
+                &updater.pubkey(),
+                user,
+                mint,
+                None,
+                Some(true),
+                None,
+                None,
+            )],
+            &[&updater],
+        )
+        .unwrap();
+    }
+
+    pub fn token_balance(&self, token_account: &Pubkey) -> u64 {
+        self.svm.get_account(token_account).map_or(0, |account| {
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount
+        })
+    }
+}
+
+/// Events of type `T` emitted by the last processed transaction.
+pub fn emitted<T: Event>(svm: &Svm) -> Vec<T> {
+    svm.logs()
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(&T::DISCRIMINATOR))
+        .map(|data| T::try_from_slice(&data[8..]).unwrap())
+        .collect()
+}
+
+/// Asserts that a transaction failed with the given program error.
+pub fn assert_shipment_error(result: Result<(), TransactionError>, expected: ShipmentError) {
+    assert_program_error(result, ProgramError::Custom(expected.into()));
+}
+
+pub fn assert_program_error(result: Result<(), TransactionError>, expected: ProgramError) {
+    match result {
+        Ok(()) => panic!("expected {:?}, transaction succeeded", expected),
+        Err(err) => assert_eq!(err.error, expected),
+    }
+}
@@ -0,0 +1,212 @@
+//! Test validator used by the integration tests.
+//!
+//! Transactions run on a `solana-program-test` bank with the SBF builds of
+//! the program and of mpl-token-metadata, next to the system, SPL token,
+//! associated token and ed25519 programs. `cargo test-sbf` builds
+//! `shipment_sc.so` into `SBF_OUT_DIR`, `mpl_token_metadata.so` is the
+//! mainnet program dumped into `tests/fixtures`.
+
+use std::collections::HashSet;
+
+use anchor_lang::{
+    solana_program::{
+        clock::Clock, instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+        system_program,
+    },
+    AccountDeserialize, AccountSerialize,
+};
+use solana_program_test::{
+    tokio::runtime::{Builder, Runtime},
+    ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    message::Message,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError as RuntimeError},
+};
+
+/// Compute budget of a transaction, deposits run several token and
+/// metadata CPIs.
+const COMPUTE_MAX_UNITS: u64 = 1_400_000;
+
+/// Error returned by [`Svm::process`], with the index of the failing instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionError {
+    pub index: usize,
+    pub error: ProgramError,
+}
+
+pub struct Svm {
+    context: ProgramTestContext,
+    runtime: Runtime,
+    clock: Clock,
+    logs: Vec<String>,
+    signatures: HashSet<Signature>,
+}
+
+impl Default for Svm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Svm {
+    pub fn new() -> Self {
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.add_program("shipment_sc", shipment_sc::ID, None);
+        program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+        program_test.set_compute_max_units(COMPUTE_MAX_UNITS);
+        let context = runtime.block_on(program_test.start_with_context());
+
+        Svm {
+            context,
+            runtime,
+            clock: Clock {
+                unix_timestamp: 1_700_000_000,
+                ..Clock::default()
+            },
+            logs: vec![],
+            signatures: HashSet::new(),
+        }
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.context
+            .set_account(&key, &AccountSharedData::from(account));
+    }
+
+    pub fn get_account(&self, key: &Pubkey) -> Option<Account> {
+        let mut banks_client = self.context.banks_client.clone();
+        self.runtime
+            .block_on(banks_client.get_account(*key))
+            .unwrap()
+    }
+
+    /// Deserializes an Anchor or SPL account, panicking if it is missing.
+    pub fn get<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self
+            .get_account(key)
+            .unwrap_or_else(|| panic!("account {} does not exist", key));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Rewrites an existing Anchor account in place, bypassing the program.
+    pub fn update<T: AccountDeserialize + AccountSerialize>(
+        &mut self,
+        key: &Pubkey,
+        f: impl FnOnce(&mut T),
+    ) {
+        let mut value: T = self.get(key);
+        f(&mut value);
+        let mut account = self.get_account(key).unwrap();
+        let mut data = Vec::with_capacity(account.data.len());
+        value.try_serialize(&mut data).unwrap();
+        data.resize(account.data.len().max(data.len()), 0);
+        account.data = data;
+        self.set_account(*key, account);
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.get_account(key).map_or(0, |account| account.lamports)
+    }
+
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let mut account = self.get_account(key).unwrap_or_else(|| Account {
+            owner: system_program::ID,
+            ..Account::default()
+        });
+        account.lamports += lamports;
+        self.set_account(*key, account);
+    }
+
+    pub fn clock(&self) -> Clock {
+        self.clock.clone()
+    }
+
+    pub fn warp_time(&mut self, seconds: i64) {
+        self.clock.unix_timestamp += seconds;
+        self.clock.slot += (seconds as u64).max(1);
+    }
+
+    /// Program logs of the last processed transaction, as the runtime
+    /// reported them.
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// Executes `instructions` in one transaction paid by the test payer,
+    /// signed by whichever of `signers` the instructions require.
+    pub fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        self.logs.clear();
+        let fee_payer = self.context.payer.insecure_clone();
+        let payer = fee_payer.pubkey();
+        for (index, ix) in instructions.iter().enumerate() {
+            for meta in &ix.accounts {
+                if meta.is_signer
+                    && meta.pubkey != payer
+                    && !signers.iter().any(|signer| signer.pubkey() == meta.pubkey)
+                {
+                    return Err(TransactionError {
+                        index,
+                        error: ProgramError::MissingRequiredSignature,
+                    });
+                }
+            }
+        }
+
+        let message = Message::new(instructions, Some(&payer));
+        let keypairs: Vec<&Keypair> = message.account_keys
+            [..message.header.num_required_signatures as usize]
+            .iter()
+            .map(|key| {
+                if *key == payer {
+                    &fee_payer
+                } else {
+                    *signers
+                        .iter()
+                        .find(|signer| signer.pubkey() == *key)
+                        .unwrap()
+                }
+            })
+            .collect();
+        let mut banks_client = self.context.banks_client.clone();
+        let blockhash = self
+            .runtime
+            .block_on(banks_client.get_latest_blockhash())
+            .unwrap();
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.sign(&keypairs, blockhash);
+        // A repeated transaction would be dropped as already processed
+        if !self.signatures.insert(transaction.signatures[0]) {
+            let slot = self.runtime.block_on(banks_client.get_root_slot()).unwrap();
+            self.context.warp_to_slot(slot + 1).unwrap();
+            transaction.sign(&keypairs, self.context.last_blockhash);
+            self.signatures.insert(transaction.signatures[0]);
+        }
+        self.context.set_sysvar(&self.clock);
+
+        let result = self
+            .runtime
+            .block_on(banks_client.process_transaction_with_metadata(transaction))
+            .unwrap();
+        if let Some(metadata) = result.metadata {
+            self.logs = metadata.log_messages;
+        }
+        match result.result {
+            Ok(()) => Ok(()),
+            Err(RuntimeError::InstructionError(index, error)) => Err(TransactionError {
+                index: index as usize,
+                error: ProgramError::try_from(error)
+                    .unwrap_or_else(|error| panic!("instruction {index} failed: {error}")),
+            }),
+            Err(error) => panic!("transaction failed: {error}"),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{
+    error::ShipmentError, event::DepositFinalized, state::*, validations::delivery_proof_message,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+const PROOF: [u8; 32] = [5; 32];
+
+fn require_proof(env: &mut TestEnv) -> Keypair {
+    let oracle = Keypair::new();
+    let admin = env.super_admin.pubkey();
+    env.process(
+        &[instructions::change_delivery_proof(
+            &admin,
+            true,
+            &oracle.pubkey(),
+        )],
+        &[],
+    )
+    .unwrap();
+    oracle
+}
+
+#[test]
+fn change_delivery_proof_requires_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let oracle = Keypair::new();
+
+    let result = env.process(
+        &[instructions::change_delivery_proof(
+            &user.pubkey(),
+            true,
+            &oracle.pubkey(),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    require_proof(&mut env);
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert!(global.delivery_proof_required);
+}
+
+#[test]
+fn finalize_without_proof_mode_needs_no_signature() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+
+    env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &env.collection,
+            &treasury,
+            PROOF,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+}
+
+#[test]
+fn finalize_rejects_missing_proof() {
+    let mut env = TestEnv::new();
+    require_proof(&mut env);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+
+    let result = env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &env.collection,
+            &treasury,
+            PROOF,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::DeliveryProofRequired);
+}
+
+#[test]
+fn finalize_with_owner_signature() {
+    let mut env = TestEnv::new();
+    require_proof(&mut env);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+
+    let mut ix = finalize_deposit_ix(
+        &updater.pubkey(),
+        &user.pubkey(),
+        &nft.mint,
+        &env.collection,
+        &treasury,
+        PROOF,
+    );
+    ix.accounts[1].is_signer = true;
+    env.process(&[ix], &[&updater, &user]).unwrap();
+
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.total_deposit_count, 0);
+}
+
+#[test]
+fn finalize_with_courier_attestation() {
+    let mut env = TestEnv::new();
+    let oracle = require_proof(&mut env);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+
+    env.process(
+        &[
+            ed25519_ix(&oracle, &delivery_proof_message(&nft.mint, &PROOF)),
+            finalize_deposit_ix(
+                &updater.pubkey(),
+                &user.pubkey(),
+                &nft.mint,
+                &env.collection,
+                &treasury,
+                PROOF,
+            ),
+        ],
+        &[&updater],
+    )
+    .unwrap();
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+
+    // Proof outlives the closed deposit account in the finalize event
+    let events: Vec<DepositFinalized> = emitted(&env.svm);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].mint, nft.mint);
+    assert_eq!(events[0].owner, user.pubkey());
+    assert_eq!(events[0].collection, env.collection);
+    assert_eq!(events[0].user, UUID);
+    assert_eq!(events[0].delivery_proof, PROOF);
+}
+
+#[test]
+fn finalize_rejects_invalid_attestation() {
+    let mut env = TestEnv::new();
+    let oracle = require_proof(&mut env);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+    let finalize = finalize_deposit_ix(
+        &updater.pubkey(),
+        &user.pubkey(),
+        &nft.mint,
+        &env.collection,
+        &treasury,
+        PROOF,
+    );
+
+    // Signed by a key other than the courier oracle
+    let result = env.process(
+        &[
+            ed25519_ix(&Keypair::new(), &delivery_proof_message(&nft.mint, &PROOF)),
+            finalize.clone(),
+        ],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDeliveryProof);
+
+    // Attestation for a different proof hash
+    let result = env.process(
+        &[
+            ed25519_ix(&oracle, &delivery_proof_message(&nft.mint, &[6; 32])),
+            finalize.clone(),
+        ],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDeliveryProof);
+
+    // Attestation for another mint
+    let other = solana_sdk::pubkey::Pubkey::new_unique();
+    let result = env.process(
+        &[
+            ed25519_ix(&oracle, &delivery_proof_message(&other, &PROOF)),
+            finalize,
+        ],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDeliveryProof);
+}
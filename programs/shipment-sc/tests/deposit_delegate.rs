@@ -0,0 +1,277 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{
+    account::Account,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn register(env: &mut TestEnv, beneficiary: &Keypair, depositor: &Keypair) {
+    env.process(
+        &[instructions::register_deposit_delegate(
+            &beneficiary.pubkey(),
+            &depositor.pubkey(),
+        )],
+        &[beneficiary],
+    )
+    .unwrap();
+}
+
+#[test]
+fn cosigned_deposit_belongs_to_beneficiary() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let partner = env.new_user();
+    let beneficiary = Keypair::new();
+    let nft = env.mint_nft(&partner.pubkey());
+
+    let ix = deposit_nft_for_ix(
+        &partner.pubkey(),
+        &beneficiary.pubkey(),
+        &nft,
+        &collection,
+        UUID,
+        false,
+    );
+    env.process(&[ix], &[&partner, &beneficiary]).unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, beneficiary.pubkey());
+    assert_eq!(deposit.payer, partner.pubkey());
+    assert_eq!(deposit.user, UUID);
+    let beneficiary_pool: UserPool = env.svm.get(&pda::user_pool(&beneficiary.pubkey()).0);
+    assert_eq!(beneficiary_pool.address, beneficiary.pubkey());
+    assert_eq!(beneficiary_pool.deposit_count, 1);
+    let partner_pool: UserPool = env.svm.get(&pda::user_pool(&partner.pubkey()).0);
+    assert_eq!(partner_pool.deposit_count, 0);
+}
+
+#[test]
+fn deposit_requires_beneficiary_consent() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let partner = env.new_user();
+    let beneficiary = Keypair::new();
+    let nft = env.mint_nft(&partner.pubkey());
+
+    // Beneficiary neither signs nor has a delegate account
+    let mut ix = deposit_nft_for_ix(
+        &partner.pubkey(),
+        &beneficiary.pubkey(),
+        &nft,
+        &collection,
+        UUID,
+        false,
+    );
+    ix.accounts[1].is_signer = false;
+    let result = env.process(&[ix], &[&partner]);
+    assert_shipment_error(result, ShipmentError::DepositConsentRequired);
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+}
+
+#[test]
+fn delegate_deposits_and_beneficiary_withdraws() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let partner = env.new_user();
+    let beneficiary = env.new_user();
+    register(&mut env, &beneficiary, &partner);
+    let delegate: DepositDelegate = env
+        .svm
+        .get(&pda::deposit_delegate(&beneficiary.pubkey(), &partner.pubkey()).0);
+    assert_eq!(delegate.beneficiary, beneficiary.pubkey());
+    assert_eq!(delegate.delegate, partner.pubkey());
+
+    let nft = env.mint_nft(&partner.pubkey());
+    env.process(
+        &[deposit_nft_for_ix(
+            &partner.pubkey(),
+            &beneficiary.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            true,
+        )],
+        &[&partner],
+    )
+    .unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, beneficiary.pubkey());
+
+    // The partner holds no claim on the deposit
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &partner.pubkey(),
+            &partner.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&partner],
+    );
+    assert!(result.is_err());
+
+    env.process(
+        &[withdraw_owner_ix(
+            &beneficiary.pubkey(),
+            &beneficiary.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&beneficiary],
+    )
+    .unwrap();
+    let beneficiary_account = get_associated_token_address(&beneficiary.pubkey(), &nft.mint);
+    assert_eq!(env.token_balance(&beneficiary_account), 1);
+    assert_eq!(env.token_balance(&nft.token_account), 0);
+}
+
+#[test]
+fn revoked_delegate_cannot_deposit() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let partner = env.new_user();
+    let beneficiary = env.new_user();
+    register(&mut env, &beneficiary, &partner);
+    let key = pda::deposit_delegate(&beneficiary.pubkey(), &partner.pubkey()).0;
+    let rent = env.svm.lamports(&key);
+    let balance = env.svm.lamports(&beneficiary.pubkey());
+
+    env.process(
+        &[instructions::revoke_deposit_delegate(
+            &beneficiary.pubkey(),
+            &partner.pubkey(),
+        )],
+        &[&beneficiary],
+    )
+    .unwrap();
+    assert!(env.svm.get_account(&key).is_none());
+    assert_eq!(env.svm.lamports(&beneficiary.pubkey()), balance + rent);
+
+    let nft = env.mint_nft(&partner.pubkey());
+    let result = env.process(
+        &[deposit_nft_for_ix(
+            &partner.pubkey(),
+            &beneficiary.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            true,
+        )],
+        &[&partner],
+    );
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::AccountNotInitialized.into()),
+    );
+}
+
+#[test]
+fn delegate_is_bound_to_its_depositor() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let partner = env.new_user();
+    let other = env.new_user();
+    let beneficiary = env.new_user();
+    register(&mut env, &beneficiary, &other);
+
+    let nft = env.mint_nft(&partner.pubkey());
+    let mut ix = deposit_nft_for_ix(
+        &partner.pubkey(),
+        &beneficiary.pubkey(),
+        &nft,
+        &collection,
+        UUID,
+        true,
+    );
+    ix.accounts[2].pubkey = pda::deposit_delegate(&beneficiary.pubkey(), &other.pubkey()).0;
+    let result = env.process(&[ix], &[&partner]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintSeeds.into()),
+    );
+}
+
+#[test]
+fn deposit_for_rejects_spoofed_metadata() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let partner = env.new_user();
+    let beneficiary = env.new_user();
+    register(&mut env, &beneficiary, &partner);
+    let nft = env.mint_nft(&partner.pubkey());
+    let metadata = env.svm.get_account(&nft.metadata).unwrap();
+
+    // Metadata naming the collection, at an address other than the mint's metadata PDA
+    let spoofed = Pubkey::new_unique();
+    env.svm.set_account(spoofed, metadata.clone());
+    let mut ix = deposit_nft_for_ix(
+        &partner.pubkey(),
+        &beneficiary.pubkey(),
+        &nft,
+        &collection,
+        UUID,
+        true,
+    );
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == nft.metadata {
+            meta.pubkey = spoofed;
+        }
+    }
+    let result = env.process(&[ix], &[&partner]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintAddress.into()),
+    );
+
+    // Token metadata program swapped for another one
+    let mut ix = deposit_nft_for_ix(
+        &partner.pubkey(),
+        &beneficiary.pubkey(),
+        &nft,
+        &collection,
+        UUID,
+        true,
+    );
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == mpl_token_metadata::ID {
+            meta.pubkey = Pubkey::new_unique();
+        }
+    }
+    let result = env.process(&[ix], &[&partner]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintAddress.into()),
+    );
+
+    // Metadata PDA not written by the token metadata program
+    env.svm.set_account(
+        nft.metadata,
+        Account {
+            owner: Pubkey::new_unique(),
+            ..metadata
+        },
+    );
+    let result = env.process(
+        &[deposit_nft_for_ix(
+            &partner.pubkey(),
+            &beneficiary.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            true,
+        )],
+        &[&partner],
+    );
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::ConstraintOwner.into()),
+    );
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+}
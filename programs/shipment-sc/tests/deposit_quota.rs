@@ -0,0 +1,219 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{constant::*, error::ShipmentError, state::*};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn quota(max_concurrent_deposits: u64, max_daily_deposits: u64) -> DepositQuota {
+    DepositQuota {
+        max_concurrent_deposits,
+        max_daily_deposits,
+    }
+}
+
+fn try_deposit(env: &mut TestEnv, user: &Keypair) -> Result<(), TransactionError> {
+    let nft = env.mint_nft(&user.pubkey());
+    let collection = env.collection;
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[user],
+    )
+}
+
+#[test]
+fn concurrent_quota_limits_active_deposits() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    env.process(
+        &[instructions::change_deposit_quota(&admin, quota(2, 0))],
+        &[],
+    )
+    .unwrap();
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.deposit_quota, quota(2, 0));
+
+    let first = env.deposit(&user);
+    env.deposit(&user);
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+
+    // Withdrawing frees a slot
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &first.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    try_deposit(&mut env, &user).unwrap();
+
+    // Quota is per user
+    let other = env.new_user();
+    try_deposit(&mut env, &other).unwrap();
+}
+
+#[test]
+fn daily_quota_resets_after_window() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    env.process(
+        &[instructions::change_deposit_quota(&admin, quota(0, 2))],
+        &[],
+    )
+    .unwrap();
+
+    let first = env.deposit(&user);
+    env.deposit(&user);
+    let now = env.svm.clock().unix_timestamp;
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.deposits_in_window(now), 2);
+    assert_eq!(
+        pool.recent_deposits.iter().filter(|t| **t == now).count(),
+        2
+    );
+
+    // Withdrawals do not give back daily deposits
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &first.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW - 1);
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+
+    env.svm.warp_time(1);
+    try_deposit(&mut env, &user).unwrap();
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.deposits_in_window(env.svm.clock().unix_timestamp), 1);
+}
+
+#[test]
+fn daily_quota_rolls_over_deposit_times() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let user = env.new_user();
+
+    env.process(
+        &[instructions::change_deposit_quota(&admin, quota(0, 2))],
+        &[],
+    )
+    .unwrap();
+
+    // A fixed window would reset here and allow a burst across its boundary
+    env.deposit(&user);
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW - 60);
+    env.deposit(&user);
+    env.svm.warp_time(60);
+    try_deposit(&mut env, &user).unwrap();
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+
+    // Only once the second deposit leaves the window is a slot free again
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW - 61);
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+    env.svm.warp_time(1);
+    try_deposit(&mut env, &user).unwrap();
+}
+
+#[test]
+fn daily_quota_is_bounded_by_tracked_deposits() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let over = quota(0, MAX_DAILY_DEPOSITS as u64 + 1);
+
+    let result = env.process(&[instructions::change_deposit_quota(&admin, over)], &[]);
+    assert_shipment_error(result, ShipmentError::InvalidDepositQuota);
+    let result = env.process(
+        &[instructions::change_collection_deposit_quota(
+            &admin,
+            &collection,
+            over,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDepositQuota);
+
+    let max = quota(0, MAX_DAILY_DEPOSITS as u64);
+    env.process(&[instructions::change_deposit_quota(&admin, max)], &[])
+        .unwrap();
+}
+
+#[test]
+fn collection_quota_overrides_global_quota() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    env.process(
+        &[
+            instructions::change_deposit_quota(&admin, quota(1, 1)),
+            instructions::change_collection_deposit_quota(&admin, &collection, quota(3, 0)),
+        ],
+        &[],
+    )
+    .unwrap();
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.deposit_quota, quota(3, 0));
+
+    // Concurrent limit comes from the collection, daily limit from the global quota
+    env.deposit(&user);
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW);
+    try_deposit(&mut env, &user).unwrap();
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW);
+    try_deposit(&mut env, &user).unwrap();
+    env.svm.warp_time(DEPOSIT_QUOTA_WINDOW);
+    let result = try_deposit(&mut env, &user);
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+}
+
+#[test]
+fn deposit_quota_requires_admin() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::change_deposit_quota(
+            &user.pubkey(),
+            quota(1, 1),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let result = env.process(
+        &[instructions::change_collection_deposit_quota(
+            &user.pubkey(),
+            &collection,
+            quota(1, 1),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
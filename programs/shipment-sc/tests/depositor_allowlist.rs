@@ -0,0 +1,203 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+#[test]
+fn global_root_requires_depositor_proof() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+    let (root, proof) = merkle_tree([
+        user.pubkey(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ]);
+
+    env.process(
+        &[instructions::change_depositor_merkle_root(&admin, root)],
+        &[],
+    )
+    .unwrap();
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.depositor_merkle_root, root);
+
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DepositorNotAllowed);
+
+    env.process(
+        &[approved_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            Some(proof),
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 1);
+    assert_eq!(deposit.owner, user.pubkey());
+}
+
+#[test]
+fn unapproved_depositor_is_rejected() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let approved = env.new_user();
+    let other = env.new_user();
+    let nft = env.mint_nft(&other.pubkey());
+    let (root, proof) = merkle_tree([
+        approved.pubkey(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ]);
+
+    env.process(
+        &[instructions::change_depositor_merkle_root(&admin, root)],
+        &[],
+    )
+    .unwrap();
+
+    // Proof of another wallet does not approve the signer
+    let result = env.process(
+        &[approved_deposit_nft_ix(
+            &other.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            Some(proof),
+        )],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::DepositorNotAllowed);
+}
+
+#[test]
+fn collection_root_overrides_global_root() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+    let (global_root, global_proof) = merkle_tree([
+        user.pubkey(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ]);
+    let (collection_root, collection_proof) = merkle_tree([
+        user.pubkey(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ]);
+
+    env.process(
+        &[
+            instructions::change_depositor_merkle_root(&admin, global_root),
+            instructions::change_collection_depositor_merkle_root(
+                &admin,
+                &collection,
+                collection_root,
+            ),
+        ],
+        &[],
+    )
+    .unwrap();
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.depositor_merkle_root, collection_root);
+
+    let result = env.process(
+        &[approved_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            Some(global_proof),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DepositorNotAllowed);
+
+    env.process(
+        &[approved_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            Some(collection_proof),
+        )],
+        &[&user],
+    )
+    .unwrap();
+}
+
+#[test]
+fn clearing_root_reopens_deposits() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+
+    env.process(
+        &[instructions::change_depositor_merkle_root(&admin, [1; 32])],
+        &[],
+    )
+    .unwrap();
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DepositorNotAllowed);
+
+    env.process(
+        &[instructions::change_depositor_merkle_root(&admin, [0; 32])],
+        &[],
+    )
+    .unwrap();
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    )
+    .unwrap();
+}
+
+#[test]
+fn depositor_root_requires_admin() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::change_depositor_merkle_root(
+            &user.pubkey(),
+            [1; 32],
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let result = env.process(
+        &[instructions::change_collection_depositor_merkle_root(
+            &user.pubkey(),
+            &collection,
+            [1; 32],
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
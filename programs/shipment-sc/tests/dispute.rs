@@ -0,0 +1,418 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use shipment_sc::{error::ShipmentError, event::DisputeResolved, state::*};
+use solana_sdk::{
+    program_error::ProgramError,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+const EVIDENCE: [u8; 32] = [7; 32];
+const RESOLUTION: [u8; 32] = [9; 32];
+
+fn disputed_deposit(env: &mut TestEnv) -> (Keypair, Nft) {
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            EVIDENCE,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    (user, nft)
+}
+
+#[test]
+fn open_dispute_records_evidence() {
+    let mut env = TestEnv::new();
+    let (user, nft) = disputed_deposit(&mut env);
+
+    let dispute: Dispute = env.svm.get(&pda::dispute(&nft.mint).0);
+    assert_eq!(dispute.owner, user.pubkey());
+    assert_eq!(dispute.mint, nft.mint);
+    assert_eq!(dispute.opened, env.svm.clock().unix_timestamp);
+    assert_eq!(dispute.evidence_hash, EVIDENCE);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert!(deposit.disputed);
+
+    let result = env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            EVIDENCE,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DepositDisputed);
+}
+
+#[test]
+fn only_owner_can_open_dispute() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let result = env.process(
+        &[instructions::open_dispute(
+            &other.pubkey(),
+            &nft.mint,
+            EVIDENCE,
+        )],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+}
+
+#[test]
+fn dispute_freezes_withdrawal_and_finalize() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let (user, nft) = disputed_deposit(&mut env);
+    let admin = env.super_admin.pubkey();
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DepositDisputed);
+
+    let result = env.process(
+        &[withdraw_treasury_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::DepositDisputed);
+
+    let result = env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &env.collection,
+            &treasury,
+            [0; 32],
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::DepositDisputed);
+
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+    let result = env.process(
+        &[approve_withdrawal_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::DepositDisputed);
+}
+
+#[test]
+fn resolve_returns_to_owner() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let (user, nft) = disputed_deposit(&mut env);
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+    let opened = env.svm.clock().unix_timestamp;
+    let rent = env.svm.lamports(&pda::dispute(&nft.mint).0);
+    let balance = env.svm.lamports(&user.pubkey());
+
+    env.svm.warp_time(60);
+    env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            Some(nft.token_account),
+            DisputeOutcome::ReturnToOwner,
+            RESOLUTION,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+    // Resolution outlives the closed dispute account in the resolve event
+    let events: Vec<DisputeResolved> = emitted(&env.svm);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].mint, nft.mint);
+    assert_eq!(events[0].owner, user.pubkey());
+    assert_eq!(events[0].opened, opened);
+    assert_eq!(events[0].resolved, env.svm.clock().unix_timestamp);
+    assert_eq!(events[0].evidence_hash, EVIDENCE);
+    assert_eq!(events[0].resolution_hash, RESOLUTION);
+    assert_eq!(events[0].outcome, DisputeOutcome::ReturnToOwner);
+    assert!(env.svm.get_account(&pda::dispute(&nft.mint).0).is_none());
+    assert_eq!(env.svm.lamports(&user.pubkey()), balance + rent);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 0);
+    assert!(!deposit.disputed);
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.deposit_count, 0);
+}
+
+#[test]
+fn resolve_sends_to_treasury() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let (user, nft) = disputed_deposit(&mut env);
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+    let treasury_ata = get_associated_token_address(&treasury, &nft.mint);
+
+    // Owner token account is rejected for the treasury outcome
+    let result = env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            Some(nft.token_account),
+            DisputeOutcome::SendToTreasury,
+            RESOLUTION,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDestination);
+
+    env.process(
+        &[
+            create_associated_token_account_idempotent(
+                &admin,
+                &treasury,
+                &nft.mint,
+                &anchor_spl::token::ID,
+            ),
+            resolve_dispute_ix(
+                &admin,
+                &user.pubkey(),
+                &nft.mint,
+                &collection,
+                &treasury,
+                Some(treasury_ata),
+                DisputeOutcome::SendToTreasury,
+                RESOLUTION,
+            ),
+        ],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(env.token_balance(&treasury_ata), 1);
+    let events: Vec<DisputeResolved> = emitted(&env.svm);
+    assert_eq!(events[0].outcome, DisputeOutcome::SendToTreasury);
+    assert!(env.svm.get_account(&pda::dispute(&nft.mint).0).is_none());
+}
+
+#[test]
+fn resolve_finalizes() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let (user, nft) = disputed_deposit(&mut env);
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+
+    let result = env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            None,
+            DisputeOutcome::ReturnToOwner,
+            RESOLUTION,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDestination);
+
+    env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            None,
+            DisputeOutcome::Finalize,
+            RESOLUTION,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.total_deposit_count, 0);
+    let events: Vec<DisputeResolved> = emitted(&env.svm);
+    assert_eq!(events[0].outcome, DisputeOutcome::Finalize);
+    assert!(env.svm.get_account(&pda::dispute(&nft.mint).0).is_none());
+}
+
+#[test]
+fn resolve_requires_admin_and_open_dispute() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+
+    env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            EVIDENCE,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let result = env.process(
+        &[resolve_dispute_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            Some(nft.token_account),
+            DisputeOutcome::ReturnToOwner,
+            RESOLUTION,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            Some(nft.token_account),
+            DisputeOutcome::ReturnToOwner,
+            RESOLUTION,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    // Re-deposited nft can be disputed again, but not resolved before opening
+    let collection = env.collection;
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    )
+    .unwrap();
+    let result = env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            Some(nft.token_account),
+            DisputeOutcome::ReturnToOwner,
+            RESOLUTION,
+        )],
+        &[],
+    );
+    // No dispute account until one is opened
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::AccountNotInitialized.into()),
+    );
+    env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            [1; 32],
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let dispute: Dispute = env.svm.get(&pda::dispute(&nft.mint).0);
+    assert_eq!(dispute.evidence_hash, [1; 32]);
+}
+
+#[test]
+fn dispute_reopens_after_resolution() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let (user, nft) = disputed_deposit(&mut env);
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+    let resolve = resolve_dispute_ix(
+        &admin,
+        &user.pubkey(),
+        &nft.mint,
+        &collection,
+        &treasury,
+        Some(nft.token_account),
+        DisputeOutcome::ReturnToOwner,
+        RESOLUTION,
+    );
+    env.process(std::slice::from_ref(&resolve), &[]).unwrap();
+
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    )
+    .unwrap();
+    env.svm.warp_time(60);
+    env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            [1; 32],
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let dispute: Dispute = env.svm.get(&pda::dispute(&nft.mint).0);
+    assert_eq!(dispute.owner, user.pubkey());
+    assert_eq!(dispute.opened, env.svm.clock().unix_timestamp);
+    assert_eq!(dispute.evidence_hash, [1; 32]);
+
+    env.process(&[resolve], &[]).unwrap();
+    let events: Vec<DisputeResolved> = emitted(&env.svm);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].evidence_hash, [1; 32]);
+    assert!(env.svm.get_account(&pda::dispute(&nft.mint).0).is_none());
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+}
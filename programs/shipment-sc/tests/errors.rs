@@ -0,0 +1,285 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{account::Account, signature::Signer};
+
+#[test]
+fn invalid_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::change_treasury(
+            &user.pubkey(),
+            &user.pubkey(),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn invalid_updater() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let result = env.process(
+        &[update_deposit_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            Some(2),
+            None,
+            None,
+            None,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidUpdater);
+}
+
+#[test]
+fn invalid_metadata() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+    // Metadata PDA holding bytes the token metadata program never wrote
+    let account = env.svm.get_account(&nft.metadata).unwrap();
+    env.svm.set_account(
+        nft.metadata,
+        Account {
+            data: vec![0xff; 16],
+            ..account
+        },
+    );
+
+    let collection = env.collection;
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidMetadata);
+}
+
+#[test]
+fn invalid_collection() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let other = env.create_collection();
+    let nft = env.mint_nft_with(&user.pubkey(), Some(other), true);
+
+    let collection = env.collection;
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidCollection);
+}
+
+#[test]
+fn metadata_creator_parse_error() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.mint_nft_with(&user.pubkey(), None, false);
+
+    let collection = env.collection;
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::MetadataCreatorParseError);
+}
+
+#[test]
+fn already_deposited() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let collection = env.collection;
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::AlreadyDeposited);
+}
+
+#[test]
+fn not_deposited() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    let updater = env.updater.insecure_clone();
+    let result = env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            Some(2),
+            None,
+            None,
+            None,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::NotDeposited);
+}
+
+#[test]
+fn status_stays_set_in_custody() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let updater = env.updater.insecure_clone();
+    let result = env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            Some(0),
+            None,
+            None,
+            None,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidDepositStatus);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 1);
+}
+
+#[test]
+fn invalid_owner() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let admin = env.super_admin.pubkey();
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &admin,
+            &other.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+}
+
+#[test]
+fn invalid_nft_address() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.svm
+        .update::<NftDeposit>(&pda::nft_deposit(&nft.mint).0, |deposit| {
+            deposit.mint = solana_sdk::pubkey::Pubkey::new_unique();
+        });
+
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidNFTAddress);
+}
+
+#[test]
+fn disabled_withdrawal() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let updater = env.updater.insecure_clone();
+    env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            None,
+            Some(true),
+            None,
+            None,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DisabledWithdrawal);
+}
+
+#[test]
+fn math_overflow_on_deposit() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    env.svm
+        .update::<UserPool>(&pda::user_pool(&user.pubkey()).0, |pool| {
+            pool.deposit_count = u64::MAX;
+        });
+    let nft = env.mint_nft(&user.pubkey());
+
+    let collection = env.collection;
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::MathOverflow);
+}
+
+#[test]
+fn math_overflow_on_withdraw() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.svm
+        .update::<UserPool>(&pda::user_pool(&user.pubkey()).0, |pool| {
+            pool.deposit_count = 0;
+        });
+
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::MathOverflow);
+}
@@ -0,0 +1,602 @@
+//! Random deposit lifecycles by random signers, checked against a model of
+//! where every NFT should be after each step.
+
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use common::*;
+use proptest::prelude::*;
+use shipment_sc::state::*;
+use solana_sdk::{
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const SUPER_ADMIN: usize = 0;
+const UPDATER: usize = 1;
+const ADMIN: usize = 2;
+/// Super admin, updater, admin and plain users.
+const ACTORS: usize = 6;
+/// NFTs minted to each plain user.
+const NFTS_PER_USER: usize = 2;
+const NFTS: usize = (ACTORS - ADMIN - 1) * NFTS_PER_USER;
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Deposit {
+        nft: usize,
+        signer: usize,
+    },
+    DepositFor {
+        nft: usize,
+        signer: usize,
+        beneficiary: usize,
+    },
+    Update {
+        nft: usize,
+        signer: usize,
+        status: Option<u8>,
+        locked: Option<bool>,
+    },
+    RequestWithdrawal {
+        nft: usize,
+        signer: usize,
+    },
+    ApproveWithdrawal {
+        nft: usize,
+        signer: usize,
+    },
+    WithdrawOwner {
+        nft: usize,
+        signer: usize,
+        owner: usize,
+    },
+    WithdrawTreasury {
+        nft: usize,
+        signer: usize,
+        owner: usize,
+    },
+    Finalize {
+        nft: usize,
+        signer: usize,
+        owner: usize,
+    },
+    Transfer {
+        nft: usize,
+        signer: usize,
+        new_owner: usize,
+    },
+    OpenDispute {
+        nft: usize,
+        signer: usize,
+    },
+    ResolveDispute {
+        nft: usize,
+        signer: usize,
+        outcome: DisputeOutcome,
+    },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let nft = 0..NFTS;
+    let actor = 0..ACTORS;
+    let outcome = prop_oneof![
+        Just(DisputeOutcome::ReturnToOwner),
+        Just(DisputeOutcome::SendToTreasury),
+        Just(DisputeOutcome::Finalize),
+    ];
+    prop_oneof![
+        3 => (nft.clone(), actor.clone()).prop_map(|(nft, signer)| Op::Deposit { nft, signer }),
+        1 => (nft.clone(), actor.clone(), actor.clone()).prop_map(|(nft, signer, beneficiary)| {
+            Op::DepositFor { nft, signer, beneficiary }
+        }),
+        2 => (
+            nft.clone(),
+            actor.clone(),
+            prop::option::of(0..3u8),
+            prop::option::of(any::<bool>()),
+        )
+            .prop_map(|(nft, signer, status, locked)| Op::Update { nft, signer, status, locked }),
+        1 => (nft.clone(), actor.clone())
+            .prop_map(|(nft, signer)| Op::RequestWithdrawal { nft, signer }),
+        1 => (nft.clone(), actor.clone())
+            .prop_map(|(nft, signer)| Op::ApproveWithdrawal { nft, signer }),
+        2 => (nft.clone(), actor.clone(), actor.clone())
+            .prop_map(|(nft, signer, owner)| Op::WithdrawOwner { nft, signer, owner }),
+        1 => (nft.clone(), actor.clone(), actor.clone())
+            .prop_map(|(nft, signer, owner)| Op::WithdrawTreasury { nft, signer, owner }),
+        1 => (nft.clone(), actor.clone(), actor.clone())
+            .prop_map(|(nft, signer, owner)| Op::Finalize { nft, signer, owner }),
+        1 => (nft.clone(), actor.clone(), actor.clone())
+            .prop_map(|(nft, signer, new_owner)| Op::Transfer { nft, signer, new_owner }),
+        1 => (nft.clone(), actor.clone())
+            .prop_map(|(nft, signer)| Op::OpenDispute { nft, signer }),
+        1 => (nft, actor, outcome)
+            .prop_map(|(nft, signer, outcome)| Op::ResolveDispute { nft, signer, outcome }),
+    ]
+}
+
+/// Where an NFT is expected to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Wallet {
+        holder: usize,
+    },
+    Custody {
+        owner: usize,
+        locked: bool,
+        requested: bool,
+        disputed: bool,
+    },
+    Treasury,
+    Burned,
+}
+
+struct Harness {
+    env: TestEnv,
+    actors: Vec<Keypair>,
+    nfts: Vec<Nft>,
+    locations: Vec<Location>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let mut env = TestEnv::new();
+        let super_admin = env.super_admin.pubkey();
+        let mut actors = vec![
+            env.super_admin.insecure_clone(),
+            env.updater.insecure_clone(),
+        ];
+        while actors.len() < ACTORS {
+            actors.push(env.new_user());
+        }
+        env.process(
+            &[instructions::change_role(
+                &super_admin,
+                &actors[ADMIN].pubkey(),
+                Some(true),
+                None,
+            )],
+            &[],
+        )
+        .unwrap();
+
+        let holders: Vec<usize> = (ADMIN + 1..ACTORS)
+            .flat_map(|holder| std::iter::repeat_n(holder, NFTS_PER_USER))
+            .collect();
+        let nfts: Vec<Nft> = holders
+            .iter()
+            .map(|holder| env.mint_nft(&actors[*holder].pubkey()))
+            .collect();
+        // Empty token accounts, so NFTs can be returned to any wallet
+        let treasury = env.treasury;
+        for (nft, holder) in nfts.iter().zip(&holders) {
+            for (actor, keypair) in actors.iter().enumerate() {
+                if actor != *holder {
+                    env.create_token_account(&nft.mint, &keypair.pubkey(), 0);
+                }
+            }
+            env.create_token_account(&nft.mint, &treasury, 0);
+        }
+        Harness {
+            env,
+            actors,
+            nfts,
+            locations: holders
+                .into_iter()
+                .map(|holder| Location::Wallet { holder })
+                .collect(),
+        }
+    }
+
+    fn key(&self, actor: usize) -> Pubkey {
+        self.actors[actor].pubkey()
+    }
+
+    fn is_admin(actor: usize) -> bool {
+        actor == SUPER_ADMIN || actor == ADMIN
+    }
+
+    fn is_updater(actor: usize) -> bool {
+        Self::is_admin(actor) || actor == UPDATER
+    }
+
+    /// Runs `op` and, if it succeeded, checks it was authorized and moves
+    /// the NFT in the model.
+    fn apply(&mut self, op: &Op) -> Result<(), TestCaseError> {
+        let collection = self.env.collection;
+        let treasury = self.env.treasury;
+        let (nft_index, signer) = match *op {
+            Op::Deposit { nft, signer }
+            | Op::DepositFor { nft, signer, .. }
+            | Op::Update { nft, signer, .. }
+            | Op::RequestWithdrawal { nft, signer }
+            | Op::ApproveWithdrawal { nft, signer }
+            | Op::WithdrawOwner { nft, signer, .. }
+            | Op::WithdrawTreasury { nft, signer, .. }
+            | Op::Finalize { nft, signer, .. }
+            | Op::Transfer { nft, signer, .. }
+            | Op::OpenDispute { nft, signer }
+            | Op::ResolveDispute { nft, signer, .. } => (nft, signer),
+        };
+        let nft = self.nfts[nft_index];
+        let signer_key = self.key(signer);
+        let location = self.locations[nft_index];
+        // Deposit owner the op acts on, or the wallet holding the nft
+        let owner = match location {
+            Location::Custody { owner, .. } | Location::Wallet { holder: owner } => owner,
+            Location::Treasury | Location::Burned => signer,
+        };
+        let owner_key = self.key(owner);
+        let mut signers = vec![self.actors[signer].insecure_clone()];
+
+        let ix = match *op {
+            Op::Deposit { .. } => deposit_nft_ix(&signer_key, &nft, &collection, UUID),
+            Op::DepositFor { beneficiary, .. } => {
+                let beneficiary_key = self.key(beneficiary);
+                let ix = deposit_nft_for_ix(
+                    &signer_key,
+                    &beneficiary_key,
+                    &nft,
+                    &collection,
+                    UUID,
+                    false,
+                );
+                signers.push(self.actors[beneficiary].insecure_clone());
+                ix
+            }
+            Op::Update { status, locked, .. } => update_deposit_ix(
+                &signer_key,
+                &owner_key,
+                &nft.mint,
+                status,
+                locked,
+                None,
+                None,
+            ),
+            Op::RequestWithdrawal { .. } => {
+                instructions::request_withdrawal(&signer_key, &nft.mint)
+            }
+            Op::ApproveWithdrawal { .. } => {
+                approve_withdrawal_ix(&signer_key, &owner_key, &nft.mint, &collection)
+            }
+            Op::WithdrawOwner { owner, .. } => {
+                withdraw_owner_ix(&signer_key, &self.key(owner), &nft.mint, &collection)
+            }
+            Op::WithdrawTreasury { owner, .. } => withdraw_treasury_ix(
+                &signer_key,
+                &self.key(owner),
+                &nft.mint,
+                &collection,
+                &treasury,
+            ),
+            Op::Finalize { owner, .. } => finalize_deposit_ix(
+                &signer_key,
+                &self.key(owner),
+                &nft.mint,
+                &collection,
+                &treasury,
+                [0; 32],
+            ),
+            Op::Transfer { new_owner, .. } => transfer_deposit_ownership_ix(
+                &signer_key,
+                &self.key(new_owner),
+                &nft.mint,
+                &collection,
+                None,
+                false,
+                UUID,
+            ),
+            Op::OpenDispute { .. } => instructions::open_dispute(&signer_key, &nft.mint, [1; 32]),
+            Op::ResolveDispute { outcome, .. } => {
+                let destination = match outcome {
+                    DisputeOutcome::ReturnToOwner => Some(&owner_key),
+                    DisputeOutcome::SendToTreasury => Some(&treasury),
+                    DisputeOutcome::Finalize => None,
+                };
+                resolve_dispute_ix(
+                    &signer_key,
+                    &owner_key,
+                    &nft.mint,
+                    &collection,
+                    &treasury,
+                    destination.map(|wallet| get_associated_token_address(wallet, &nft.mint)),
+                    outcome,
+                    [2; 32],
+                )
+            }
+        };
+        let signers: Vec<&Keypair> = signers.iter().collect();
+        if self.env.process(&[ix], &signers).is_err() {
+            return Ok(());
+        }
+
+        let next = match (*op, location) {
+            (Op::Deposit { .. }, Location::Wallet { holder }) => {
+                prop_assert_eq!(signer, holder, "deposit of another wallet's nft");
+                Location::Custody {
+                    owner: signer,
+                    locked: false,
+                    requested: false,
+                    disputed: false,
+                }
+            }
+            (Op::DepositFor { beneficiary, .. }, Location::Wallet { holder }) => {
+                prop_assert_eq!(signer, holder, "deposit of another wallet's nft");
+                Location::Custody {
+                    owner: beneficiary,
+                    locked: false,
+                    requested: false,
+                    disputed: false,
+                }
+            }
+            (
+                Op::Update { status, locked, .. },
+                Location::Custody {
+                    owner,
+                    locked: was_locked,
+                    requested,
+                    disputed,
+                },
+            ) => {
+                prop_assert!(Self::is_updater(signer), "update by non updater");
+                prop_assert_ne!(status, Some(0), "status cleared in custody");
+                Location::Custody {
+                    owner,
+                    locked: locked.unwrap_or(was_locked),
+                    requested,
+                    disputed,
+                }
+            }
+            (
+                Op::RequestWithdrawal { .. },
+                Location::Custody {
+                    owner,
+                    locked,
+                    requested,
+                    disputed,
+                },
+            ) => {
+                prop_assert_eq!(signer, owner, "withdrawal request by non owner");
+                prop_assert!(!requested, "withdrawal requested twice");
+                Location::Custody {
+                    owner,
+                    locked,
+                    requested: true,
+                    disputed,
+                }
+            }
+            (
+                Op::ApproveWithdrawal { .. },
+                Location::Custody {
+                    owner,
+                    requested,
+                    disputed,
+                    ..
+                },
+            ) => {
+                prop_assert!(Self::is_updater(signer), "approval by non updater");
+                prop_assert!(requested, "approval of an unrequested withdrawal");
+                prop_assert!(!disputed, "approval of a disputed deposit");
+                Location::Wallet { holder: owner }
+            }
+            (
+                Op::WithdrawOwner { owner: claimed, .. },
+                Location::Custody {
+                    owner,
+                    locked,
+                    disputed,
+                    ..
+                },
+            ) => {
+                prop_assert_eq!(claimed, owner, "withdrawal to a non owner");
+                prop_assert!(
+                    signer == owner || Self::is_admin(signer),
+                    "withdrawal by non owner"
+                );
+                prop_assert!(!locked, "withdrawal of a locked deposit");
+                prop_assert!(!disputed, "withdrawal of a disputed deposit");
+                Location::Wallet { holder: owner }
+            }
+            (
+                Op::WithdrawTreasury { owner: claimed, .. },
+                Location::Custody {
+                    owner,
+                    locked,
+                    disputed,
+                    ..
+                },
+            ) => {
+                prop_assert_eq!(claimed, owner, "treasury withdrawal of a mismatched owner");
+                prop_assert!(Self::is_admin(signer), "treasury withdrawal by non admin");
+                prop_assert!(!locked, "treasury withdrawal of a locked deposit");
+                prop_assert!(!disputed, "treasury withdrawal of a disputed deposit");
+                Location::Treasury
+            }
+            (
+                Op::Finalize { owner: claimed, .. },
+                Location::Custody {
+                    owner, disputed, ..
+                },
+            ) => {
+                prop_assert_eq!(claimed, owner, "finalize of a mismatched owner");
+                prop_assert!(Self::is_updater(signer), "finalize by non updater");
+                prop_assert!(!disputed, "finalize of a disputed deposit");
+                Location::Burned
+            }
+            (
+                Op::Transfer { new_owner, .. },
+                Location::Custody {
+                    owner,
+                    locked,
+                    disputed,
+                    ..
+                },
+            ) => {
+                prop_assert_eq!(signer, owner, "transfer by non owner");
+                prop_assert_ne!(new_owner, owner, "transfer to the same owner");
+                prop_assert!(!disputed, "transfer of a disputed deposit");
+                Location::Custody {
+                    owner: new_owner,
+                    locked,
+                    requested: false,
+                    disputed: false,
+                }
+            }
+            (
+                Op::OpenDispute { .. },
+                Location::Custody {
+                    owner,
+                    locked,
+                    requested,
+                    disputed,
+                },
+            ) => {
+                prop_assert_eq!(signer, owner, "dispute by non owner");
+                prop_assert!(!disputed, "dispute opened twice");
+                Location::Custody {
+                    owner,
+                    locked,
+                    requested,
+                    disputed: true,
+                }
+            }
+            (
+                Op::ResolveDispute { outcome, .. },
+                Location::Custody {
+                    owner, disputed, ..
+                },
+            ) => {
+                prop_assert!(Self::is_admin(signer), "dispute resolved by non admin");
+                prop_assert!(disputed, "resolution of an undisputed deposit");
+                match outcome {
+                    DisputeOutcome::ReturnToOwner => Location::Wallet { holder: owner },
+                    DisputeOutcome::SendToTreasury => Location::Treasury,
+                    DisputeOutcome::Finalize => Location::Burned,
+                }
+            }
+            (op, location) => {
+                return Err(TestCaseError::fail(format!(
+                    "{op:?} succeeded on nft at {location:?}"
+                )))
+            }
+        };
+        self.locations[nft_index] = next;
+        Ok(())
+    }
+
+    /// Token custody matches deposit status and counters equal live deposits.
+    fn check_invariants(&self) -> Result<(), TestCaseError> {
+        let svm = &self.env.svm;
+        let balance = |wallet: &Pubkey, nft: &Nft| {
+            self.env
+                .token_balance(&get_associated_token_address(wallet, &nft.mint))
+        };
+        for (index, (nft, location)) in self.nfts.iter().zip(&self.locations).enumerate() {
+            let wallets: u64 = self
+                .actors
+                .iter()
+                .map(|actor| balance(&actor.pubkey(), nft))
+                .sum();
+            let custody = balance(&pda::global_pool().0, nft);
+            let treasury = balance(&self.env.treasury, nft);
+            let deposit = svm
+                .get_account(&pda::nft_deposit(&nft.mint).0)
+                .map(|_| svm.get::<NftDeposit>(&pda::nft_deposit(&nft.mint).0));
+            let status = deposit.as_ref().map_or(0, |deposit| deposit.status);
+
+            match *location {
+                Location::Wallet { holder } => {
+                    prop_assert_eq!(
+                        (
+                            balance(&self.key(holder), nft),
+                            wallets,
+                            custody,
+                            treasury,
+                            status
+                        ),
+                        (1, 1, 0, 0, 0),
+                        "nft {}",
+                        index
+                    )
+                }
+                Location::Custody {
+                    owner,
+                    locked,
+                    requested,
+                    disputed,
+                } => {
+                    prop_assert_eq!((wallets, custody, treasury), (0, 1, 0), "nft {}", index);
+                    let deposit = deposit.unwrap();
+                    prop_assert_ne!(deposit.status, 0, "nft {}", index);
+                    prop_assert_eq!(deposit.owner, self.key(owner));
+                    prop_assert_eq!(deposit.locked, locked);
+                    prop_assert_eq!(deposit.withdrawal_requested_at != 0, requested);
+                    prop_assert_eq!(deposit.disputed, disputed);
+                }
+                Location::Treasury => {
+                    prop_assert_eq!(
+                        (wallets, custody, treasury, status),
+                        (0, 0, 1, 0),
+                        "nft {}",
+                        index
+                    )
+                }
+                Location::Burned => {
+                    prop_assert_eq!((wallets, custody, treasury), (0, 0, 0), "nft {}", index);
+                    prop_assert!(
+                        deposit.is_none(),
+                        "finalized deposit of nft {} still open",
+                        index
+                    );
+                    let mint =
+                        spl_token::state::Mint::unpack(&svm.get_account(&nft.mint).unwrap().data)
+                            .unwrap();
+                    prop_assert_eq!(mint.supply, 0);
+                }
+            }
+        }
+
+        let live = |actor: Option<usize>| {
+            self.locations
+                .iter()
+                .filter(|location| match location {
+                    Location::Custody { owner, .. } => actor.is_none_or(|actor| actor == *owner),
+                    _ => false,
+                })
+                .count() as u64
+        };
+        for actor in 0..ACTORS {
+            let user_pool: UserPool = svm.get(&pda::user_pool(&self.key(actor)).0);
+            prop_assert_eq!(
+                user_pool.deposit_count,
+                live(Some(actor)),
+                "actor {}",
+                actor
+            );
+        }
+        let global_pool: GlobalPool = svm.get(&pda::global_pool().0);
+        prop_assert_eq!(global_pool.total_deposit_count, live(None));
+        let collection_pool: CollectionPool =
+            svm.get(&pda::collection_pool(&self.env.collection).0);
+        prop_assert_eq!(collection_pool.deposit_count, live(None));
+        Ok(())
+    }
+}
+
+proptest! {
+    // Each case starts a bank, and solana-program-test 1.18 never frees them
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn deposit_lifecycle_keeps_invariants(ops in prop::collection::vec(op(), 1..48)) {
+        let mut harness = Harness::new();
+        for op in &ops {
+            harness.apply(op)?;
+            harness.check_invariants()?;
+        }
+    }
+}
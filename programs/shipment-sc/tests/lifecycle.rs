@@ -0,0 +1,259 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use shipment_sc::state::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// Active deposits counted by the user, global and collection pools.
+fn deposit_counts(env: &TestEnv, user: &Pubkey) -> (u64, u64, u64) {
+    let user_pool: UserPool = env.svm.get(&pda::user_pool(user).0);
+    let global_pool: GlobalPool = env.svm.get(&pda::global_pool().0);
+    let collection_pool: CollectionPool = env.svm.get(&pda::collection_pool(&env.collection).0);
+    (
+        user_pool.deposit_count,
+        global_pool.total_deposit_count,
+        collection_pool.deposit_count,
+    )
+}
+
+fn custody(nft: &Nft) -> Pubkey {
+    pda::custody_token_account(&nft.mint)
+}
+
+fn finalize(env: &mut TestEnv, user: &Keypair, nft: &Nft) {
+    let updater = env.updater.insecure_clone();
+    let collection = env.collection;
+    let treasury = env.treasury;
+    env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            [0; 32],
+        )],
+        &[&updater],
+    )
+    .unwrap();
+}
+
+#[test]
+fn deposit_takes_custody() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    assert_eq!(env.token_balance(&nft.token_account), 0);
+    assert_eq!(env.token_balance(&custody(&nft)), 1);
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, user.pubkey());
+    assert_eq!(deposit.mint, nft.mint);
+    assert_eq!(deposit.collection, env.collection);
+    assert_eq!(deposit.user, UUID);
+    assert_eq!(deposit.status, 1);
+    assert_eq!(deposit.created, env.svm.clock().unix_timestamp);
+    assert!(!deposit.locked);
+    assert!(!deposit.receipt);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (1, 1, 1));
+}
+
+#[test]
+fn update_deposit_sets_status() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.insecure_clone();
+
+    env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            Some(3),
+            None,
+            None,
+            None,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 3);
+    assert!(!deposit.locked);
+}
+
+#[test]
+fn withdraw_owner_returns_nft() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+    assert_eq!(env.token_balance(&custody(&nft)), 0);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 0);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (0, 0, 0));
+}
+
+#[test]
+fn withdrawn_nft_can_be_deposited_again() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    )
+    .unwrap();
+
+    assert_eq!(env.token_balance(&custody(&nft)), 1);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (1, 1, 1));
+}
+
+#[test]
+fn withdraw_treasury_sends_nft_to_treasury() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let admin = env.super_admin.pubkey();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    env.process(
+        &[withdraw_treasury_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    let treasury_token_account = get_associated_token_address(&treasury, &nft.mint);
+    assert_eq!(env.token_balance(&treasury_token_account), 1);
+    assert_eq!(env.token_balance(&nft.token_account), 0);
+    assert_eq!(env.token_balance(&custody(&nft)), 0);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 0);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (0, 0, 0));
+}
+
+#[test]
+fn finalize_burns_nft_and_closes_accounts() {
+    let mut env = TestEnv::new();
+    let treasury = env.treasury;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let rent = env.svm.lamports(&pda::nft_deposit(&nft.mint).0) + env.svm.lamports(&custody(&nft));
+    let before = env.svm.lamports(&treasury);
+
+    finalize(&mut env, &user, &nft);
+
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+    assert!(env.svm.get_account(&custody(&nft)).is_none());
+    assert_eq!(env.svm.lamports(&treasury) - before, rent);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (0, 0, 0));
+}
+
+#[test]
+fn counters_track_several_deposits() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let alice = env.new_user();
+    let bob = env.new_user();
+    let first = env.deposit(&alice);
+    let second = env.deposit(&alice);
+    env.deposit(&bob);
+    assert_eq!(deposit_counts(&env, &alice.pubkey()), (2, 3, 3));
+
+    env.process(
+        &[withdraw_owner_ix(
+            &alice.pubkey(),
+            &alice.pubkey(),
+            &first.mint,
+            &collection,
+        )],
+        &[&alice],
+    )
+    .unwrap();
+    finalize(&mut env, &alice, &second);
+
+    assert_eq!(deposit_counts(&env, &alice.pubkey()), (0, 1, 1));
+    assert_eq!(deposit_counts(&env, &bob.pubkey()), (1, 1, 1));
+}
+
+#[test]
+fn first_deposit_initializes_user_pool() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = Keypair::new();
+    env.svm.airdrop(&user.pubkey(), LAMPORTS);
+    let nft = env.mint_nft(&user.pubkey());
+
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    )
+    .unwrap();
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.address, user.pubkey());
+    assert_eq!(pool.deposit_count, 1);
+    assert!(!pool.admin);
+    assert!(!pool.updater);
+
+    // Later deposits reuse the pool
+    env.deposit(&user);
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.deposit_count, 2);
+}
+
+#[test]
+fn deposit_keeps_existing_roles() {
+    let mut env = TestEnv::new();
+    let updater = env.updater.insecure_clone();
+    env.deposit(&updater);
+
+    let pool: UserPool = env.svm.get(&pda::user_pool(&updater.pubkey()).0);
+    assert_eq!(pool.address, updater.pubkey());
+    assert_eq!(pool.deposit_count, 1);
+    assert!(pool.updater);
+    assert!(!pool.admin);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::signature::{Keypair, Signer};
+
+const HOUR: i64 = 60 * 60;
+
+fn update(
+    env: &mut TestEnv,
+    user: &Keypair,
+    nft: &Nft,
+    locked: Option<bool>,
+    lock_reason: Option<u8>,
+    locked_until: Option<i64>,
+) -> Result<(), TransactionError> {
+    let updater = env.updater.insecure_clone();
+    env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            None,
+            locked,
+            lock_reason,
+            locked_until,
+        )],
+        &[&updater],
+    )
+}
+
+#[test]
+fn lock_records_reason_and_expiry() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let until = env.svm.clock().unix_timestamp + HOUR;
+
+    update(&mut env, &user, &nft, Some(true), Some(3), Some(until)).unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert!(deposit.locked);
+    assert_eq!(deposit.lock_reason, 3);
+    assert_eq!(deposit.locked_until, until);
+}
+
+#[test]
+fn lock_expires_automatically() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let until = env.svm.clock().unix_timestamp + HOUR;
+    update(&mut env, &user, &nft, Some(true), Some(1), Some(until)).unwrap();
+
+    let withdraw = withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection);
+    let result = env.process(std::slice::from_ref(&withdraw), &[&user]);
+    assert_shipment_error(result, ShipmentError::DisabledWithdrawal);
+
+    env.svm.warp_time(HOUR);
+    env.process(&[withdraw], &[&user]).unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert!(!deposit.locked);
+    assert_eq!(deposit.lock_reason, 0);
+    assert_eq!(deposit.locked_until, 0);
+}
+
+#[test]
+fn lock_without_expiry_holds() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    update(&mut env, &user, &nft, Some(true), Some(2), None).unwrap();
+
+    env.svm.warp_time(365 * 24 * HOUR);
+    let treasury = env.treasury;
+    let admin = env.super_admin.pubkey();
+    let result = env.process(
+        &[withdraw_treasury_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::DisabledWithdrawal);
+}
+
+#[test]
+fn expired_lock_allows_treasury_withdrawal() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let until = env.svm.clock().unix_timestamp + HOUR;
+    update(&mut env, &user, &nft, Some(true), None, Some(until)).unwrap();
+
+    env.svm.warp_time(HOUR);
+    let treasury = env.treasury;
+    let admin = env.super_admin.pubkey();
+    env.process(
+        &[withdraw_treasury_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+        )],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn expiry_must_be_in_future() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let now = env.svm.clock().unix_timestamp;
+
+    let result = update(&mut env, &user, &nft, Some(true), None, Some(now));
+    assert_shipment_error(result, ShipmentError::InvalidLockExpiry);
+}
+
+#[test]
+fn unlock_clears_context() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let until = env.svm.clock().unix_timestamp + HOUR;
+    update(&mut env, &user, &nft, Some(true), Some(4), Some(until)).unwrap();
+
+    update(&mut env, &user, &nft, Some(false), None, None).unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert!(!deposit.locked);
+    assert_eq!(deposit.lock_reason, 0);
+    assert_eq!(deposit.locked_until, 0);
+}
@@ -0,0 +1,432 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::{error::ErrorCode, AnchorSerialize, Discriminator};
+use common::*;
+use shipment_sc::{constant::*, error::ShipmentError, legacy::*, state::*};
+use solana_sdk::{
+    account::Account,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+};
+
+fn set_legacy<T: Discriminator, L: AnchorSerialize>(
+    env: &mut TestEnv,
+    key: Pubkey,
+    legacy: &L,
+    size: usize,
+) {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data.resize(size, 0);
+    env.svm.set_account(
+        key,
+        Account {
+            lamports: Rent::default().minimum_balance(size),
+            data,
+            owner: shipment_sc::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+fn set_legacy_global_pool(env: &mut TestEnv, total_deposit_count: u64) {
+    let legacy = LegacyGlobalPool {
+        super_admin: env.super_admin.pubkey(),
+        treasury: env.treasury,
+        total_deposit_count,
+    };
+    set_legacy::<GlobalPool, _>(
+        env,
+        pda::global_pool().0,
+        &legacy,
+        LegacyGlobalPool::DATA_SIZE,
+    );
+}
+
+fn set_legacy_user_pool(env: &mut TestEnv, user: &Pubkey, updater: bool, deposit_count: u64) {
+    let legacy = LegacyUserPool {
+        address: *user,
+        deposit_count,
+        admin: false,
+        updater,
+    };
+    set_legacy::<UserPool, _>(
+        env,
+        pda::user_pool(user).0,
+        &legacy,
+        LegacyUserPool::DATA_SIZE,
+    );
+}
+
+fn set_legacy_deposit(env: &mut TestEnv, owner: &Pubkey, mint: &Pubkey, status: u8) {
+    let legacy = LegacyNftDeposit {
+        owner: *owner,
+        mint: *mint,
+        created: 1,
+        status,
+        locked: false,
+        user: UUID.to_string(),
+    };
+    set_legacy::<NftDeposit, _>(
+        env,
+        pda::nft_deposit(mint).0,
+        &legacy,
+        LegacyNftDeposit::DATA_SIZE,
+    );
+}
+
+/// Global, user and collection pools as the legacy program left them, with
+/// `deposit_count` deposits of `user` in custody and no collection registry.
+fn set_legacy_pools(env: &mut TestEnv, user: &Pubkey, deposit_count: u64) {
+    let admin = env.super_admin.pubkey();
+    let updater = env.updater.pubkey();
+    let collection = env.collection;
+
+    set_legacy_global_pool(env, deposit_count);
+    set_legacy_user_pool(env, &admin, false, 0);
+    set_legacy_user_pool(env, &updater, true, 0);
+    set_legacy_user_pool(env, user, false, deposit_count);
+    let legacy = LegacyCollectionPool {
+        address: collection,
+        allowed: true,
+    };
+    set_legacy::<CollectionPool, _>(
+        env,
+        pda::collection_pool(&collection).0,
+        &legacy,
+        LegacyCollectionPool::DATA_SIZE,
+    );
+    env.svm
+        .set_account(pda::collection_registry(0).0, Account::default());
+}
+
+/// Program state as the legacy program left it.
+fn legacy_env() -> (TestEnv, Keypair) {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    set_legacy_pools(&mut env, &user.pubkey(), 0);
+    (env, user)
+}
+
+/// Legacy program state holding one deposit of the user in custody, with
+/// the pools already migrated.
+fn legacy_env_with_deposit() -> (TestEnv, Keypair, Nft) {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    set_legacy_pools(&mut env, &user.pubkey(), 1);
+    set_legacy_deposit(&mut env, &user.pubkey(), &nft.mint, 1);
+    env.svm
+        .set_account(pda::web2_user_index(UUID, 0).0, Account::default());
+    migrate(&mut env, &user);
+    (env, user, nft)
+}
+
+fn deposit_counts(env: &TestEnv, user: &Pubkey) -> (u64, u64, u64) {
+    let user_pool: UserPool = env.svm.get(&pda::user_pool(user).0);
+    let global_pool: GlobalPool = env.svm.get(&pda::global_pool().0);
+    let collection_pool: CollectionPool = env.svm.get(&pda::collection_pool(&env.collection).0);
+    (
+        user_pool.deposit_count,
+        global_pool.total_deposit_count,
+        collection_pool.deposit_count,
+    )
+}
+
+fn migrate(env: &mut TestEnv, user: &Keypair) {
+    let admin = env.super_admin.pubkey();
+    let updater = env.updater.pubkey();
+    let collection = env.collection;
+    env.process(
+        &[
+            instructions::migrate_global_pool(&admin),
+            instructions::migrate_user_pool(&admin, &admin),
+            instructions::migrate_user_pool(&admin, &updater),
+            instructions::migrate_user_pool(&admin, &user.pubkey()),
+            instructions::migrate_collection_pool(&admin, &collection, 0),
+        ],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn migration_keeps_legacy_state() {
+    let (mut env, user) = legacy_env();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+
+    // Other pools are read through the global pool, it goes first
+    let result = env.process(
+        &[instructions::migrate_user_pool(&admin, &user.pubkey())],
+        &[],
+    );
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::AccountDidNotDeserialize.into()),
+    );
+    let result = env.process(
+        &[instructions::migrate_global_pool(&user.pubkey())],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    migrate(&mut env, &user);
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.super_admin, admin);
+    assert_eq!(global.treasury, env.treasury);
+    assert_eq!(
+        global.withdrawal_grace_period,
+        DEFAULT_WITHDRAWAL_GRACE_PERIOD
+    );
+    assert_eq!(global.collection_count, 1);
+    let updater: UserPool = env.svm.get(&pda::user_pool(&env.updater.pubkey()).0);
+    assert!(updater.updater && !updater.admin);
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert!(pool.allowed);
+    assert_eq!(pool.registry_index, 0);
+    let registry: CollectionRegistry = env.svm.get(&pda::collection_registry(0).0);
+    assert_eq!(registry.collections[0], collection);
+    assert_eq!(registry.count, 1);
+    for key in [pda::global_pool().0, pda::collection_pool(&collection).0] {
+        let account = env.svm.get_account(&key).unwrap();
+        assert!(account.lamports >= Rent::default().minimum_balance(account.data.len()));
+    }
+
+    // Migrated accounts are left as they are
+    migrate(&mut env, &user);
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.collection_count, 1);
+
+    // Migrated roles and pools serve new deposits
+    let nft = env.deposit(&user);
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.total_deposit_count, 1);
+    let updater = env.updater.insecure_clone();
+    env.process(
+        &[update_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            Some(2),
+            None,
+            None,
+            None,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+}
+
+#[test]
+fn live_legacy_deposit_is_migrated_in_place() {
+    let (mut env, user, nft) = legacy_env_with_deposit();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.recent_deposits, [0; MAX_DAILY_DEPOSITS]);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (1, 1, 0));
+
+    env.process(
+        &[instructions::migrate_nft_deposit(
+            &admin,
+            &nft.mint,
+            &collection,
+            UUID,
+            0,
+        )],
+        &[],
+    )
+    .unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, user.pubkey());
+    assert_eq!(deposit.status, 1);
+    assert_eq!(deposit.created, 1);
+    assert_eq!(deposit.collection, collection);
+    assert_eq!(deposit.payer, user.pubkey());
+    assert_eq!(deposit.user, UUID);
+    assert!(!deposit.receipt && !deposit.disputed);
+    let index: Web2UserIndex = env.svm.get(&pda::web2_user_index(UUID, 0).0);
+    assert_eq!(index.mints[0], nft.mint);
+    assert_eq!(index.count, 1);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (1, 1, 1));
+    let account = env.svm.get_account(&pda::nft_deposit(&nft.mint).0).unwrap();
+    assert_eq!(account.data.len(), NftDeposit::DATA_SIZE);
+    assert!(account.lamports >= Rent::default().minimum_balance(account.data.len()));
+
+    // Migrated deposit is left as it is
+    env.process(
+        &[instructions::migrate_nft_deposit(
+            &admin,
+            &nft.mint,
+            &collection,
+            UUID,
+            0,
+        )],
+        &[],
+    )
+    .unwrap();
+    let index: Web2UserIndex = env.svm.get(&pda::web2_user_index(UUID, 0).0);
+    assert_eq!(index.count, 1);
+
+    // Owner withdraws the nft the legacy program took into custody
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+    assert_eq!(deposit_counts(&env, &user.pubkey()), (0, 0, 0));
+}
+
+#[test]
+fn live_legacy_deposit_migration_checks_its_deposit() {
+    let (mut env, user, nft) = legacy_env_with_deposit();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+
+    let result = env.process(
+        &[instructions::migrate_nft_deposit(
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            UUID,
+            0,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    // Deposit stays listed under the web2 user it was made for
+    let result = env.process(
+        &[instructions::migrate_nft_deposit(
+            &admin,
+            &nft.mint,
+            &collection,
+            "other-user",
+            0,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidWeb2User);
+
+    // Collection must be the verified one of the nft
+    let other_collection = env.create_collection();
+    let other = env.mint_nft_with(&user.pubkey(), Some(other_collection), true);
+    set_legacy_deposit(&mut env, &user.pubkey(), &other.mint, 1);
+    let result = env.process(
+        &[instructions::migrate_nft_deposit(
+            &admin,
+            &other.mint,
+            &collection,
+            UUID,
+            0,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidCollection);
+
+    // Live deposits are kept, only withdrawn ones are closed
+    let result = env.process(
+        &[instructions::close_legacy_deposit(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::AlreadyDeposited);
+}
+
+#[test]
+fn withdrawn_legacy_deposit_is_closed() {
+    let (mut env, user) = legacy_env();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    migrate(&mut env, &user);
+    let nft = env.mint_nft(&user.pubkey());
+    set_legacy_deposit(&mut env, &user.pubkey(), &nft.mint, 0);
+    let rent = env.svm.lamports(&pda::nft_deposit(&nft.mint).0);
+    let balance = env.svm.lamports(&user.pubkey());
+
+    let result = env.process(
+        &[instructions::migrate_nft_deposit(
+            &admin,
+            &nft.mint,
+            &collection,
+            UUID,
+            0,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::NotDeposited);
+    let result = env.process(
+        &[instructions::close_legacy_deposit(
+            &admin, &admin, &nft.mint,
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+
+    env.process(
+        &[instructions::close_legacy_deposit(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[],
+    )
+    .unwrap();
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+    assert_eq!(env.svm.lamports(&user.pubkey()), balance + rent);
+
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    )
+    .unwrap();
+}
+
+#[test]
+fn migration_rejects_unknown_layout() {
+    let (mut env, _) = legacy_env();
+    let admin = env.super_admin.pubkey();
+
+    let legacy = LegacyGlobalPool {
+        super_admin: admin,
+        treasury: env.treasury,
+        total_deposit_count: 0,
+    };
+    set_legacy::<GlobalPool, _>(
+        &mut env,
+        pda::global_pool().0,
+        &legacy,
+        LegacyGlobalPool::DATA_SIZE + 8,
+    );
+    let result = env.process(&[instructions::migrate_global_pool(&admin)], &[]);
+    assert_shipment_error(result, ShipmentError::InvalidLegacyAccount);
+
+    // Accounts of another type are refused
+    set_legacy::<UserPool, _>(
+        &mut env,
+        pda::global_pool().0,
+        &legacy,
+        LegacyGlobalPool::DATA_SIZE,
+    );
+    let result = env.process(&[instructions::migrate_global_pool(&admin)], &[]);
+    assert_shipment_error(result, ShipmentError::InvalidLegacyAccount);
+}
@@ -0,0 +1,275 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+fn collection_deposits(env: &TestEnv, collection: &Pubkey) -> u64 {
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(collection).0);
+    pool.deposit_count
+}
+
+#[test]
+fn one_off_mint_requires_allowlist() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft_with(&user.pubkey(), None, false);
+
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::MetadataCreatorParseError);
+
+    env.process(
+        &[instructions::register_mint(&admin, &nft.mint, &collection)],
+        &[],
+    )
+    .unwrap();
+    let allowlist: MintAllowlist = env.svm.get(&pda::mint_allowlist(&nft.mint).0);
+    assert_eq!(allowlist.mint, nft.mint);
+    assert_eq!(allowlist.collection, collection);
+
+    env.process(
+        &[allowlisted_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            true,
+            None,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 1);
+    assert_eq!(deposit.collection, collection);
+    assert_eq!(collection_deposits(&env, &collection), 1);
+}
+
+#[test]
+fn allowlisted_mint_is_bound_to_its_collection() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let other = Pubkey::new_unique();
+    let user = env.new_user();
+    let nft = env.mint_nft_with(&user.pubkey(), None, false);
+
+    env.process(
+        &[
+            instructions::register_collection(&admin, &other, 0),
+            instructions::register_mint(&admin, &nft.mint, &other),
+        ],
+        &[],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[allowlisted_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            true,
+            None,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidCollection);
+}
+
+#[test]
+fn revoke_mint_closes_allowlist() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft_with(&user.pubkey(), None, false);
+
+    env.process(
+        &[instructions::register_mint(&admin, &nft.mint, &collection)],
+        &[],
+    )
+    .unwrap();
+    let rent = env.svm.lamports(&pda::mint_allowlist(&nft.mint).0);
+    let before = env.svm.lamports(&admin);
+    env.process(&[instructions::revoke_mint(&admin, &nft.mint)], &[])
+        .unwrap();
+
+    assert!(env
+        .svm
+        .get_account(&pda::mint_allowlist(&nft.mint).0)
+        .is_none());
+    assert_eq!(env.svm.lamports(&admin), before + rent);
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::MetadataCreatorParseError);
+}
+
+#[test]
+fn mint_allowlist_requires_admin() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let mint = Pubkey::new_unique();
+
+    let result = env.process(
+        &[instructions::register_mint(
+            &user.pubkey(),
+            &mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    env.process(
+        &[instructions::register_mint(&admin, &mint, &collection)],
+        &[],
+    )
+    .unwrap();
+    let result = env.process(
+        &[instructions::revoke_mint(&user.pubkey(), &mint)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let result = env.process(
+        &[instructions::change_mint_merkle_root(
+            &user.pubkey(),
+            &collection,
+            [1; 32],
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+}
+
+#[test]
+fn merkle_allowlisted_mint() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft_with(&user.pubkey(), None, false);
+    let (root, proof) = merkle_tree([
+        nft.mint,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ]);
+
+    // Proof is rejected until the collection has a mint root
+    let result = env.process(
+        &[allowlisted_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            false,
+            Some(proof.clone()),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidMerkleProof);
+
+    env.process(
+        &[instructions::change_mint_merkle_root(
+            &admin,
+            &collection,
+            root,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[allowlisted_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            false,
+            Some(vec![proof[1], proof[0]]),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidMerkleProof);
+
+    env.process(
+        &[allowlisted_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            false,
+            Some(proof),
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(collection_deposits(&env, &collection), 1);
+}
+
+#[test]
+fn merkle_proof_rejects_other_mint() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft_with(&user.pubkey(), None, false);
+    let (root, proof) = merkle_tree([
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ]);
+
+    env.process(
+        &[instructions::change_mint_merkle_root(
+            &admin,
+            &collection,
+            root,
+        )],
+        &[],
+    )
+    .unwrap();
+    let result = env.process(
+        &[allowlisted_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            false,
+            Some(proof),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidMerkleProof);
+}
+
+#[test]
+fn revoked_collection_rejects_deposits() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+
+    env.process(&[instructions::revoke_collection(&admin, &collection)], &[])
+        .unwrap();
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::CollectionNotAllowed);
+}
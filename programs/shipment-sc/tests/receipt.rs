@@ -0,0 +1,322 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_spl::{
+    token::spl_token,
+    token_2022::spl_token_2022::{
+        self,
+        extension::StateWithExtensions,
+        state::{Account as TokenAccount2022, Mint as Mint2022},
+    },
+};
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+fn deposit_with_receipt(env: &mut TestEnv, user: &Keypair) -> Nft {
+    let nft = env.mint_nft(&user.pubkey());
+    let collection = env.collection;
+    env.process(
+        &[with_receipt(
+            deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID),
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[user],
+    )
+    .unwrap();
+    nft
+}
+
+fn receipt_balance(env: &TestEnv, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    env.svm
+        .get_account(&pda::receipt_token_account(owner, mint))
+        .map_or(0, |account| {
+            StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+                .unwrap()
+                .base
+                .amount
+        })
+}
+
+fn receipt_supply(env: &TestEnv, mint: &Pubkey) -> u64 {
+    let account = env.svm.get_account(&pda::receipt_mint(mint).0).unwrap();
+    StateWithExtensions::<Mint2022>::unpack(&account.data)
+        .unwrap()
+        .base
+        .supply
+}
+
+#[test]
+fn deposit_mints_receipt() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+
+    assert_eq!(receipt_balance(&env, &user.pubkey(), &nft.mint), 1);
+    assert_eq!(receipt_supply(&env, &nft.mint), 1);
+    let receipt_mint = env
+        .svm
+        .get_account(&pda::receipt_mint(&nft.mint).0)
+        .unwrap();
+    assert_eq!(receipt_mint.owner, spl_token_2022::ID);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert!(deposit.receipt);
+}
+
+#[test]
+fn deposit_without_receipt_accounts() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert!(!deposit.receipt);
+    assert!(env
+        .svm
+        .get_account(&pda::receipt_mint(&nft.mint).0)
+        .is_none());
+
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+}
+
+#[test]
+fn receipt_is_not_transferable() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let other = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    let receipt_mint = pda::receipt_mint(&nft.mint).0;
+
+    let result = env.process(
+        &[
+            create_associated_token_account_idempotent(
+                &user.pubkey(),
+                &other.pubkey(),
+                &receipt_mint,
+                &spl_token_2022::ID,
+            ),
+            spl_token_2022::instruction::transfer_checked(
+                &spl_token_2022::ID,
+                &pda::receipt_token_account(&user.pubkey(), &nft.mint),
+                &receipt_mint,
+                &pda::receipt_token_account(&other.pubkey(), &nft.mint),
+                &user.pubkey(),
+                &[],
+                1,
+                0,
+            )
+            .unwrap(),
+        ],
+        &[&user],
+    );
+    assert!(result.is_err());
+    assert_eq!(receipt_balance(&env, &user.pubkey(), &nft.mint), 1);
+}
+
+#[test]
+fn withdraw_owner_burns_receipt() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    let withdraw = withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection);
+
+    let result = env.process(std::slice::from_ref(&withdraw), &[&user]);
+    assert_shipment_error(result, ShipmentError::ReceiptRequired);
+
+    env.process(
+        &[with_receipt(withdraw, &user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(receipt_balance(&env, &user.pubkey(), &nft.mint), 0);
+    assert_eq!(receipt_supply(&env, &nft.mint), 0);
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+}
+
+#[test]
+fn withdraw_treasury_burns_receipt() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    let admin = env.super_admin.pubkey();
+    let treasury = env.treasury;
+
+    env.process(
+        &[with_receipt(
+            withdraw_treasury_ix(&admin, &user.pubkey(), &nft.mint, &collection, &treasury),
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(receipt_balance(&env, &user.pubkey(), &nft.mint), 0);
+}
+
+#[test]
+fn finalize_burns_receipt() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    let updater = env.updater.insecure_clone();
+    let treasury = env.treasury;
+
+    env.process(
+        &[with_receipt(
+            finalize_deposit_ix(
+                &updater.pubkey(),
+                &user.pubkey(),
+                &nft.mint,
+                &env.collection,
+                &treasury,
+                [0; 32],
+            ),
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+    assert_eq!(receipt_balance(&env, &user.pubkey(), &nft.mint), 0);
+    assert_eq!(receipt_supply(&env, &nft.mint), 0);
+}
+
+#[test]
+fn receipt_must_belong_to_owner() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let other = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+
+    let result = env.process(
+        &[with_receipt(
+            withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection),
+            &other.pubkey(),
+            &nft.mint,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidReceipt);
+}
+
+#[test]
+fn redeposit_reuses_receipt_mint() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    env.process(
+        &[with_receipt(
+            withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection),
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    env.process(
+        &[with_receipt(
+            deposit_nft_ix(&user.pubkey(), &nft, &collection, UUID),
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(receipt_balance(&env, &user.pubkey(), &nft.mint), 1);
+    assert_eq!(receipt_supply(&env, &nft.mint), 1);
+}
+
+fn burn_own_receipt(env: &mut TestEnv, user: &Keypair, nft: &Nft, close: bool) {
+    let token_account = pda::receipt_token_account(&user.pubkey(), &nft.mint);
+    let mut instructions = vec![spl_token_2022::instruction::burn(
+        &spl_token_2022::ID,
+        &token_account,
+        &pda::receipt_mint(&nft.mint).0,
+        &user.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap()];
+    if close {
+        instructions.push(
+            spl_token_2022::instruction::close_account(
+                &spl_token_2022::ID,
+                &token_account,
+                &user.pubkey(),
+                &user.pubkey(),
+                &[],
+            )
+            .unwrap(),
+        );
+    }
+    env.process(&instructions, &[user]).unwrap();
+}
+
+#[test]
+fn holder_burned_receipt_does_not_block_withdrawal() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    burn_own_receipt(&mut env, &user, &nft, false);
+    assert_eq!(receipt_supply(&env, &nft.mint), 0);
+
+    env.process(
+        &[with_receipt(
+            withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection),
+            &user.pubkey(),
+            &nft.mint,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+}
+
+#[test]
+fn closed_receipt_account_needs_only_receipt_mint() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = deposit_with_receipt(&mut env, &user);
+    burn_own_receipt(&mut env, &user, &nft, true);
+    let withdraw = withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection);
+
+    // The program can only tell the receipt is gone from its mint
+    let result = env.process(std::slice::from_ref(&withdraw), &[&user]);
+    assert_shipment_error(result, ShipmentError::ReceiptRequired);
+
+    let mut ix = with_receipt(withdraw, &user.pubkey(), &nft.mint);
+    let token_program = ix
+        .accounts
+        .iter()
+        .position(|meta| meta.pubkey == spl_token::ID)
+        .unwrap();
+    ix.accounts[token_program - 1].pubkey = shipment_sc::ID;
+    ix.accounts[token_program - 1].is_writable = false;
+    env.process(&[ix], &[&user]).unwrap();
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 0);
+}
@@ -0,0 +1,230 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use shipment_sc::{error::ShipmentError, event::DisputeResolved, state::*};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+fn set_redemption_mint(env: &mut TestEnv, redemption_mint: Option<Pubkey>) {
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    env.process(
+        &[instructions::change_redemption_mint(
+            &admin,
+            &collection,
+            redemption_mint,
+        )],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn change_redemption_mint_requires_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let collection = env.collection;
+    let redemption_mint = env.create_mint(&pda::redemption_authority(&collection).0);
+
+    let result = env.process(
+        &[instructions::change_redemption_mint(
+            &user.pubkey(),
+            &collection,
+            Some(redemption_mint),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    set_redemption_mint(&mut env, Some(redemption_mint));
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.redemption_mint, redemption_mint);
+
+    set_redemption_mint(&mut env, None);
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.redemption_mint, Pubkey::default());
+}
+
+#[test]
+fn finalize_mints_redeemed_token() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let updater = env.updater.insecure_clone();
+    let redemption_mint = env.create_mint(&pda::redemption_authority(&collection).0);
+    set_redemption_mint(&mut env, Some(redemption_mint));
+
+    // Redemption accounts can not be skipped once the collection redeems
+    let result = env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            [0; 32],
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::RedemptionRequired);
+
+    env.process(
+        &[redeem_finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            &redemption_mint,
+            None,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+
+    let redeemed = get_associated_token_address(&user.pubkey(), &redemption_mint);
+    assert_eq!(env.token_balance(&redeemed), 1);
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+}
+
+#[test]
+fn finalize_transfers_redeemed_token_from_stock() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let updater = env.updater.insecure_clone();
+    let authority = pda::redemption_authority(&collection).0;
+    let admin = env.super_admin.pubkey();
+    let redemption_mint = env.create_mint(&admin);
+    let stock = env.create_token_account(&redemption_mint, &authority, 5);
+    set_redemption_mint(&mut env, Some(redemption_mint));
+
+    let result = env.process(
+        &[redeem_finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            &redemption_mint,
+            None,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::RedemptionRequired);
+
+    env.process(
+        &[redeem_finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            &redemption_mint,
+            Some(stock),
+        )],
+        &[&updater],
+    )
+    .unwrap();
+
+    let redeemed = get_associated_token_address(&user.pubkey(), &redemption_mint);
+    assert_eq!(env.token_balance(&redeemed), 1);
+    assert_eq!(env.token_balance(&stock), 4);
+}
+
+#[test]
+fn finalize_rejects_other_redemption_mint() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let updater = env.updater.insecure_clone();
+    let authority = pda::redemption_authority(&collection).0;
+    let redemption_mint = env.create_mint(&authority);
+    let other_mint = env.create_mint(&authority);
+    set_redemption_mint(&mut env, Some(redemption_mint));
+
+    let result = env.process(
+        &[redeem_finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            &other_mint,
+            None,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidRedemption);
+}
+
+#[test]
+fn disputed_finalize_mints_redeemed_token() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let admin = env.super_admin.pubkey();
+    let redemption_mint = env.create_mint(&pda::redemption_authority(&collection).0);
+    set_redemption_mint(&mut env, Some(redemption_mint));
+    env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            [7; 32],
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            None,
+            DisputeOutcome::Finalize,
+            [9; 32],
+        )],
+        &[],
+    );
+    assert_shipment_error(result, ShipmentError::RedemptionRequired);
+
+    env.process(
+        &[redeem_resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            &redemption_mint,
+            None,
+        )],
+        &[],
+    )
+    .unwrap();
+
+    let redeemed = get_associated_token_address(&user.pubkey(), &redemption_mint);
+    assert_eq!(env.token_balance(&redeemed), 1);
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+    assert!(env.svm.get_account(&pda::dispute(&nft.mint).0).is_none());
+    let events: Vec<DisputeResolved> = emitted(&env.svm);
+    assert_eq!(events[0].outcome, DisputeOutcome::Finalize);
+}
@@ -0,0 +1,212 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+fn set_rent_destination(env: &mut TestEnv, rent_destination: RentDestination) {
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    env.process(
+        &[instructions::change_rent_destination(
+            &admin,
+            &collection,
+            rent_destination,
+        )],
+        &[],
+    )
+    .unwrap();
+}
+
+/// Finalizes `nft` of web2 user `uuid` sending rent to `rent_recipient`, returns
+/// the lamports it gained.
+fn finalize_to(
+    env: &mut TestEnv,
+    user: &Pubkey,
+    nft: &Nft,
+    uuid: &str,
+    rent_recipient: &Pubkey,
+) -> Result<u64, TransactionError> {
+    let updater = env.updater.insecure_clone();
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let before = env.svm.lamports(rent_recipient);
+    env.process(
+        &[with_web2_user_index(
+            finalize_deposit_to_ix(
+                &updater.pubkey(),
+                user,
+                &nft.mint,
+                &collection,
+                &treasury,
+                rent_recipient,
+            ),
+            uuid,
+            0,
+        )],
+        &[&updater],
+    )?;
+    Ok(env.svm.lamports(rent_recipient) - before)
+}
+
+#[test]
+fn deposit_records_payer() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.payer, user.pubkey());
+}
+
+#[test]
+fn change_rent_destination_requires_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let collection = env.collection;
+
+    let result = env.process(
+        &[instructions::change_rent_destination(
+            &user.pubkey(),
+            &collection,
+            RentDestination::Depositor,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    set_rent_destination(&mut env, RentDestination::Depositor);
+    let pool: CollectionPool = env.svm.get(&pda::collection_pool(&collection).0);
+    assert_eq!(pool.rent_destination, RentDestination::Depositor);
+}
+
+#[test]
+fn rent_goes_to_treasury_by_default() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let treasury = env.treasury;
+
+    let result = finalize_to(&mut env, &user.pubkey(), &nft, UUID, &user.pubkey());
+    assert_shipment_error(result.map(drop), ShipmentError::InvalidRentRecipient);
+
+    let refund = finalize_to(&mut env, &user.pubkey(), &nft, UUID, &treasury).unwrap();
+    assert!(refund > 0);
+}
+
+#[test]
+fn rent_goes_to_depositor() {
+    let mut env = TestEnv::new();
+    set_rent_destination(&mut env, RentDestination::Depositor);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let deposit_rent = env.svm.lamports(&pda::nft_deposit(&nft.mint).0);
+    let treasury = env.treasury;
+
+    let result = finalize_to(&mut env, &user.pubkey(), &nft, UUID, &treasury);
+    assert_shipment_error(result.map(drop), ShipmentError::InvalidRentRecipient);
+
+    let refund = finalize_to(&mut env, &user.pubkey(), &nft, UUID, &user.pubkey()).unwrap();
+    assert!(refund > deposit_rent);
+}
+
+#[test]
+fn rent_goes_to_depositor_after_ownership_transfer() {
+    let mut env = TestEnv::new();
+    set_rent_destination(&mut env, RentDestination::Depositor);
+    let user = env.new_user();
+    let new_owner = env.new_user();
+    let nft = env.deposit(&user);
+    env.process(
+        &[transfer_deposit_ownership_ix(
+            &user.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "new-user",
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    let result = finalize_to(
+        &mut env,
+        &new_owner.pubkey(),
+        &nft,
+        "new-user",
+        &new_owner.pubkey(),
+    );
+    assert_shipment_error(result.map(drop), ShipmentError::InvalidRentRecipient);
+    finalize_to(
+        &mut env,
+        &new_owner.pubkey(),
+        &nft,
+        "new-user",
+        &user.pubkey(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn rent_goes_to_updater() {
+    let mut env = TestEnv::new();
+    set_rent_destination(&mut env, RentDestination::Updater);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let updater = env.updater.pubkey();
+
+    let refund = finalize_to(&mut env, &user.pubkey(), &nft, UUID, &updater).unwrap();
+    assert!(refund > 0);
+}
+
+#[test]
+fn disputed_finalize_honors_rent_destination() {
+    let mut env = TestEnv::new();
+    set_rent_destination(&mut env, RentDestination::Depositor);
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    let deposit_rent = env.svm.lamports(&pda::nft_deposit(&nft.mint).0);
+    let admin = env.super_admin.pubkey();
+    let collection = env.collection;
+    let treasury = env.treasury;
+    env.process(
+        &[instructions::open_dispute(
+            &user.pubkey(),
+            &nft.mint,
+            [7; 32],
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    let resolve = |rent_recipient: Option<Pubkey>| {
+        let mut ix = resolve_dispute_ix(
+            &admin,
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+            &treasury,
+            None,
+            DisputeOutcome::Finalize,
+            [9; 32],
+        );
+        ix.accounts[11].pubkey = rent_recipient.unwrap_or(shipment_sc::ID);
+        ix
+    };
+    let result = env.process(&[resolve(Some(treasury))], &[]);
+    assert_shipment_error(result, ShipmentError::InvalidRentRecipient);
+    let result = env.process(&[resolve(None)], &[]);
+    assert_shipment_error(result, ShipmentError::InvalidRentRecipient);
+
+    let before = env.svm.lamports(&user.pubkey());
+    env.process(&[resolve(Some(user.pubkey()))], &[]).unwrap();
+    assert!(env.svm.lamports(&user.pubkey()) - before > deposit_rent);
+    assert!(env
+        .svm
+        .get_account(&pda::nft_deposit(&nft.mint).0)
+        .is_none());
+}
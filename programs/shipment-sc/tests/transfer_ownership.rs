@@ -0,0 +1,294 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::StateWithExtensions, state::Account as TokenAccount2022,
+};
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+fn receipt_balance(env: &TestEnv, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    env.svm
+        .get_account(&pda::receipt_token_account(owner, mint))
+        .map_or(0, |account| {
+            StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+                .unwrap()
+                .base
+                .amount
+        })
+}
+
+#[test]
+fn transfer_moves_claim_and_counts() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let owner = env.new_user();
+    let new_owner = env.new_user();
+    let nft = env.deposit(&owner);
+    env.process(
+        &[instructions::request_withdrawal(&owner.pubkey(), &nft.mint)],
+        &[&owner],
+    )
+    .unwrap();
+
+    env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "new-user",
+        )],
+        &[&owner],
+    )
+    .unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, new_owner.pubkey());
+    assert_eq!(deposit.user, "new-user");
+    assert_eq!(deposit.withdrawal_requested_at, 0);
+    let index: Web2UserIndex = env.svm.get(&pda::web2_user_index(UUID, 0).0);
+    assert_eq!(index.count, 0);
+    let index: Web2UserIndex = env.svm.get(&pda::web2_user_index("new-user", 0).0);
+    assert_eq!(index.mints[0], nft.mint);
+    let pool: UserPool = env.svm.get(&pda::user_pool(&owner.pubkey()).0);
+    assert_eq!(pool.deposit_count, 0);
+    let pool: UserPool = env.svm.get(&pda::user_pool(&new_owner.pubkey()).0);
+    assert_eq!(pool.deposit_count, 1);
+
+    // Only the new owner can withdraw now
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &owner.pubkey(),
+            &owner.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&owner],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+    env.process(
+        &[with_web2_user_index(
+            withdraw_owner_ix(
+                &new_owner.pubkey(),
+                &new_owner.pubkey(),
+                &nft.mint,
+                &collection,
+            ),
+            "new-user",
+            0,
+        )],
+        &[&new_owner],
+    )
+    .unwrap();
+    let new_owner_ata =
+        anchor_spl::associated_token::get_associated_token_address(&new_owner.pubkey(), &nft.mint);
+    assert_eq!(env.token_balance(&new_owner_ata), 1);
+}
+
+#[test]
+fn transfer_requires_owner_and_new_wallet() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &other.pubkey(),
+            &other.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "other",
+        )],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "same",
+        )],
+        &[&owner],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidNewOwner);
+}
+
+#[test]
+fn transfer_policy_requires_admin_cosign() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let new_owner = env.new_user();
+    let nft = env.deposit(&owner);
+    let admin = env.super_admin.pubkey();
+    env.process(&[instructions::change_transfer_policy(&admin, true)], &[])
+        .unwrap();
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "new-user",
+        )],
+        &[&owner],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            Some(&new_owner.pubkey()),
+            false,
+            "new-user",
+        )],
+        &[&owner, &new_owner],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            Some(&admin),
+            false,
+            "new-user",
+        )],
+        &[&owner],
+    )
+    .unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, new_owner.pubkey());
+}
+
+#[test]
+fn transfer_blocked_by_dispute() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let new_owner = env.new_user();
+    let nft = env.deposit(&owner);
+    env.process(
+        &[instructions::open_dispute(
+            &owner.pubkey(),
+            &nft.mint,
+            [1; 32],
+        )],
+        &[&owner],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "new-user",
+        )],
+        &[&owner],
+    );
+    assert_shipment_error(result, ShipmentError::DepositDisputed);
+}
+
+#[test]
+fn transfer_respects_concurrent_quota() {
+    let mut env = TestEnv::new();
+    let admin = env.super_admin.pubkey();
+    let owner = env.new_user();
+    let new_owner = env.new_user();
+    let nft = env.deposit(&owner);
+    env.deposit(&new_owner);
+    let quota = DepositQuota {
+        max_concurrent_deposits: 1,
+        max_daily_deposits: 0,
+    };
+    env.process(&[instructions::change_deposit_quota(&admin, quota)], &[])
+        .unwrap();
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "new-user",
+        )],
+        &[&owner],
+    );
+    assert_shipment_error(result, ShipmentError::QuotaExceeded);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, owner.pubkey());
+}
+
+#[test]
+fn transfer_moves_receipt() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let new_owner = env.new_user();
+    let nft = env.mint_nft(&owner.pubkey());
+    let collection = env.collection;
+    env.process(
+        &[with_receipt(
+            deposit_nft_ix(&owner.pubkey(), &nft, &collection, UUID),
+            &owner.pubkey(),
+            &nft.mint,
+        )],
+        &[&owner],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            false,
+            "new-user",
+        )],
+        &[&owner],
+    );
+    assert_shipment_error(result, ShipmentError::ReceiptRequired);
+
+    env.process(
+        &[transfer_deposit_ownership_ix(
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &env.collection,
+            None,
+            true,
+            "new-user",
+        )],
+        &[&owner],
+    )
+    .unwrap();
+
+    assert_eq!(receipt_balance(&env, &owner.pubkey(), &nft.mint), 0);
+    assert_eq!(receipt_balance(&env, &new_owner.pubkey(), &nft.mint), 1);
+}
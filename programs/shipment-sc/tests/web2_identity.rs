@@ -0,0 +1,186 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use shipment_sc::{error::ShipmentError, state::*, validations::web2_identity_message};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn require_identity(env: &mut TestEnv) -> Keypair {
+    let authority = Keypair::new();
+    let admin = env.super_admin.pubkey();
+    env.process(
+        &[instructions::change_identity_authority(
+            &admin,
+            &authority.pubkey(),
+        )],
+        &[],
+    )
+    .unwrap();
+    authority
+}
+
+fn link(
+    env: &mut TestEnv,
+    authority: &Keypair,
+    user: &Keypair,
+    uuid: &str,
+) -> Result<(), TransactionError> {
+    env.process(
+        &[
+            ed25519_ix(authority, &web2_identity_message(&user.pubkey(), uuid)),
+            instructions::link_web2_identity(&user.pubkey(), uuid),
+        ],
+        &[user],
+    )
+}
+
+fn try_deposit(env: &mut TestEnv, user: &Keypair, uuid: &str) -> Result<(), TransactionError> {
+    let nft = env.mint_nft(&user.pubkey());
+    let collection = env.collection;
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, uuid)],
+        &[user],
+    )
+}
+
+#[test]
+fn change_identity_authority_requires_admin() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let authority = Keypair::new();
+
+    let result = env.process(
+        &[instructions::change_identity_authority(
+            &user.pubkey(),
+            &authority.pubkey(),
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let authority = require_identity(&mut env);
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.identity_authority, authority.pubkey());
+}
+
+#[test]
+fn link_stores_attested_binding() {
+    let mut env = TestEnv::new();
+    let authority = require_identity(&mut env);
+    let user = env.new_user();
+
+    link(&mut env, &authority, &user, UUID).unwrap();
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.web2_user, Web2UserIndex::user_seed(UUID));
+
+    // A new attestation moves the wallet to another web2 user
+    link(&mut env, &authority, &user, "other-user").unwrap();
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.web2_user, Web2UserIndex::user_seed("other-user"));
+}
+
+#[test]
+fn link_creates_user_pool() {
+    let mut env = TestEnv::new();
+    let authority = require_identity(&mut env);
+    let user = Keypair::new();
+    env.svm.airdrop(&user.pubkey(), LAMPORTS);
+
+    // Linking comes before the first deposit, no user pool exists yet
+    link(&mut env, &authority, &user, UUID).unwrap();
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.address, user.pubkey());
+    assert_eq!(pool.web2_user, Web2UserIndex::user_seed(UUID));
+    assert!(!pool.admin && !pool.updater);
+
+    try_deposit(&mut env, &user, UUID).unwrap();
+}
+
+#[test]
+fn link_requires_attestation() {
+    let mut env = TestEnv::new();
+    let authority = require_identity(&mut env);
+    let user = env.new_user();
+
+    let result = env.process(
+        &[instructions::link_web2_identity(&user.pubkey(), UUID)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::IdentityAttestationRequired);
+
+    // Signed by another key
+    let result = link(&mut env, &Keypair::new(), &user, UUID);
+    assert_shipment_error(result, ShipmentError::InvalidIdentityAttestation);
+
+    // Attestation of another uuid
+    let result = env.process(
+        &[
+            ed25519_ix(
+                &authority,
+                &web2_identity_message(&user.pubkey(), "other-user"),
+            ),
+            instructions::link_web2_identity(&user.pubkey(), UUID),
+        ],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidIdentityAttestation);
+
+    // Attestation of another wallet
+    let other = env.new_user();
+    let result = env.process(
+        &[
+            ed25519_ix(&authority, &web2_identity_message(&other.pubkey(), UUID)),
+            instructions::link_web2_identity(&user.pubkey(), UUID),
+        ],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidIdentityAttestation);
+}
+
+#[test]
+fn deposit_requires_linked_uuid() {
+    let mut env = TestEnv::new();
+    // Any uuid is accepted until an identity authority is set
+    let user = env.new_user();
+    try_deposit(&mut env, &user, "someone-else").unwrap();
+
+    let authority = require_identity(&mut env);
+    let result = try_deposit(&mut env, &user, UUID);
+    assert_shipment_error(result, ShipmentError::Web2UserNotLinked);
+
+    link(&mut env, &authority, &user, UUID).unwrap();
+    let result = try_deposit(&mut env, &user, "someone-else");
+    assert_shipment_error(result, ShipmentError::Web2UserNotLinked);
+    try_deposit(&mut env, &user, UUID).unwrap();
+}
+
+#[test]
+fn transfer_requires_uuid_linked_to_new_owner() {
+    let mut env = TestEnv::new();
+    let authority = require_identity(&mut env);
+    let user = env.new_user();
+    let new_owner = env.new_user();
+    link(&mut env, &authority, &user, UUID).unwrap();
+    let nft = env.deposit(&user);
+    let collection = env.collection;
+
+    let transfer = |new_user: &str| {
+        transfer_deposit_ownership_ix(
+            &user.pubkey(),
+            &new_owner.pubkey(),
+            &nft.mint,
+            &collection,
+            None,
+            false,
+            new_user,
+        )
+    };
+    let result = env.process(&[transfer("new-user")], &[&user]);
+    assert_shipment_error(result, ShipmentError::Web2UserNotLinked);
+
+    link(&mut env, &authority, &new_owner, "new-user").unwrap();
+    env.process(&[transfer("new-user")], &[&user]).unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.owner, new_owner.pubkey());
+}
@@ -0,0 +1,275 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use shipment_sc::{constant::*, error::ShipmentError, state::*};
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey, signature::Signer};
+
+fn index(env: &TestEnv, uuid: &str, page: u64) -> Web2UserIndex {
+    env.svm.get(&pda::web2_user_index(uuid, page).0)
+}
+
+fn listed(index: &Web2UserIndex) -> Vec<Pubkey> {
+    index
+        .mints
+        .iter()
+        .filter(|mint| **mint != Pubkey::default())
+        .copied()
+        .collect()
+}
+
+#[test]
+fn deposit_lists_mint() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let first = env.deposit(&user);
+    let second = env.deposit(&user);
+
+    let page = index(&env, UUID, 0);
+    assert_eq!(page.page, 0);
+    assert_eq!(page.count, 2);
+    assert_eq!(listed(&page), vec![first.mint, second.mint]);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&first.mint).0);
+    assert_eq!(deposit.index_page, 0);
+}
+
+#[test]
+fn removals_unlist_mint() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let treasury = env.treasury;
+    let admin = env.super_admin.pubkey();
+    let updater = env.updater.insecure_clone();
+    let user = env.new_user();
+    let withdrawn = env.deposit(&user);
+    let to_treasury = env.deposit(&user);
+    let finalized = env.deposit(&user);
+    let kept = env.deposit(&user);
+
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &withdrawn.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    env.process(
+        &[withdraw_treasury_ix(
+            &admin,
+            &user.pubkey(),
+            &to_treasury.mint,
+            &collection,
+            &treasury,
+        )],
+        &[],
+    )
+    .unwrap();
+    env.process(
+        &[finalize_deposit_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &finalized.mint,
+            &collection,
+            &treasury,
+            [1; 32],
+        )],
+        &[&updater],
+    )
+    .unwrap();
+
+    let page = index(&env, UUID, 0);
+    assert_eq!(page.count, 1);
+    assert_eq!(listed(&page), vec![kept.mint]);
+
+    // Freed slots are reused
+    let nft = env.deposit(&user);
+    let page = index(&env, UUID, 0);
+    assert_eq!(page.count, 2);
+    assert_eq!(page.mints[0], nft.mint);
+}
+
+#[test]
+fn full_page_rejects_deposit() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    for _ in 0..WEB2_USER_INDEX_PAGE_SIZE {
+        env.deposit(&user);
+    }
+    assert_eq!(index(&env, UUID, 0).count, WEB2_USER_INDEX_PAGE_SIZE as u64);
+
+    let nft = env.mint_nft(&user.pubkey());
+    let result = env.process(
+        &[indexed_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            0,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::Web2UserIndexFull);
+
+    // The next page takes the deposit
+    env.process(
+        &[indexed_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            1,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let page = index(&env, UUID, 1);
+    assert_eq!(page.page, 1);
+    assert_eq!(listed(&page), vec![nft.mint]);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.index_page, 1);
+
+    // Removal finds the deposit in its page
+    env.process(
+        &[with_web2_user_index(
+            withdraw_owner_ix(&user.pubkey(), &user.pubkey(), &nft.mint, &collection),
+            UUID,
+            1,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(index(&env, UUID, 1).count, 0);
+    assert_eq!(index(&env, UUID, 0).count, WEB2_USER_INDEX_PAGE_SIZE as u64);
+}
+
+#[test]
+fn pages_open_in_order() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.mint_nft(&user.pubkey());
+    let deposit_to_page_1 =
+        |nft: &Nft| indexed_deposit_nft_ix(&user.pubkey(), nft, &collection, UUID, 1);
+
+    // Page 0 was never opened
+    let result = env.process(&[deposit_to_page_1(&nft)], &[&user]);
+    assert_program_error(
+        result,
+        ProgramError::Custom(ErrorCode::AccountNotInitialized.into()),
+    );
+
+    // Page 0 still has free slots
+    env.deposit(&user);
+    let result = env.process(&[deposit_to_page_1(&nft)], &[&user]);
+    assert_shipment_error(result, ShipmentError::Web2UserIndexPageSkipped);
+
+    for _ in 1..WEB2_USER_INDEX_PAGE_SIZE {
+        env.deposit(&user);
+    }
+    env.process(&[deposit_to_page_1(&nft)], &[&user]).unwrap();
+    assert_eq!(listed(&index(&env, UUID, 1)), vec![nft.mint]);
+
+    // Opened page keeps taking deposits once page 0 frees a slot
+    let first = index(&env, UUID, 0).mints[0];
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &first,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    let nft = env.mint_nft(&user.pubkey());
+    env.process(&[deposit_to_page_1(&nft)], &[&user]).unwrap();
+    assert_eq!(index(&env, UUID, 1).count, 2);
+}
+
+#[test]
+fn removal_requires_deposit_page() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    for _ in 0..WEB2_USER_INDEX_PAGE_SIZE {
+        env.deposit(&user);
+    }
+    let nft = env.mint_nft(&user.pubkey());
+    env.process(
+        &[indexed_deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            UUID,
+            1,
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert!(result.is_err());
+    assert_eq!(index(&env, UUID, 1).count, 1);
+}
+
+#[test]
+fn users_have_separate_indexes() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let mine = env.deposit(&user);
+    let nft = env.mint_nft(&user.pubkey());
+    env.process(
+        &[deposit_nft_ix(
+            &user.pubkey(),
+            &nft,
+            &collection,
+            "other-user",
+        )],
+        &[&user],
+    )
+    .unwrap();
+
+    assert_eq!(listed(&index(&env, UUID, 0)), vec![mine.mint]);
+    assert_eq!(listed(&index(&env, "other-user", 0)), vec![nft.mint]);
+}
+
+#[test]
+fn uuid_length_is_bounded() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let uuid = "123e4567-e89b-12d3-a456-426614174000";
+    assert_eq!(uuid.len(), MAX_UUID_LEN);
+
+    let nft = env.mint_nft(&user.pubkey());
+    env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, uuid)],
+        &[&user],
+    )
+    .unwrap();
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.user, uuid);
+
+    let too_long = format!("{uuid}0");
+    let nft = env.mint_nft(&user.pubkey());
+    let result = env.process(
+        &[deposit_nft_ix(&user.pubkey(), &nft, &collection, &too_long)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::Web2UserTooLong);
+}
@@ -0,0 +1,243 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use shipment_sc::{constant::DEFAULT_WITHDRAWAL_GRACE_PERIOD, error::ShipmentError, state::*};
+use solana_sdk::signature::Signer;
+
+#[test]
+fn request_sets_timestamp() {
+    let mut env = TestEnv::new();
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(
+        deposit.withdrawal_requested_at,
+        env.svm.clock().unix_timestamp
+    );
+
+    let result = env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::WithdrawalAlreadyRequested);
+}
+
+#[test]
+fn only_owner_can_request() {
+    let mut env = TestEnv::new();
+    let owner = env.new_user();
+    let other = env.new_user();
+    let nft = env.deposit(&owner);
+
+    let result = env.process(
+        &[instructions::request_withdrawal(&other.pubkey(), &nft.mint)],
+        &[&other],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidOwner);
+}
+
+#[test]
+fn approve_sends_locked_nft_to_owner() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.lock(&user.pubkey(), &nft.mint);
+
+    let updater = env.updater.insecure_clone();
+    let result = env.process(
+        &[approve_withdrawal_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&updater],
+    );
+    assert_shipment_error(result, ShipmentError::WithdrawalNotRequested);
+
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+    env.process(
+        &[approve_withdrawal_ix(
+            &updater.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&updater],
+    )
+    .unwrap();
+
+    assert_eq!(env.token_balance(&nft.token_account), 1);
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.status, 0);
+    assert!(!deposit.locked);
+    assert_eq!(deposit.withdrawal_requested_at, 0);
+    let pool: UserPool = env.svm.get(&pda::user_pool(&user.pubkey()).0);
+    assert_eq!(pool.deposit_count, 0);
+}
+
+#[test]
+fn only_updater_can_answer() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+
+    let result = env.process(
+        &[approve_withdrawal_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidUpdater);
+
+    let result = env.process(
+        &[instructions::deny_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidUpdater);
+}
+
+#[test]
+fn deny_clears_request() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.lock(&user.pubkey(), &nft.mint);
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+
+    let updater = env.updater.insecure_clone();
+    env.process(
+        &[instructions::deny_withdrawal(&updater.pubkey(), &nft.mint)],
+        &[&updater],
+    )
+    .unwrap();
+
+    let deposit: NftDeposit = env.svm.get(&pda::nft_deposit(&nft.mint).0);
+    assert_eq!(deposit.withdrawal_requested_at, 0);
+    assert_eq!(deposit.status, 1);
+
+    // A denied request no longer lifts the lock
+    env.svm.warp_time(DEFAULT_WITHDRAWAL_GRACE_PERIOD);
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DisabledWithdrawal);
+}
+
+#[test]
+fn owner_can_force_withdraw_after_grace_period() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let nft = env.deposit(&user);
+    env.lock(&user.pubkey(), &nft.mint);
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+
+    env.svm.warp_time(DEFAULT_WITHDRAWAL_GRACE_PERIOD - 1);
+    let result = env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::DisabledWithdrawal);
+
+    env.svm.warp_time(1);
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+    assert_eq!(
+        env.token_balance(&get_associated_token_address(&user.pubkey(), &nft.mint)),
+        1
+    );
+}
+
+#[test]
+fn admin_changes_grace_period() {
+    let mut env = TestEnv::new();
+    let collection = env.collection;
+    let user = env.new_user();
+    let admin = env.super_admin.pubkey();
+
+    let result = env.process(
+        &[instructions::change_grace_period(&user.pubkey(), 60)],
+        &[&user],
+    );
+    assert_shipment_error(result, ShipmentError::InvalidAdmin);
+
+    let result = env.process(&[instructions::change_grace_period(&admin, -1)], &[]);
+    assert_shipment_error(result, ShipmentError::InvalidGracePeriod);
+
+    env.process(&[instructions::change_grace_period(&admin, 60)], &[])
+        .unwrap();
+    let global: GlobalPool = env.svm.get(&pda::global_pool().0);
+    assert_eq!(global.withdrawal_grace_period, 60);
+
+    let nft = env.deposit(&user);
+    env.lock(&user.pubkey(), &nft.mint);
+    env.process(
+        &[instructions::request_withdrawal(&user.pubkey(), &nft.mint)],
+        &[&user],
+    )
+    .unwrap();
+    env.svm.warp_time(60);
+    env.process(
+        &[withdraw_owner_ix(
+            &user.pubkey(),
+            &user.pubkey(),
+            &nft.mint,
+            &collection,
+        )],
+        &[&user],
+    )
+    .unwrap();
+}